@@ -121,6 +121,45 @@ impl App {
         self
     }
 
+    pub fn sub_command(&self, name: &str) -> Option<&App> {
+        self.subcommands.iter().find(|cmd| cmd.name == name)
+    }
+
+    /// Names of the sub commands together with their description
+    pub fn sub_commands(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.subcommands
+            .iter()
+            .map(|cmd| (cmd.name.as_str(), cmd.about.as_str()))
+    }
+
+    /// Every way to write a flag or option, like `--long` and `-s`, together with its help text
+    pub fn switches(&self) -> Vec<(String, &str)> {
+        let mut output = Vec::new();
+        let flags = self
+            .flags
+            .iter()
+            .map(|flag| (&flag.long, flag.short, flag.help.as_str()));
+        let options = self
+            .options
+            .iter()
+            .map(|opt| (&opt.long, opt.short, opt.help.as_str()));
+        for (long, short, help) in flags.chain(options) {
+            if let Some(long) = long {
+                output.push((format!("--{long}"), help));
+            }
+            if let Some(short) = short {
+                output.push((format!("-{short}"), help));
+            }
+        }
+        output.push((String::from("--help"), "Display this help message"));
+        output.push((String::from("-h"), "Display this help message"));
+        if self.version.is_some() {
+            output.push((String::from("--version"), "Print version info"));
+            output.push((String::from("-v"), "Print version info"));
+        }
+        output
+    }
+
     fn validate_naming(&self, name: &str, long: Option<&str>, short: Option<char>) -> bool {
         if long.is_none() && short.is_none() {
             return true;
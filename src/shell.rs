@@ -1,37 +1,48 @@
 use std::{
     collections::HashMap,
     fs::{self, OpenOptions},
-    io::{self, Write},
+    io::{self, IsTerminal, Write},
+    mem,
     path::{Path, PathBuf},
+    ptr::NonNull,
     rc::Rc,
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc, Mutex, MutexGuard,
     },
+    time::Instant,
 };
 
 use crossterm::{style::Stylize, terminal};
 use directories::{ProjectDirs, UserDirs};
 use miette::{Diagnostic, GraphicalReportHandler};
-use rustyline::{config::BellStyle, error::ReadlineError, Editor};
+use rustyline::{config::BellStyle, error::ReadlineError, Editor, KeyEvent};
 
+pub mod abbreviations;
 pub mod builtins;
+pub mod completions;
 pub mod dir_history;
+pub mod editor;
+pub mod hooks;
 pub mod parser;
 pub mod stream;
 pub mod value;
 use parser::{shell_error::ShellErrorKind, Parser};
 use subprocess::ExitStatus;
 use tracing::instrument;
-use value::Value;
+use value::{overflow::Overflow, Value};
 mod frame;
 use frame::Frame;
 mod hello;
 
 use self::{
+    abbreviations::Abbreviation,
+    completions::ExternalCompleter,
     dir_history::DirHistory,
-    helper::EditorHelper,
+    editor::{EditorOptions, KeyBinding},
+    helper::{is_incomplete_line, EditorHelper},
     history::JsonHistory,
+    hooks::{Hook, HookEvent},
     parser::{
         ast::{context::Context, expr::closure::Closure},
         shell_error::ShellError,
@@ -51,14 +62,31 @@ pub struct Shell {
     child_id: Arc<Mutex<Option<u32>>>,
     stack: Frame,
     aliases: HashMap<String, String>,
+    abbreviations: Vec<Abbreviation>,
+    completers: HashMap<String, ExternalCompleter>,
     recursion_limit: usize,
     interrupt: Arc<AtomicBool>,
     args: Vec<String>,
-    editor: Editor<EditorHelper, JsonHistory>,
+    /// Taken out while a line is read so that completers can borrow the shell
+    editor: Option<Editor<EditorHelper, JsonHistory>>,
     interactive: bool,
     pub dir_history: DirHistory,
     print_ast: bool,
+    overflow: Overflow,
     prompt: Option<Rc<(Rc<Closure>, Frame)>>,
+    rprompt: Option<Rc<(Rc<Closure>, Frame)>>,
+    transient_prompt: Option<Rc<(Rc<Closure>, Frame)>>,
+    prompt_continuation: Option<Rc<(Rc<Closure>, Frame)>>,
+    hooks: Vec<Hook>,
+    // events whose hooks are running, so that a hook does not trigger its own event again
+    running_hooks: Vec<HookEvent>,
+    editor_options: EditorOptions,
+    key_bindings: Vec<KeyBinding>,
+    // keys bound in the editor by `configure_editor` the last time it ran
+    bound_keys: Vec<KeyEvent>,
+    /// The line that is edited when the next line is read split at the cursor
+    initial_line: (String, String),
+    last_match: Value,
 }
 
 impl Shell {
@@ -100,6 +128,7 @@ impl Shell {
         let mut editor = Editor::with_history(config, JsonHistory::with_config(config)).unwrap();
         editor.set_helper(Some(helper::EditorHelper::new()));
         let _ = editor.load_history(&history_path(&project_dirs));
+        let dir_history = DirHistory::new(frecency_path(&project_dirs));
 
         Shell {
             running: true,
@@ -109,14 +138,27 @@ impl Shell {
             child_id,
             stack: Frame::default(),
             aliases: HashMap::new(),
+            abbreviations: Vec::new(),
+            completers: HashMap::new(),
             recursion_limit: 1000,
             interrupt,
             args,
-            editor,
+            editor: Some(editor),
             interactive: false,
-            dir_history: DirHistory::new(),
+            dir_history,
             print_ast: false,
+            overflow: Overflow::default(),
             prompt: None,
+            rprompt: None,
+            transient_prompt: None,
+            prompt_continuation: None,
+            hooks: Vec::new(),
+            running_hooks: Vec::new(),
+            editor_options: EditorOptions::default(),
+            key_bindings: Vec::new(),
+            bound_keys: Vec::new(),
+            initial_line: Default::default(),
+            last_match: Value::Null,
         }
     }
 
@@ -157,6 +199,18 @@ impl Shell {
                 &mut output,
                 ValueStream::new(),
             );
+
+            let abbreviations_path = self.abbreviations_path();
+            if abbreviations_path.is_file() {
+                let abbreviations = std::fs::read_to_string(&abbreviations_path)
+                    .map_err(|e| ShellErrorKind::Io(Some(abbreviations_path.to_path_buf()), e))?;
+                self.run_src(
+                    abbreviations_path.to_string_lossy().to_string(),
+                    abbreviations,
+                    &mut output,
+                    ValueStream::new(),
+                );
+            }
             output.end();
         }
         Ok(())
@@ -190,6 +244,48 @@ impl Shell {
         }
     }
 
+    /// Run a closure that the shell calls on its own, like a prompt, hook or key binding, with
+    /// its output going to `output`. It has no effect on the exit status and an error is
+    /// reported before it is returned.
+    pub(super) fn run_closure(
+        &mut self,
+        closure: &(Rc<Closure>, Frame),
+        arguments: Vec<Value>,
+        output: &mut OutputStream,
+    ) -> Result<(), ()> {
+        let (closure, frame) = closure;
+        let mut ctx = Context {
+            frame: frame.clone(),
+            shell: self,
+            output,
+            input: &mut ValueStream::new(),
+            src: closure.src.clone(),
+        };
+
+        let status = ctx.shell.status();
+        let res = closure.eval(&mut ctx, arguments.into_iter());
+        ctx.shell.exit_status = status;
+        match res {
+            Ok(()) => Ok(()),
+            Err(ShellErrorKind::Exit) => Err(()),
+            Err(err) => {
+                report_error(ShellError::new(err, closure.src.clone()));
+                Err(())
+            }
+        }
+    }
+
+    /// Like [Shell::run_closure] but the output is captured and returned
+    pub(super) fn eval_closure(
+        &mut self,
+        closure: &(Rc<Closure>, Frame),
+        arguments: Vec<Value>,
+    ) -> Result<ValueStream, ()> {
+        let mut output = OutputStream::new_capture();
+        self.run_closure(closure, arguments, &mut output)?;
+        Ok(output.into_value_stream())
+    }
+
     pub fn validate_syntax(&mut self, name: String, src: String) -> bool {
         let (_, errors) = Parser::new(name, src).parse();
         if errors.is_empty() {
@@ -216,24 +312,39 @@ impl Shell {
                 current_dir_str().replace(&self.home_dir().to_string_lossy().to_string(), "~");
             let _ = crossterm::execute!(term, terminal::SetTitle(format!("Crust: {info}")));
 
-            self.editor.helper_mut().unwrap().prompt = self.prompt();
-            let stripped = strip_ansi_escapes::strip_str(&self.editor.helper_mut().unwrap().prompt);
+            self.run_hooks(HookEvent::PrePrompt, &[]);
+            let prompt = self.prompt();
+            let rprompt = self.render_prompt(self.rprompt.clone());
+            let continuation = self.render_prompt(self.prompt_continuation.clone());
+            let mut editor = self.editor.take().unwrap();
+            let helper = editor.helper_mut().unwrap();
+            helper.prompt = prompt;
+            helper.rprompt = rprompt.unwrap_or_default();
+            helper.set_continuation_prompt(continuation);
+            helper.frame = self.stack.clone();
+            helper.aliases = self.aliases.clone();
+            helper.frecency = Some(self.dir_history.frecency.clone());
+            helper.home_dir = self.home_dir();
+            self.configure_editor(&mut editor);
+            self.editor = Some(editor);
 
             let mut output = OutputStream::new_output();
-
-            let readline = self.editor.readline(&stripped);
-            match readline {
+            match self.read_input() {
                 Ok(line) => {
-                    if line.is_empty() {
+                    let input = self.enter_input(&line);
+                    if input.is_empty() {
                         continue;
                     }
-
-                    let _ = self.editor.add_history_entry(&line);
-                    self.save_history();
-                    self.run_src(String::from("shell"), line, &mut output, ValueStream::new());
+                    self.run_line(input, &mut output);
                 }
                 Err(ReadlineError::Interrupted) => {
                     println!("{}", "^C".red());
+                    self.editor_mut().helper_mut().unwrap().reset_input();
+                }
+                // ctrl-d only cancels an incomplete input
+                Err(ReadlineError::Eof) if self.editor_mut().helper().unwrap().is_continued() => {
+                    println!("{}", "^D".red());
+                    self.editor_mut().helper_mut().unwrap().reset_input();
                 }
                 Err(ReadlineError::Eof) => {
                     println!("{}", "^D".red());
@@ -250,30 +361,121 @@ impl Shell {
         Ok(self.exit_status)
     }
 
-    fn prompt(&mut self) -> String {
-        if let Some(prompt) = &self.prompt {
-            let (closure, frame) = &*prompt.clone();
-            let mut output = OutputStream::new_capture();
-            let mut ctx = Context {
-                frame: frame.clone(),
-                shell: self,
-                output: &mut output,
-                input: &mut ValueStream::new(),
-                src: closure.src.clone(),
-            };
+    // reads the input with the editor, an incomplete input is continued on the next line. A bound
+    // key or an abbreviation is handled by the shell before the line is edited again, without
+    // drawing a new prompt.
+    fn read_input(&mut self) -> rustyline::Result<String> {
+        let mut initial = mem::take(&mut self.initial_line);
+        loop {
+            let mut editor = self.editor.take().unwrap();
+            editor.helper_mut().unwrap().shell = NonNull::new(self as *mut Shell);
+            let readline = read_line(&mut editor, initial);
+            editor.helper_mut().unwrap().shell = None;
+            self.editor = Some(editor);
+
+            let helper = self.editor_mut().helper_mut().unwrap();
+            let line = match readline {
+                Err(ReadlineError::Interrupted) => {
+                    // the next read draws the prompt and the line again
+                    let columns = terminal::size().map(|(columns, _)| columns as usize);
+                    if let Some((key, line, pos, hint)) = helper.take_binding_request() {
+                        if let Ok(columns) = columns {
+                            helper.clear_prompt(&(line.clone() + &hint), columns);
+                        }
+                        initial = self.run_key_binding(key, line, pos);
+                        continue;
+                    }
+                    let Some((request, hint)) = helper.take_abbr_request() else {
+                        return Err(ReadlineError::Interrupted);
+                    };
+                    if let Ok(columns) = columns {
+                        helper.clear_prompt(&(request.line.clone() + &hint), columns);
+                    }
+                    let accept = request.accept;
+                    initial = self.expand_abbr_request(request);
+                    if !accept {
+                        continue;
+                    }
 
-            let status = ctx.shell.status();
-            let res = closure.eval(&mut ctx, Vec::new().into_iter());
-            match res {
-                Ok(_) => {
-                    // Prompt should not have any effect on the status variable so we reset it
-                    ctx.shell.exit_status = status;
-                    return output.to_string();
+                    // the expanded line is entered in place of the one on screen
+                    let line = mem::take(&mut initial.0) + &initial.1;
+                    let helper = self.editor_mut().helper().unwrap();
+                    let highlighted = helper.highlight_edited(&line);
+                    println!("{}{highlighted}", helper.current_prompt());
+                    line
                 }
-                Err(err) => report_error(ShellError::new(err, closure.src.clone())),
+                readline => readline?,
+            };
+
+            let helper = self.editor_mut().helper_mut().unwrap();
+            if !is_incomplete_line(&helper.input(&line)) {
+                return Ok(line);
             }
+            initial = (helper.continue_input(&line), String::new());
+        }
+    }
+
+    // runs an entered line and saves it in the history
+    fn run_line(&mut self, line: String, output: &mut OutputStream) {
+        let added = self.editor_mut().add_history_entry(&line).unwrap_or(false);
+        self.run_hooks(HookEvent::PreExec, &[Value::from(line.clone())]);
+        let start = Instant::now();
+        self.run_src(String::from("shell"), line, output, ValueStream::new());
+        output.end();
+        let duration = start.elapsed().as_nanos().try_into().unwrap_or(i64::MAX);
+        // the line is saved once it is known how it finished
+        if added {
+            let status = self.exit_status;
+            self.editor_mut()
+                .history_mut()
+                .finish_last(status, duration);
+        }
+        self.save_history();
+        self.run_hooks(
+            HookEvent::PostExec,
+            &[Value::Int(self.exit_status), Value::Duration(duration)],
+        );
+    }
+
+    fn prompt(&mut self) -> String {
+        match self.render_prompt(self.prompt.clone()) {
+            Some(prompt) => prompt,
+            None => self.default_prompt(),
+        }
+    }
+
+    // returns `None` when there is no closure or it failed
+    fn render_prompt(&mut self, prompt: Option<Rc<(Rc<Closure>, Frame)>>) -> Option<String> {
+        let output = self.eval_closure(&*prompt?, Vec::new()).ok()?;
+        Some(output.to_string())
+    }
+
+    // the whole input after `line` was entered, the pending lines of the editor are reset
+    fn enter_input(&mut self, line: &str) -> String {
+        self.transient_prompt(line);
+        let helper = self.editor_mut().helper_mut().unwrap();
+        let input = helper.input(line);
+        helper.reset_input();
+        input
+    }
+
+    // replaces the prompt and the input that was just entered with the transient prompt followed
+    // by the input, `line` is its last line
+    fn transient_prompt(&mut self, line: &str) {
+        if self.transient_prompt.is_none() || !io::stdout().is_terminal() {
+            return;
         }
-        self.default_prompt()
+        let Ok((columns, _)) = terminal::size() else {
+            return;
+        };
+        let Some(transient) = self.render_prompt(self.transient_prompt.clone()) else {
+            return;
+        };
+
+        let helper = self.editor_mut().helper().unwrap();
+        helper.clear_input(line, columns as usize);
+        let highlighted = helper.highlight_line(&helper.input(line));
+        println!("{transient}{highlighted}");
     }
 
     fn default_prompt(&self) -> String {
@@ -296,6 +498,16 @@ impl Shell {
             .collect::<PathBuf>()
     }
 
+    /// Written by `abbr add --save`, run after the config file
+    pub fn abbreviations_path(&self) -> PathBuf {
+        [
+            self.project_dirs.config_dir(),
+            Path::new("abbreviations.crust"),
+        ]
+        .iter()
+        .collect::<PathBuf>()
+    }
+
     pub fn home_dir(&self) -> PathBuf {
         self.user_dirs.home_dir().to_path_buf()
     }
@@ -341,7 +553,13 @@ impl Shell {
     }
 
     fn save_history(&mut self) {
-        let _ = self.editor.append_history(&self.history_path());
+        let path = self.history_path();
+        let _ = self.editor_mut().append_history(&path);
+    }
+
+    // the editor is only missing while a line is read
+    fn editor_mut(&mut self) -> &mut Editor<EditorHelper, JsonHistory> {
+        self.editor.as_mut().unwrap()
     }
 
     pub fn set_interactive(&mut self, interactive: bool) {
@@ -363,6 +581,37 @@ pub fn current_dir_str() -> String {
     current_dir_path().to_string_lossy().to_string()
 }
 
+// ctrl-r ends the line to open the history search, the picked line is edited after it
+fn read_line(
+    editor: &mut Editor<EditorHelper, JsonHistory>,
+    mut initial: (String, String),
+) -> rustyline::Result<String> {
+    loop {
+        // rustyline lays the line out after the prompt without its colors
+        let prompt = strip_ansi_escapes::strip_str(editor.helper().unwrap().current_prompt());
+        let readline = editor.readline_with_initial(&prompt, (&initial.0, &initial.1));
+        let helper = editor.helper().unwrap();
+        let Some((line, hint)) = helper.take_search_request() else {
+            return readline;
+        };
+
+        // the next read draws the prompt and the line again
+        if let Ok((columns, _)) = terminal::size() {
+            helper.clear_prompt(&(line.clone() + &hint), columns as usize);
+        }
+        initial = match helper::search_history(editor.history(), &line) {
+            Ok(Some(picked)) => (picked, String::new()),
+            _ => (line, String::new()),
+        };
+    }
+}
+
+pub fn frecency_path(project_dirs: &ProjectDirs) -> PathBuf {
+    [project_dirs.data_dir(), Path::new("dirs.jsonl")]
+        .iter()
+        .collect()
+}
+
 pub fn history_path(project_dirs: &ProjectDirs) -> PathBuf {
     [project_dirs.data_dir(), Path::new("history.jsonl")]
         .iter()
@@ -371,10 +620,14 @@ pub fn history_path(project_dirs: &ProjectDirs) -> PathBuf {
 
 pub fn report_error(error: impl Diagnostic) {
     reset_cursor();
+    eprintln!("{}", render_error(error));
+}
+
+pub fn render_error(error: impl Diagnostic) -> String {
     let mut output = String::new();
     let report = GraphicalReportHandler::new();
     report.render_report(&mut output, &error).unwrap();
-    eprintln!("{}", output);
+    output
 }
 
 pub fn reset_cursor() {
@@ -0,0 +1,277 @@
+use std::{collections::HashMap, fs, io, path::Path, rc::Rc};
+
+use super::{
+    frame::Frame,
+    helper::AbbrRequest,
+    parser::{
+        ast::expr::closure::Closure,
+        shell_error::{ShellError, ShellErrorKind},
+    },
+    report_error,
+    value::Value,
+    Shell,
+};
+
+/// What an abbreviation is replaced with
+#[derive(Debug, Clone)]
+pub enum Expansion {
+    Text(String),
+    /// Gets the abbreviation and returns the text, or nothing to keep it
+    Closure(Rc<(Rc<Closure>, Frame)>),
+}
+
+impl Expansion {
+    pub fn to_value(&self) -> Value {
+        match self {
+            Self::Text(text) => Value::from(text.as_str()),
+            Self::Closure(closure) => Value::Closure(closure.clone()),
+        }
+    }
+}
+
+/// A word that the editor expands when space or enter is pressed after it
+#[derive(Debug, Clone)]
+pub struct Abbreviation {
+    pub name: String,
+    pub expansion: Expansion,
+    /// Expanded in any position instead of only as a command
+    pub anywhere: bool,
+}
+
+/// The names of the abbreviations and if they are expanded anywhere, the editor finds them and
+/// leaves the expanding to the shell
+pub type EditorAbbreviations = HashMap<String, bool>;
+
+/// Characters that can not be in the name of an abbreviation
+pub const FORBIDDEN: &[char] = &[
+    '\'', '"', '$', '(', ')', '{', '}', '[', ']', '|', ';', '&', '#',
+];
+
+impl Shell {
+    /// Add an abbreviation, replacing one with the same name
+    pub fn add_abbreviation(&mut self, abbreviation: Abbreviation) {
+        self.abbreviations
+            .retain(|added| added.name != abbreviation.name);
+        self.abbreviations.push(abbreviation);
+    }
+
+    /// Remove an abbreviation, returns false if there is none with the name
+    pub fn remove_abbreviation(&mut self, name: &str) -> bool {
+        let len = self.abbreviations.len();
+        self.abbreviations.retain(|added| added.name != name);
+        len != self.abbreviations.len()
+    }
+
+    pub fn abbreviations(&self) -> &[Abbreviation] {
+        &self.abbreviations
+    }
+
+    pub(super) fn editor_abbreviations(&self) -> EditorAbbreviations {
+        self.abbreviations
+            .iter()
+            .map(|abbreviation| (abbreviation.name.clone(), abbreviation.anywhere))
+            .collect()
+    }
+
+    /// The text an abbreviation expands to, `None` when there is no abbreviation with the name
+    /// or its closure returned nothing or failed
+    pub(super) fn expand_abbreviation(&mut self, name: &str) -> Option<String> {
+        let abbreviation = self.abbreviations.iter().find(|added| added.name == name)?;
+        let closure = match &abbreviation.expansion {
+            Expansion::Text(text) => return Some(text.clone()),
+            Expansion::Closure(closure) => closure.clone(),
+        };
+
+        let args = match closure.0.parameters.len() {
+            0 => Vec::new(),
+            _ => vec![Value::from(name)],
+        };
+        match self.eval_closure(&closure, args).ok()?.unpack() {
+            Value::Null => None,
+            Value::String(text) => Some(text.to_string()),
+            value => {
+                let message = format!(
+                    "Abbreviation closure must return a string or nothing, got a {}",
+                    value.to_type()
+                );
+                report_error(ShellError::new(
+                    ShellErrorKind::Basic("Abbr Error", message),
+                    closure.0.src.clone(),
+                ));
+                None
+            }
+        }
+    }
+
+    /// Expand an abbreviation that was found by the editor, returns the line before and after
+    /// the cursor. A space follows the expansion unless the line is entered.
+    pub(super) fn expand_abbr_request(&mut self, request: AbbrRequest) -> (String, String) {
+        let AbbrRequest {
+            line,
+            start,
+            pos,
+            accept,
+        } = request;
+        let word = &line[start..pos];
+        let text = self.expand_abbreviation(word);
+        let mut left = line[..start].to_string() + text.as_deref().unwrap_or(word);
+        if !accept {
+            left.push(' ');
+        }
+        (left, line[pos..].to_string())
+    }
+
+    /// Write the `abbr add` line of an abbreviation to the saved abbreviations so it is added
+    /// in every shell, a line saved before for the same name is replaced
+    pub fn save_abbreviation(
+        &self,
+        name: &str,
+        text: &str,
+        anywhere: bool,
+    ) -> Result<(), ShellErrorKind> {
+        save_to(&self.abbreviations_path(), name, text, anywhere)
+    }
+
+    /// Remove an abbreviation from the saved abbreviations, returns false if it was not saved
+    pub fn forget_abbreviation(&self, name: &str) -> Result<bool, ShellErrorKind> {
+        forget_from(&self.abbreviations_path(), name)
+    }
+}
+
+// saved abbreviations are kept out of the config file, every line of this file is written here
+const SAVED_HEADER: &str =
+    "# Written by `abbr add --save` and `abbr remove --save`, changes here are overwritten\n";
+
+fn save_to(path: &Path, name: &str, text: &str, anywhere: bool) -> Result<(), ShellErrorKind> {
+    let (mut saved, _) = without_saved(&read_saved(path)?, name);
+    saved.push_str(&config_line(name, text, anywhere));
+    saved.push('\n');
+    fs::write(path, saved).map_err(|e| ShellErrorKind::Io(Some(path.to_path_buf()), e))
+}
+
+fn forget_from(path: &Path, name: &str) -> Result<bool, ShellErrorKind> {
+    let (saved, removed) = without_saved(&read_saved(path)?, name);
+    if removed {
+        fs::write(path, saved).map_err(|e| ShellErrorKind::Io(Some(path.to_path_buf()), e))?;
+    }
+    Ok(removed)
+}
+
+// nothing is saved when there is no file
+fn read_saved(path: &Path) -> Result<String, ShellErrorKind> {
+    match fs::read_to_string(path) {
+        Ok(saved) => Ok(saved),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(ShellErrorKind::Io(Some(path.to_path_buf()), e)),
+    }
+}
+
+fn config_line(name: &str, text: &str, anywhere: bool) -> String {
+    let mut quoted = String::from('"');
+    for c in text.chars() {
+        match c {
+            // a backslash can only be written as a hex escape
+            '\\' => quoted.push_str("\\x5c"),
+            '"' | '$' | '(' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            '\r' => quoted.push_str("\\r"),
+            '\0' => quoted.push_str("\\0"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+
+    match anywhere {
+        true => format!("abbr add {name} {quoted} --position anywhere"),
+        false => format!("abbr add {name} {quoted}"),
+    }
+}
+
+// the saved lines without the one for `name` after a fresh header, and if there was one
+fn without_saved(saved: &str, name: &str) -> (String, bool) {
+    let prefix = format!("abbr add {name} ");
+    let mut removed = false;
+    let mut kept = String::from(SAVED_HEADER);
+    for line in saved.lines().filter(|line| line.starts_with("abbr add ")) {
+        if line.starts_with(&prefix) {
+            removed = true;
+        } else {
+            kept.push_str(line);
+            kept.push('\n');
+        }
+    }
+    (kept, removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_line_test() {
+        assert_eq!(
+            config_line("gco", "git checkout", false),
+            "abbr add gco \"git checkout\""
+        );
+        assert_eq!(
+            config_line("L", "| less", true),
+            "abbr add L \"| less\" --position anywhere"
+        );
+        assert_eq!(
+            config_line("e", "echo \"$HOME\" (pwd) \\s\n", false),
+            r#"abbr add e "echo \"\$HOME\" \(pwd) \x5cs\n""#
+        );
+    }
+
+    #[test]
+    fn without_saved_test() {
+        let saved =
+            "# old header\nabbr add g \"git\"\nabbr add gc \"git commit\"\nabbr add g \"git\"";
+        assert_eq!(
+            without_saved(saved, "g"),
+            (format!("{SAVED_HEADER}abbr add gc \"git commit\"\n"), true)
+        );
+        assert_eq!(
+            without_saved(saved, "x"),
+            (
+                format!("{SAVED_HEADER}abbr add g \"git\"\nabbr add gc \"git commit\"\nabbr add g \"git\"\n"),
+                false
+            )
+        );
+    }
+
+    #[test]
+    fn save_test() {
+        let dir = std::env::temp_dir().join(format!("crust-abbr-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        // hand written abbreviations in the config are never touched
+        let config = "abbr add g \"git\"\nabbr add gco {|name|\n    abbr add g \"inside\"\n    \"git checkout\"\n}\n";
+        let config_path = dir.join("config.crust");
+        fs::write(&config_path, config).unwrap();
+        let path = dir.join("abbreviations.crust");
+
+        save_to(&path, "g", "git", false).unwrap();
+        save_to(&path, "gco", "git checkout", false).unwrap();
+        save_to(&path, "g", "git status", true).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            format!(
+                "{SAVED_HEADER}abbr add gco \"git checkout\"\nabbr add g \"git status\" --position anywhere\n"
+            )
+        );
+
+        assert!(forget_from(&path, "gco").unwrap());
+        assert!(!forget_from(&path, "gco").unwrap());
+        assert!(!forget_from(&dir.join("missing.crust"), "g").unwrap());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            format!("{SAVED_HEADER}abbr add g \"git status\" --position anywhere\n")
+        );
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), config);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
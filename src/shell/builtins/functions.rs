@@ -1,86 +1,324 @@
 use std::{
     fs,
     io::{self, Write},
+    iter,
     path::Path,
+    rc::Rc,
 };
 
+use once_cell::sync::Lazy;
 use phf::*;
 
 use crate::{
-    parser::{ast::context::Context, shell_error::ShellErrorKind},
-    shell::value::SpannedValue,
+    argparse::App,
+    parser::{
+        ast::{context::Context, expr::closure::Closure},
+        lexer::token::span::Span,
+        shell_error::ShellErrorKind,
+    },
+    shell::{
+        frame::Frame,
+        stream::OutputStream,
+        value::{
+            cell_path::{parse_path, PathMember},
+            table::Table,
+            SpannedValue, Type, Value,
+        },
+    },
 };
 
+mod abbr;
 mod alias;
+mod append;
 mod assert;
 mod cd;
+mod chunks;
 mod clear;
+mod complete;
+mod date;
+mod decode;
+mod delete;
+mod difference;
+mod dirs;
 mod do_closure;
 mod echo;
+mod encode;
+mod enumerate;
 mod env;
 mod exit;
 mod filter;
 mod first;
+mod flatten;
+mod from;
+mod get;
 mod glob;
+mod hash;
 mod help;
 mod history;
+mod hook;
+mod http;
 mod import;
 mod input;
+mod intersect;
+mod join;
+mod keybind;
+mod kill;
 mod last;
 mod len;
 mod lines;
 mod load;
+mod lower;
+mod ls;
 mod map;
+mod math;
+mod merge;
 mod open;
+mod pad;
+mod parse;
+mod path;
+mod pivot;
+mod popd;
+mod prepend;
 mod print;
+mod ps;
+mod pushd;
 mod pwd;
+mod replace;
+mod reverse;
 mod save;
+mod set;
 mod shuffle;
+mod split;
+mod substring;
 mod time;
+mod to;
+mod transpose;
+mod trim;
 mod unalias;
+mod union;
+mod uniq_by;
 mod unique;
+mod upper;
+mod upsert;
+mod window;
+mod z;
+mod zi;
+mod zip;
 
 pub type BulitinFn = fn(&mut Context, Vec<SpannedValue>) -> Result<(), ShellErrorKind>;
 
-static BUILTIN_FUNCTIONS: phf::Map<&'static str, BulitinFn> = phf_map! {
-    "alias" => alias::alias,
-    "assert" => assert::assert,
-    "cd" => cd::cd,
-    "clear" => clear::clear,
-    "do" => do_closure::do_closure,
-    "echo" => echo::echo,
-    "env" => env::env,
-    "exit" => exit::exit,
-    "filter" => filter::filter,
-    "first" => first::first,
-    "glob" => glob::glob,
-    "help" => help::help,
-    "history" => history::history,
-    "import" => import::import,
-    "input" => input::input,
-    "last" => last::last,
-    "len" => len::len,
-    "lines" => lines::lines,
-    "load" => load::load,
-    "map" => map::map,
-    "open" => open::open,
-    "print" => print::print,
-    "pwd" => pwd::pwd,
-    "save" => save::save,
-    "shuffle" => shuffle::shuffle,
-    "time" => time::time,
-    "unalias" => unalias::unalias,
-    "unique" => unique::unique,
+// the argparse app of a builtin is kept next to it so that completion can list its flags
+pub struct Builtin(BulitinFn, Option<&'static Lazy<App>>);
+
+static BUILTIN_FUNCTIONS: phf::Map<&'static str, Builtin> = phf_map! {
+    "abbr" => Builtin(abbr::abbr, Some(&abbr::APP)),
+    "alias" => Builtin(alias::alias, Some(&alias::APP)),
+    "append" => Builtin(append::append, Some(&append::APP)),
+    "assert" => Builtin(assert::assert, Some(&assert::APP)),
+    "cd" => Builtin(cd::cd, Some(&cd::APP)),
+    "chunks" => Builtin(chunks::chunks, Some(&chunks::APP)),
+    "clear" => Builtin(clear::clear, Some(&clear::APP)),
+    "complete" => Builtin(complete::complete, Some(&complete::APP)),
+    "date" => Builtin(date::date, Some(&date::APP)),
+    "decode" => Builtin(decode::decode, Some(&decode::APP)),
+    "delete" => Builtin(delete::delete, Some(&delete::APP)),
+    "difference" => Builtin(difference::difference, Some(&difference::APP)),
+    "dirs" => Builtin(dirs::dirs, Some(&dirs::APP)),
+    "do" => Builtin(do_closure::do_closure, Some(&do_closure::APP)),
+    "echo" => Builtin(echo::echo, None),
+    "encode" => Builtin(encode::encode, Some(&encode::APP)),
+    "enumerate" => Builtin(enumerate::enumerate, Some(&enumerate::APP)),
+    "env" => Builtin(env::env, Some(&env::APP)),
+    "exit" => Builtin(exit::exit, Some(&exit::APP)),
+    "filter" => Builtin(filter::filter, Some(&filter::APP)),
+    "first" => Builtin(first::first, Some(&first::APP)),
+    "flatten" => Builtin(flatten::flatten, Some(&flatten::APP)),
+    "from" => Builtin(from::from, Some(&from::APP)),
+    "get" => Builtin(get::get, Some(&get::APP)),
+    "glob" => Builtin(glob::glob, Some(&glob::APP)),
+    "hash" => Builtin(hash::hash, Some(&hash::APP)),
+    "help" => Builtin(help::help, None),
+    "history" => Builtin(history::history, Some(&history::APP)),
+    "hook" => Builtin(hook::hook, Some(&hook::APP)),
+    "http" => Builtin(http::http, Some(&http::APP)),
+    "import" => Builtin(import::import, Some(&import::APP)),
+    "input" => Builtin(input::input, Some(&input::APP)),
+    "intersect" => Builtin(intersect::intersect, Some(&intersect::APP)),
+    "join" => Builtin(join::join, Some(&join::APP)),
+    "keybind" => Builtin(keybind::keybind, Some(&keybind::APP)),
+    "kill" => Builtin(kill::kill, Some(&kill::APP)),
+    "last" => Builtin(last::last, Some(&last::APP)),
+    "len" => Builtin(len::len, Some(&len::APP)),
+    "lines" => Builtin(lines::lines, Some(&lines::APP)),
+    "load" => Builtin(load::load, Some(&load::APP)),
+    "lower" => Builtin(lower::lower, Some(&lower::APP)),
+    "ls" => Builtin(ls::ls, Some(&ls::APP)),
+    "map" => Builtin(map::map, Some(&map::APP)),
+    "math" => Builtin(math::math, Some(&math::APP)),
+    "merge" => Builtin(merge::merge, Some(&merge::APP)),
+    "open" => Builtin(open::open, Some(&open::APP)),
+    "pad" => Builtin(pad::pad, Some(&pad::APP)),
+    "parse" => Builtin(parse::parse, Some(&parse::APP)),
+    "path" => Builtin(path::path, Some(&path::APP)),
+    "pivot" => Builtin(pivot::pivot, Some(&pivot::APP)),
+    "popd" => Builtin(popd::popd, Some(&popd::APP)),
+    "prepend" => Builtin(prepend::prepend, Some(&prepend::APP)),
+    "print" => Builtin(print::print, Some(&print::APP)),
+    "ps" => Builtin(ps::ps, Some(&ps::APP)),
+    "pushd" => Builtin(pushd::pushd, Some(&pushd::APP)),
+    "pwd" => Builtin(pwd::pwd, Some(&pwd::APP)),
+    "replace" => Builtin(replace::replace, Some(&replace::APP)),
+    "reverse" => Builtin(reverse::reverse, Some(&reverse::APP)),
+    "save" => Builtin(save::save, Some(&save::APP)),
+    "set" => Builtin(set::set, Some(&set::APP)),
+    "shuffle" => Builtin(shuffle::shuffle, Some(&shuffle::APP)),
+    "split" => Builtin(split::split, Some(&split::APP)),
+    "substring" => Builtin(substring::substring, Some(&substring::APP)),
+    "time" => Builtin(time::time, Some(&time::APP)),
+    "to" => Builtin(to::to, Some(&to::APP)),
+    "transpose" => Builtin(transpose::transpose, Some(&transpose::APP)),
+    "trim" => Builtin(trim::trim, Some(&trim::APP)),
+    "unalias" => Builtin(unalias::unalias, Some(&unalias::APP)),
+    "union" => Builtin(union::union, Some(&union::APP)),
+    "uniq-by" => Builtin(uniq_by::uniq_by, Some(&uniq_by::APP)),
+    "unique" => Builtin(unique::unique, Some(&unique::APP)),
+    "upper" => Builtin(upper::upper, Some(&upper::APP)),
+    "upsert" => Builtin(upsert::upsert, Some(&upsert::APP)),
+    "window" => Builtin(window::window, Some(&window::APP)),
+    "z" => Builtin(z::z, Some(&z::APP)),
+    "zi" => Builtin(zi::zi, Some(&zi::APP)),
+    "zip" => Builtin(zip::zip, Some(&zip::APP)),
 };
 
 pub fn get_builtin(command: &str) -> Option<BulitinFn> {
-    BUILTIN_FUNCTIONS.get(command).copied()
+    BUILTIN_FUNCTIONS.get(command).map(|builtin| builtin.0)
+}
+
+pub fn get_builtin_app(command: &str) -> Option<&'static App> {
+    BUILTIN_FUNCTIONS
+        .get(command)
+        .and_then(|builtin| builtin.1)
+        .map(|app| &**app)
 }
 
 pub fn get_builtins() -> impl Iterator<Item = &'static str> {
     BUILTIN_FUNCTIONS.keys().copied()
 }
 
+// string builtins accept either a single piped string or a list of strings
+pub fn map_strings(
+    input: Value,
+    mut f: impl FnMut(&str) -> Result<Value, ShellErrorKind>,
+) -> Result<Value, ShellErrorKind> {
+    match input {
+        Value::String(string) => f(&string),
+        Value::List(list) => {
+            let mut output = Vec::with_capacity(list.len());
+            for value in list.iter() {
+                match value {
+                    Value::String(string) => output.push(f(string)?),
+                    _ => {
+                        return Err(ShellErrorKind::InvalidPipelineInput {
+                            expected: Type::STRING,
+                            recived: value.to_type(),
+                        })
+                    }
+                }
+            }
+            Ok(Value::from(output))
+        }
+        _ => Err(ShellErrorKind::InvalidPipelineInput {
+            expected: Type::STRING | Type::LIST,
+            recived: input.to_type(),
+        }),
+    }
+}
+
+// byte builtins accept a string, binary data or a list of either
+pub fn map_bytes(
+    input: Value,
+    mut f: impl FnMut(&[u8]) -> Result<Value, ShellErrorKind>,
+) -> Result<Value, ShellErrorKind> {
+    match input {
+        Value::String(string) => f(string.as_bytes()),
+        Value::Binary(data) => f(&data),
+        Value::List(list) => {
+            let mut output = Vec::with_capacity(list.len());
+            for value in list.iter() {
+                match value {
+                    Value::String(string) => output.push(f(string.as_bytes())?),
+                    Value::Binary(data) => output.push(f(data)?),
+                    _ => {
+                        return Err(ShellErrorKind::InvalidPipelineInput {
+                            expected: Type::STRING | Type::BINARY,
+                            recived: value.to_type(),
+                        })
+                    }
+                }
+            }
+            Ok(Value::from(output))
+        }
+        _ => Err(ShellErrorKind::InvalidPipelineInput {
+            expected: Type::STRING | Type::BINARY | Type::LIST,
+            recived: input.to_type(),
+        }),
+    }
+}
+
+// list builtins accept a list, a range or a table whose rows are passed on as maps
+pub fn into_items(input: Value) -> Result<Vec<Value>, ShellErrorKind> {
+    match input {
+        Value::List(list) => Ok(Rc::unwrap_or_clone(list)),
+        Value::Range(range) => Ok((*range).clone().map(Value::from).collect()),
+        Value::Table(table) => Ok(table.iter().map(Value::from).collect()),
+        _ => Err(ShellErrorKind::InvalidPipelineInput {
+            expected: Type::LIST | Type::RANGE | Type::TABLE,
+            recived: input.to_type(),
+        }),
+    }
+}
+
+// rows that are all maps become a table again just like in a list literal
+pub fn from_items(items: Vec<Value>) -> Value {
+    if items.is_empty() || !items.iter().all(|item| matches!(item, Value::Map(_))) {
+        return Value::from(items);
+    }
+
+    let mut table = Table::new();
+    for item in items {
+        table.insert_map(Rc::unwrap_or_clone(item.unwrap_map()));
+    }
+    Value::from(table)
+}
+
+// calls a closure argument with a single value and returns everything it output
+pub fn call_closure(
+    ctx: &mut Context,
+    closure: &Rc<(Rc<Closure>, Frame)>,
+    value: Value,
+) -> Result<Value, ShellErrorKind> {
+    let (closure, frame) = &**closure;
+    let mut capture = OutputStream::new_capture();
+    let mut ctx = Context {
+        shell: ctx.shell,
+        frame: frame.clone(),
+        output: &mut capture,
+        input: ctx.input,
+        src: ctx.src.clone(),
+    };
+    closure.eval(&mut ctx, iter::once(value))?;
+    Ok(capture.into_value_stream().unpack())
+}
+
+// cell paths are given as a dotted string or as a single index
+pub fn cell_path_arg(arg: SpannedValue) -> (Vec<PathMember>, Span) {
+    let path = match arg.value {
+        Value::Int(index) => vec![PathMember::Index(index)],
+        value => parse_path(&value.unwrap_string()),
+    };
+    (path, arg.span)
+}
+
 pub fn read_file(path: impl AsRef<Path>) -> Result<String, ShellErrorKind> {
     let path = path.as_ref();
     fs::read_to_string(path)
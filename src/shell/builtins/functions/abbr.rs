@@ -0,0 +1,158 @@
+use std::rc::Rc;
+
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+
+use crate::{
+    argparse::{App, Arg, Flag, Opt, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::{
+        abbreviations::{Abbreviation, Expansion, FORBIDDEN},
+        value::{table::Table, SpannedValue, Type, Value},
+    },
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("abbr")
+        .about("Words that the line editor expands when space or enter is pressed after them")
+        .sub_cmd(
+            App::new("add")
+                .about("Add an abbreviation, a closure gets the abbreviation and returns the text or nothing to keep it")
+                .arg(
+                    Arg::new("NAME", Type::STRING)
+                        .required(true)
+                        .help("The word that is expanded"),
+                )
+                .arg(
+                    Arg::new("EXPANSION", Type::STRING | Type::CLOSURE)
+                        .required(true)
+                        .help("The text the word is replaced with or a closure"),
+                )
+                .opt(
+                    Opt::new("POSITION", Type::STRING)
+                        .long("position")
+                        .short('p')
+                        .help("Expand only as a command or anywhere, command by default"),
+                )
+                .flag(
+                    Flag::new("SAVE")
+                        .long("save")
+                        .short('s')
+                        .help("Also save the abbreviation for every new shell"),
+                ),
+        )
+        .sub_cmd(
+            App::new("remove")
+                .about("Remove an abbreviation")
+                .arg(
+                    Arg::new("NAME", Type::STRING)
+                        .required(true)
+                        .help("The word that is expanded"),
+                )
+                .flag(
+                    Flag::new("SAVE")
+                        .long("save")
+                        .short('s')
+                        .help("Also remove the abbreviation from the saved ones"),
+                ),
+        )
+        .sub_cmd(App::new("list").about("List the abbreviations"))
+});
+
+pub fn abbr(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let name = matches
+        .take_value("NAME")
+        .map(|name| name.value.unwrap_string().to_string());
+    let save = matches.conatins("SAVE");
+
+    match matches.sub_cmd() {
+        Some("add") => {
+            // safe because the arguments are required
+            let name = name.unwrap();
+            if name.is_empty()
+                || name.contains(|c: char| c.is_whitespace() || FORBIDDEN.contains(&c))
+            {
+                return Err(ShellErrorKind::Basic(
+                    "Abbr Error",
+                    format!("`{name}` can not be an abbreviation, it has to be a word without quotes, brackets or operators"),
+                ));
+            }
+            let anywhere = match matches.take_value("POSITION") {
+                None => false,
+                Some(position) => match position.value.unwrap_string().as_str() {
+                    "command" => false,
+                    "anywhere" => true,
+                    position => {
+                        return Err(ShellErrorKind::Basic(
+                            "Abbr Error",
+                            format!("Unknown position `{position}`, expected command or anywhere"),
+                        ))
+                    }
+                },
+            };
+            let expansion = match matches.take_value("EXPANSION").unwrap().value {
+                Value::Closure(closure) => Expansion::Closure(closure),
+                text => Expansion::Text(text.unwrap_string().to_string()),
+            };
+
+            if save {
+                let Expansion::Text(text) = &expansion else {
+                    return Err(ShellErrorKind::Basic(
+                        "Abbr Error",
+                        String::from("A closure can not be saved, add the abbreviation to the config file instead"),
+                    ));
+                };
+                ctx.shell.save_abbreviation(&name, text, anywhere)?;
+            }
+            ctx.shell.add_abbreviation(Abbreviation {
+                name,
+                expansion,
+                anywhere,
+            });
+        }
+        Some("remove") => {
+            let name = name.unwrap();
+            let saved = save && ctx.shell.forget_abbreviation(&name)?;
+            if !ctx.shell.remove_abbreviation(&name) && !saved {
+                return Err(ShellErrorKind::Basic(
+                    "Abbr Error",
+                    format!("Unknown abbreviation `{name}`"),
+                ));
+            }
+        }
+        Some("list") => {
+            let mut table = Table::new();
+            let name_header: Rc<str> = Rc::from("name");
+            let expansion_header: Rc<str> = Rc::from("expansion");
+            let position_header: Rc<str> = Rc::from("position");
+            for abbreviation in ctx.shell.abbreviations() {
+                let position = match abbreviation.anywhere {
+                    true => "anywhere",
+                    false => "command",
+                };
+                table.insert_map(IndexMap::from([
+                    (name_header.clone(), Value::from(abbreviation.name.as_str())),
+                    (expansion_header.clone(), abbreviation.expansion.to_value()),
+                    (position_header.clone(), Value::from(position)),
+                ]));
+            }
+            ctx.output.push(table.into())?;
+        }
+        _ => {
+            return Err(ShellErrorKind::Basic(
+                "Abbr Error",
+                String::from("Missing subcommand, expected one of add, remove or list"),
+            ))
+        }
+    }
+    Ok(())
+}
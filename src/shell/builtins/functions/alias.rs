@@ -9,7 +9,7 @@ use crate::{
     shell::value::{table::Table, SpannedValue, Type, Value},
 };
 
-static APP: Lazy<App> = Lazy::new(|| {
+pub static APP: Lazy<App> = Lazy::new(|| {
     App::new("alias")
         .about("Set alias")
         .arg(Arg::new("NAME", Type::STRING).help("Name of the alias"))
@@ -0,0 +1,41 @@
+use once_cell::sync::Lazy;
+
+use super::{from_items, into_items};
+use crate::{
+    argparse::{App, Arg, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{SpannedValue, Type},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("append")
+        .about("Add items to the end of a sequence")
+        .arg(
+            Arg::new("VALUES", Type::ANY)
+                .required(true)
+                .multiple(true)
+                .help("Items to add, lists are added as a single item"),
+        )
+});
+
+pub fn append(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let values = matches
+        .take_values("VALUES")
+        .unwrap()
+        .into_iter()
+        .map(|value| value.value);
+    let mut output = into_items(ctx.input.take().unpack())?;
+    output.extend(values);
+
+    ctx.output.push(from_items(output))?;
+    Ok(())
+}
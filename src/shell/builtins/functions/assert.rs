@@ -6,7 +6,7 @@ use crate::{
     shell::value::{SpannedValue, Type},
 };
 
-static APP: Lazy<App> = Lazy::new(|| {
+pub static APP: Lazy<App> = Lazy::new(|| {
     App::new("assert")
         .about("Assert that something is true")
         .arg(
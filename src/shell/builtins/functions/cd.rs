@@ -5,10 +5,13 @@ use once_cell::sync::Lazy;
 use crate::{
     argparse::{App, Arg, ParseResult},
     parser::{ast::context::Context, shell_error::ShellErrorKind},
-    shell::value::{SpannedValue, Type},
+    shell::{
+        dir_history::DirHistory,
+        value::{SpannedValue, Type},
+    },
 };
 
-static APP: Lazy<App> = Lazy::new(|| {
+pub static APP: Lazy<App> = Lazy::new(|| {
     App::new("cd")
         .about("Change working directory")
         .arg(Arg::new("DIRECTORY", Type::STRING).help("The new working directory"))
@@ -30,8 +33,8 @@ pub fn cd(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKi
     };
 
     if dir.as_str() == "-" {
-        ctx.shell.dir_history.back()
+        DirHistory::back(ctx.shell)
     } else {
-        ctx.shell.dir_history.change_dir(&*dir)
+        DirHistory::change_dir(ctx.shell, &*dir)
     }
 }
@@ -8,7 +8,7 @@ use crate::{
     shell::value::SpannedValue,
 };
 
-static APP: Lazy<App> = Lazy::new(|| {
+pub static APP: Lazy<App> = Lazy::new(|| {
     App::new("clear").about("Clears the terminal").flag(
         Flag::new("SCROLLBACK")
             .short('x')
@@ -0,0 +1,128 @@
+use std::{fs, path::Path, rc::Rc};
+
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+
+use super::read_file;
+use crate::{
+    argparse::{App, Arg, Flag, Opt, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::{
+        completions::{parse_fish, ExternalCompleter},
+        value::{table::Table, SpannedValue, Type, Value},
+    },
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("complete")
+        .about("Register completers for external commands, lists them without arguments")
+        .arg(Arg::new("COMMAND", Type::STRING).help("Command to complete"))
+        .arg(
+            Arg::new("COMPLETER", Type::CLOSURE)
+                .help("Closure called with the line and the cursor position, returns a list or a table with a value and a description column"),
+        )
+        .opt(
+            Opt::new("FISH", Type::STRING)
+                .long("fish")
+                .short('f')
+                .help("Import a fish completion file or a directory of them"),
+        )
+        .flag(
+            Flag::new("ERASE")
+                .long("erase")
+                .short('e')
+                .help("Remove the completer of COMMAND"),
+        )
+});
+
+pub fn complete(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    if let Some(path) = matches.take_value("FISH") {
+        let path = path.value.unwrap_string();
+        return import_fish(ctx, Path::new(&*path));
+    }
+
+    let command = matches
+        .take_value("COMMAND")
+        .map(|s| Rc::unwrap_or_clone(s.value.unwrap_string()));
+    let completer = matches.take_value("COMPLETER");
+
+    match (command, completer) {
+        (Some(command), _) if matches.conatins("ERASE") => {
+            if ctx.shell.completers.remove(&command).is_none() {
+                return Err(ShellErrorKind::Basic(
+                    "Completion Error",
+                    format!("No completer for `{command}`"),
+                ));
+            }
+        }
+        (Some(command), Some(completer)) => {
+            let Value::Closure(closure) = completer.value else {
+                unreachable!()
+            };
+            ctx.shell
+                .completers
+                .insert(command, ExternalCompleter::Closure(closure));
+        }
+        (Some(command), None) => {
+            return Err(ShellErrorKind::Basic(
+                "Completion Error",
+                format!("Missing completer for `{command}`\n\n{}", APP.usage()),
+            ))
+        }
+        (None, _) => {
+            let mut table = Table::new();
+            let command_header: Rc<str> = Rc::from("command");
+            let kind_header: Rc<str> = Rc::from("kind");
+            for (command, completer) in &ctx.shell.completers {
+                table.insert_map(IndexMap::from([
+                    (command_header.clone(), Value::from(command.clone())),
+                    (kind_header.clone(), Value::from(completer.kind())),
+                ]));
+            }
+            ctx.output.push(table.into())?;
+        }
+    }
+
+    Ok(())
+}
+
+fn import_fish(ctx: &mut Context, path: &Path) -> Result<(), ShellErrorKind> {
+    let files = if path.is_dir() {
+        let entries =
+            fs::read_dir(path).map_err(|e| ShellErrorKind::Io(Some(path.to_path_buf()), e))?;
+        let mut files: Vec<_> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "fish"))
+            .collect();
+        files.sort();
+        files
+    } else {
+        vec![path.to_path_buf()]
+    };
+
+    for file in files {
+        for (command, completion) in parse_fish(&read_file(&file)?) {
+            let completer = ctx
+                .shell
+                .completers
+                .entry(command)
+                .or_insert_with(|| ExternalCompleter::Fish(Vec::new()));
+            // a closure is replaced by the imported completions
+            match completer {
+                ExternalCompleter::Fish(completions) => completions.push(completion),
+                completer => *completer = ExternalCompleter::Fish(vec![completion]),
+            }
+        }
+    }
+    Ok(())
+}
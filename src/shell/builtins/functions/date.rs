@@ -0,0 +1,102 @@
+use once_cell::sync::Lazy;
+
+use crate::{
+    argparse::{App, Arg, Flag, Opt, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{date, SpannedValue, Type, Value},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("date")
+        .about("Create, parse and format dates")
+        .sub_cmd(
+            App::new("now").about("Get the current date").flag(
+                Flag::new("UTC")
+                    .long("utc")
+                    .short('u')
+                    .help("Use UTC instead of the local timezone"),
+            ),
+        )
+        .sub_cmd(
+            App::new("parse")
+                .about("Parse a string into a date")
+                .opt(
+                    Opt::new("FORMAT", Type::STRING)
+                        .long("format")
+                        .short('f')
+                        .help("strftime style format of the string"),
+                )
+                .arg(
+                    Arg::new("DATE", Type::STRING)
+                        .help("The string to parse, read from input if omitted"),
+                ),
+        )
+        .sub_cmd(
+            App::new("format").about("Format a date into a string").arg(
+                Arg::new("FORMAT", Type::STRING)
+                    .required(true)
+                    .help("strftime style format, for example \"%Y-%m-%d\""),
+            ),
+        )
+        .sub_cmd(
+            App::new("to-timezone")
+                .about("Convert a date to another timezone")
+                .arg(Arg::new("ZONE", Type::STRING).required(true).help(
+                    "`utc`, `local`, an offset like `+02:00` or a name like `Europe/Stockholm`",
+                )),
+        )
+});
+
+pub fn date(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let value = match matches.sub_cmd() {
+        Some("parse") => {
+            let string = match matches.take_value("DATE") {
+                Some(value) => value.value.unwrap_string(),
+                None => match ctx.input.take().unpack() {
+                    Value::String(string) => string,
+                    value => {
+                        return Err(ShellErrorKind::InvalidPipelineInput {
+                            expected: Type::STRING,
+                            recived: value.to_type(),
+                        })
+                    }
+                },
+            };
+            Value::from(date::parse_date(&string, matches.get_str("FORMAT"))?)
+        }
+        Some("format") => {
+            let input = input_date(ctx)?;
+            Value::from(date::format_date_with(
+                &input,
+                matches.get_str("FORMAT").unwrap(),
+            )?)
+        }
+        Some("to-timezone") => {
+            let input = input_date(ctx)?;
+            Value::from(date::to_timezone(&input, matches.get_str("ZONE").unwrap())?)
+        }
+        _ => Value::from(date::now(matches.conatins("UTC"))),
+    };
+
+    ctx.output.push(value)?;
+    Ok(())
+}
+
+fn input_date(ctx: &mut Context) -> Result<chrono::DateTime<chrono::FixedOffset>, ShellErrorKind> {
+    match ctx.input.take().unpack() {
+        Value::Date(date) => Ok(*date),
+        value => Err(ShellErrorKind::InvalidPipelineInput {
+            expected: Type::DATE,
+            recived: value.to_type(),
+        }),
+    }
+}
@@ -0,0 +1,53 @@
+use once_cell::sync::Lazy;
+
+use super::{encode::decode_str, map_strings};
+use crate::{
+    argparse::{App, Arg, Flag, ParseResult},
+    parser::{
+        ast::{context::Context, expr::try_bytes_to_value},
+        shell_error::ShellErrorKind,
+    },
+    shell::value::{SpannedValue, Type, Value},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("decode")
+        .about("Decode an encoded string")
+        .arg(
+            Arg::new("FORMAT", Type::STRING)
+                .help("Encoding of the string (base64, base64url, hex or url)")
+                .required(true),
+        )
+        .flag(
+            Flag::new("BINARY")
+                .long("binary")
+                .short('b')
+                .help("Always return binary data even if it is valid utf-8"),
+        )
+});
+
+pub fn decode(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let binary = matches.conatins("BINARY");
+    let format = matches.take_value("FORMAT").unwrap().value.unwrap_string();
+    let input = ctx.input.take().unpack();
+    let output = map_strings(input, |string| {
+        let data = decode_str(&format, string)?;
+        if binary {
+            Ok(Value::from(data))
+        } else {
+            Ok(try_bytes_to_value(data))
+        }
+    })?;
+
+    ctx.output.push(output)?;
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use once_cell::sync::Lazy;
+
+use super::cell_path_arg;
+use crate::{
+    argparse::{App, Arg, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{cell_path::delete_path, SpannedValue, Type},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("delete")
+        .about("Remove the keys, columns or items at cell paths")
+        .arg(
+            Arg::new("PATHS", Type::STRING | Type::INT)
+                .required(true)
+                .multiple(true)
+                .help("Keys, columns and indexes separated by dots"),
+        )
+});
+
+pub fn delete(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut input = ctx.input.take().unpack();
+    for path in matches.take_values("PATHS").unwrap() {
+        let (path, span) = cell_path_arg(path);
+        delete_path(&mut input, &path, span)?;
+    }
+
+    ctx.output.push(input)?;
+    Ok(())
+}
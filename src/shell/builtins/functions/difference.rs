@@ -0,0 +1,43 @@
+use indexmap::IndexSet;
+use once_cell::sync::Lazy;
+
+use super::{from_items, into_items};
+use crate::{
+    argparse::{App, Arg, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{hashable::HashableValue, SpannedValue, Type, Value},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("difference")
+        .about("Items in the input that are not in the other sequence, without duplicates")
+        .arg(
+            Arg::new("OTHER", Type::LIST | Type::RANGE | Type::TABLE)
+                .required(true)
+                .help("The other sequence"),
+        )
+});
+
+pub fn difference(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let other = into_items(matches.take_value("OTHER").unwrap().value)?;
+    let input = into_items(ctx.input.take().unpack())?;
+    let other: IndexSet<HashableValue> = other.into_iter().map(Value::into_hashable).collect();
+    let set: IndexSet<HashableValue> = input
+        .into_iter()
+        .map(Value::into_hashable)
+        .filter(|item| !other.contains(item))
+        .collect();
+
+    ctx.output
+        .push(from_items(set.into_iter().map(Value::from).collect()))?;
+    Ok(())
+}
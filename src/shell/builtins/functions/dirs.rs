@@ -0,0 +1,40 @@
+use once_cell::sync::Lazy;
+
+use crate::{
+    argparse::{App, Flag, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::{current_dir_str, value::SpannedValue, Value},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("dirs")
+        .about("List the working directory followed by the directory stack, top first")
+        .flag(
+            Flag::new("CLEAR")
+                .short('c')
+                .long("clear")
+                .help("Clear the directory stack"),
+        )
+});
+
+pub fn dirs(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    if matches.conatins("CLEAR") {
+        ctx.shell.dir_history.clear_stack();
+        return Ok(());
+    }
+
+    let stack = ctx.shell.dir_history.stack().iter().rev();
+    let dirs = std::iter::once(Value::from(current_dir_str()))
+        .chain(stack.map(|dir| Value::from(dir.to_string_lossy().to_string())))
+        .collect::<Vec<_>>();
+    ctx.output.push(Value::from(dirs))
+}
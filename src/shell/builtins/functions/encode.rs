@@ -0,0 +1,83 @@
+use base64::{
+    alphabet,
+    engine::{general_purpose::STANDARD, DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
+    Engine,
+};
+use once_cell::sync::Lazy;
+use percent_encoding::{percent_decode_str, percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+use super::map_bytes;
+use crate::{
+    argparse::{App, Arg, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{SpannedValue, Type, Value},
+};
+
+// padding is left out when encoding but accepted when decoding
+const BASE64_URL: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::URL_SAFE,
+    GeneralPurposeConfig::new()
+        .with_encode_padding(false)
+        .with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+// everything except the unreserved characters of RFC 3986 is escaped
+const URL_COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("encode")
+        .about("Encode a string or binary data")
+        .arg(
+            Arg::new("FORMAT", Type::STRING)
+                .help("Encoding to use (base64, base64url, hex or url)")
+                .required(true),
+        )
+});
+
+pub fn encode(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let format = matches.take_value("FORMAT").unwrap().value.unwrap_string();
+    let input = ctx.input.take().unpack();
+    let output = map_bytes(input, |data| Ok(Value::from(encode_bytes(&format, data)?)))?;
+
+    ctx.output.push(output)?;
+    Ok(())
+}
+
+fn encode_bytes(format: &str, data: &[u8]) -> Result<String, ShellErrorKind> {
+    match format {
+        "base64" => Ok(STANDARD.encode(data)),
+        "base64url" => Ok(BASE64_URL.encode(data)),
+        "hex" => Ok(hex::encode(data)),
+        "url" => Ok(percent_encode(data, URL_COMPONENT).to_string()),
+        _ => Err(ShellErrorKind::UnknownFormat(format.to_string())),
+    }
+}
+
+pub fn decode_str(format: &str, string: &str) -> Result<Vec<u8>, ShellErrorKind> {
+    let invalid =
+        |e: String| ShellErrorKind::Basic("Decode Error", format!("Invalid {format} input: {e}"));
+    // surrounding whitespace like a trailing newline from a file is never part of the data
+    let string = string.trim();
+    match format {
+        "base64" => STANDARD.decode(string).map_err(|e| invalid(e.to_string())),
+        "base64url" => BASE64_URL
+            .decode(string)
+            .map_err(|e| invalid(e.to_string())),
+        "hex" => hex::decode(string).map_err(|e| invalid(e.to_string())),
+        "url" => Ok(percent_decode_str(string).collect()),
+        _ => Err(ShellErrorKind::UnknownFormat(format.to_string())),
+    }
+}
@@ -0,0 +1,36 @@
+use std::rc::Rc;
+
+use once_cell::sync::Lazy;
+
+use super::into_items;
+use crate::{
+    argparse::{App, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{table::Table, SpannedValue, Value},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("enumerate").about("Get a table of the items in a sequence with their index")
+});
+
+pub fn enumerate(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let _ = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let input = into_items(ctx.input.take().unpack())?;
+    let rows = input
+        .into_iter()
+        .enumerate()
+        .map(|(index, item)| vec![Value::Int(index as i64), item])
+        .collect();
+    let table = Table::from_parts(vec![Rc::from("index"), Rc::from("item")], rows);
+
+    ctx.output.push(Value::from(table))?;
+    Ok(())
+}
@@ -7,7 +7,7 @@ use crate::{
     shell::value::{table::Table, SpannedValue, Value},
 };
 
-static APP: Lazy<App> = Lazy::new(|| App::new("env").about("List all environment variables"));
+pub static APP: Lazy<App> = Lazy::new(|| App::new("env").about("List all environment variables"));
 
 pub fn env(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
     let _ = match APP.parse(args) {
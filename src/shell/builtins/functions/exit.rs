@@ -6,7 +6,7 @@ use crate::{
     shell::value::{SpannedValue, Type, Value},
 };
 
-static APP: Lazy<App> = Lazy::new(|| {
+pub static APP: Lazy<App> = Lazy::new(|| {
     App::new("exit")
         .about("Exit the shell")
         .arg(Arg::new("STATUS", Type::INT).help("The exit status of the shell"))
@@ -1,22 +1,16 @@
-use std::{iter, rc::Rc};
+use std::rc::Rc;
 
 use indexmap::IndexMap;
 use once_cell::sync::Lazy;
 
+use super::call_closure;
 use crate::{
     argparse::{App, Arg, ParseResult},
-    parser::{
-        ast::{context::Context, expr::closure::Closure},
-        shell_error::ShellErrorKind,
-    },
-    shell::{
-        frame::Frame,
-        stream::OutputStream,
-        value::{SpannedValue, Type, Value},
-    },
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{SpannedValue, Type, Value},
 };
 
-static APP: Lazy<App> = Lazy::new(|| {
+pub static APP: Lazy<App> = Lazy::new(|| {
     App::new("filter")
         .arg(
             Arg::new("CLOSURE", Type::CLOSURE)
@@ -48,7 +42,7 @@ pub fn filter(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErr
         Value::String(string) => {
             let mut output = String::new();
             for (ch, value) in string.chars().map(|ch| (ch, Value::from(ch))) {
-                if apply_closure(ctx, &closure, value)? {
+                if call_closure(ctx, &closure, value)?.truthy() {
                     output.push(ch);
                 }
             }
@@ -57,7 +51,7 @@ pub fn filter(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErr
         Value::List(list) => {
             let mut output = Vec::new();
             for value in list.iter() {
-                if apply_closure(ctx, &closure, value.clone())? {
+                if call_closure(ctx, &closure, value.clone())?.truthy() {
                     output.push(value.clone());
                 }
             }
@@ -66,12 +60,8 @@ pub fn filter(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErr
         Value::Map(map) => {
             let mut output = IndexMap::new();
             for (k, v) in map.iter() {
-                let keep = apply_closure(
-                    ctx,
-                    &closure,
-                    Value::from(vec![Value::from(k.to_string()), v.clone()]),
-                )?;
-                if keep {
+                let pair = Value::from(vec![Value::from(k.to_string()), v.clone()]);
+                if call_closure(ctx, &closure, pair)?.truthy() {
                     output.insert(k.clone(), v.clone());
                 }
             }
@@ -86,7 +76,7 @@ pub fn filter(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErr
                     .zip(row.iter())
                     .map(|(k, v)| ((*k).clone(), v.clone()))
                     .collect();
-                keep_rows.push(apply_closure(ctx, &closure, Value::from(map))?);
+                keep_rows.push(call_closure(ctx, &closure, Value::from(map))?.truthy());
             }
             {
                 let table = Rc::make_mut(&mut table);
@@ -104,7 +94,7 @@ pub fn filter(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErr
         Value::Range(range) => {
             let mut output = Vec::new();
             for value in (*range).clone().map(Value::from).clone() {
-                if apply_closure(ctx, &closure, value.clone())? {
+                if call_closure(ctx, &closure, value.clone())?.truthy() {
                     output.push(value.clone());
                 }
             }
@@ -113,7 +103,7 @@ pub fn filter(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErr
         Value::Binary(binary) => {
             let mut output = Vec::new();
             for value in binary.iter().copied() {
-                if apply_closure(ctx, &closure, Value::from(value as i64))? {
+                if call_closure(ctx, &closure, Value::from(value as i64))?.truthy() {
                     output.push(value);
                 }
             }
@@ -130,21 +120,3 @@ pub fn filter(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErr
     ctx.output.push(value)?;
     Ok(())
 }
-
-fn apply_closure(
-    ctx: &mut Context,
-    closure: &Rc<(Rc<Closure>, Frame)>,
-    value: Value,
-) -> Result<bool, ShellErrorKind> {
-    let (closure, frame) = &**closure;
-    let mut capture = OutputStream::new_capture();
-    let mut ctx = Context {
-        shell: ctx.shell,
-        frame: frame.clone(),
-        output: &mut capture,
-        input: ctx.input,
-        src: ctx.src.clone(),
-    };
-    closure.eval(&mut ctx, iter::once(value))?;
-    Ok(capture.into_value_stream().unpack().truthy())
-}
@@ -6,7 +6,7 @@ use crate::{
     shell::value::{SpannedValue, Type, Value},
 };
 
-static APP: Lazy<App> = Lazy::new(|| {
+pub static APP: Lazy<App> = Lazy::new(|| {
     App::new("first")
         .arg(Arg::new("COUNT", Type::INT).help("Number of items to get"))
         .about("Get first item of sequence")
@@ -0,0 +1,114 @@
+use std::rc::Rc;
+
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+
+use super::{from_items, into_items};
+use crate::{
+    argparse::{App, Opt, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{table::Table, SpannedValue, Type, Value},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("flatten")
+        .about("Flatten lists, ranges and tables nested in a sequence one level")
+        .opt(
+            Opt::new("COLUMN", Type::STRING)
+                .long("column")
+                .short('c')
+                .help("Expand the lists in a table column into rows and the maps into columns"),
+        )
+});
+
+pub fn flatten(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let input = ctx.input.take().unpack();
+    if let Some(column) = matches.take_value("COLUMN") {
+        let column = column.value.unwrap_string();
+        let Value::Table(table) = input else {
+            return Err(ShellErrorKind::InvalidPipelineInput {
+                expected: Type::TABLE,
+                recived: input.to_type(),
+            });
+        };
+        ctx.output.push(flatten_column(&table, &column)?)?;
+        return Ok(());
+    }
+
+    let input = into_items(input)?;
+    let mut output = Vec::with_capacity(input.len());
+    for item in input {
+        match item {
+            Value::List(_) | Value::Range(_) | Value::Table(_) => output.extend(into_items(item)?),
+            item => output.push(item),
+        }
+    }
+
+    ctx.output.push(from_items(output))?;
+    Ok(())
+}
+
+// every item of a list cell gets its own row and the keys of a map cell become columns
+fn flatten_column(table: &Table, column: &str) -> Result<Value, ShellErrorKind> {
+    if !table.has_column(column) {
+        return Err(ShellErrorKind::ColumnNotFound(column.to_string()));
+    }
+
+    let mut output = Table::new();
+    for mut row in table.iter() {
+        let index = row.get_index_of(column).unwrap();
+        let cell = row.shift_remove(column).unwrap();
+        let items = match cell {
+            Value::List(_) | Value::Range(_) | Value::Table(_) => into_items(cell)?,
+            cell => vec![cell],
+        };
+
+        for item in items {
+            let mut new_row = IndexMap::new();
+            for (i, (key, value)) in row.iter().enumerate() {
+                if i == index {
+                    insert_cell(&mut new_row, &row, column, item.clone())?;
+                }
+                new_row.insert(key.clone(), value.clone());
+            }
+            if index == row.len() {
+                insert_cell(&mut new_row, &row, column, item)?;
+            }
+            output.insert_map(new_row);
+        }
+    }
+    Ok(Value::from(output))
+}
+
+// keys of a map cell that are already columns would overwrite them
+fn insert_cell(
+    new_row: &mut IndexMap<Rc<str>, Value>,
+    row: &IndexMap<Rc<str>, Value>,
+    column: &str,
+    cell: Value,
+) -> Result<(), ShellErrorKind> {
+    match cell {
+        Value::Map(map) => {
+            if let Some(key) = map.keys().find(|key| row.contains_key(*key)) {
+                return Err(ShellErrorKind::Basic(
+                    "Flatten Error",
+                    format!("Key `{key}` in column `{column}` is already a column of the table"),
+                ));
+            }
+            new_row.extend(Rc::unwrap_or_clone(map));
+        }
+        cell => {
+            new_row.insert(Rc::from(column), cell);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use once_cell::sync::Lazy;
+
+use crate::{
+    argparse::{App, Arg, Flag, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{
+        load::{deserialize, deserialize_tagged},
+        SpannedValue, Type, Value,
+    },
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("from")
+        .about("Parse structured data from a string")
+        .arg(
+            Arg::new("FORMAT", Type::STRING)
+                .help("Format of the data (json, toml, yaml or ini)")
+                .required(true),
+        )
+        .flag(
+            Flag::new("TAGGED")
+                .long("tagged")
+                .short('t')
+                .help("Parse data serialized with `to --tagged`"),
+        )
+});
+
+pub fn from(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let format = matches.take_value("FORMAT").unwrap().value.unwrap_string();
+    let deserialize = if matches.conatins("TAGGED") {
+        deserialize_tagged
+    } else {
+        deserialize
+    };
+
+    let input = ctx.input.take().unpack();
+    let value = match input {
+        Value::String(string) => deserialize(&format, &string)?,
+        Value::Binary(data) => deserialize(&format, &String::from_utf8_lossy(&data))?,
+        _ => {
+            return Err(ShellErrorKind::InvalidPipelineInput {
+                expected: Type::STRING | Type::BINARY,
+                recived: input.to_type(),
+            })
+        }
+    };
+
+    ctx.output.push(value)?;
+    Ok(())
+}
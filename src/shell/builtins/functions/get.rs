@@ -0,0 +1,35 @@
+use once_cell::sync::Lazy;
+
+use super::cell_path_arg;
+use crate::{
+    argparse::{App, Arg, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{cell_path::get_path, SpannedValue, Type},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("get")
+        .about("Get the value at a cell path like `servers.0.port`")
+        .arg(
+            Arg::new("PATH", Type::STRING | Type::INT)
+                .required(true)
+                .help("Keys, columns and indexes separated by dots"),
+        )
+});
+
+pub fn get(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let (path, span) = cell_path_arg(matches.take_value("PATH").unwrap());
+    let input = ctx.input.take().unpack();
+
+    ctx.output.push(get_path(&input, &path, span)?)?;
+    Ok(())
+}
@@ -6,7 +6,7 @@ use crate::{
     shell::value::{SpannedValue, Type},
 };
 
-static APP: Lazy<App> = Lazy::new(|| {
+pub static APP: Lazy<App> = Lazy::new(|| {
     App::new("glob")
         .about("Match against files or folders with pattern")
         .arg(
@@ -0,0 +1,81 @@
+use md5::Md5;
+use once_cell::sync::Lazy;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+use super::{map_bytes, read_file_raw};
+use crate::{
+    argparse::{App, Arg, Flag, Opt, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{SpannedValue, Type, Value},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("hash")
+        .about("Hash a string, binary data or a file")
+        .arg(
+            Arg::new("ALGORITHM", Type::STRING)
+                .help("Hash algorithm to use (sha256, sha1, md5 or blake3)")
+                .required(true),
+        )
+        .opt(
+            Opt::new("FILE", Type::STRING)
+                .long("file")
+                .short('f')
+                .help("Hash the contents of a file instead of the input"),
+        )
+        .flag(
+            Flag::new("BINARY")
+                .long("binary")
+                .short('b')
+                .help("Return the digest as binary data instead of a hex string"),
+        )
+});
+
+pub fn hash(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let binary = matches.conatins("BINARY");
+    let algorithm = matches
+        .take_value("ALGORITHM")
+        .unwrap()
+        .value
+        .unwrap_string();
+
+    let hash_value = |data: &[u8]| {
+        let digest = digest(&algorithm, data)?;
+        if binary {
+            Ok(Value::from(digest))
+        } else {
+            Ok(Value::from(hex::encode(digest)))
+        }
+    };
+
+    let output = match matches.get_str("FILE") {
+        Some(path) => hash_value(&read_file_raw(path)?)?,
+        None => map_bytes(ctx.input.take().unpack(), hash_value)?,
+    };
+
+    ctx.output.push(output)?;
+    Ok(())
+}
+
+fn digest(algorithm: &str, data: &[u8]) -> Result<Vec<u8>, ShellErrorKind> {
+    match algorithm {
+        "sha256" => Ok(Sha256::digest(data).to_vec()),
+        "sha1" => Ok(Sha1::digest(data).to_vec()),
+        "md5" => Ok(Md5::digest(data).to_vec()),
+        "blake3" => Ok(blake3::hash(data).as_bytes().to_vec()),
+        _ => Err(ShellErrorKind::Basic(
+            "Hash Error",
+            format!("Unknown hash algorithm `{algorithm}`"),
+        )),
+    }
+}
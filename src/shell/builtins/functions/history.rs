@@ -1,20 +1,41 @@
+use std::{fs, path::PathBuf, rc::Rc};
+
+use chrono::{DateTime, Local};
 use once_cell::sync::Lazy;
 use rustyline::history::History;
 
 use crate::{
-    argparse::{App, Flag, ParseResult},
+    argparse::{App, Arg, Flag, Opt, ParseResult},
     parser::{ast::context::Context, shell_error::ShellErrorKind},
-    shell::value::{SpannedValue, Value},
+    shell::{
+        history::import::HistoryFormat,
+        value::{table::Table, SpannedValue, Type, Value},
+    },
 };
 
-static APP: Lazy<App> = Lazy::new(|| {
+pub static APP: Lazy<App> = Lazy::new(|| {
     App::new("history")
-        .about("Display or edit history")
+        .about("Show the history as a table or clear it")
         .flag(Flag::new("CLEAR").short('c').long("clear"))
+        .sub_cmd(
+            App::new("import")
+                .about("Add the history of another shell, returns the number of added lines")
+                .opt(
+                    Opt::new("FORMAT", Type::STRING)
+                        .long("from")
+                        .short('f')
+                        .required(true)
+                        .help("The shell the history is from, `bash`, `zsh` or `fish`"),
+                )
+                .arg(
+                    Arg::new("PATH", Type::STRING)
+                        .help("The history file, the default file of the shell if omitted"),
+                ),
+        )
 });
 
 pub fn history(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
-    let matches = match APP.parse(args) {
+    let mut matches = match APP.parse(args) {
         Ok(ParseResult::Matches(m)) => m,
         Ok(ParseResult::Info(info)) => {
             ctx.output.push(info)?;
@@ -23,16 +44,63 @@ pub fn history(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellEr
         Err(e) => return Err(e.into()),
     };
 
-    if matches.conatins("CLEAR") {
-        let history = ctx.shell.editor.history_mut();
+    let home = ctx.shell.home_dir();
+    let history_path = ctx.shell.history_path();
+    let Some(editor) = ctx.shell.editor.as_mut() else {
+        return Err(ShellErrorKind::Basic(
+            "History Error",
+            String::from("History can't be used while a line is being edited"),
+        ));
+    };
+
+    if matches.sub_cmd() == Some("import") {
+        // safe because the option is required
+        let name = matches.take_value("FORMAT").unwrap().value.unwrap_string();
+        let Some(format) = HistoryFormat::from_name(&name) else {
+            return Err(ShellErrorKind::Basic(
+                "History Error",
+                format!("Can't import history from `{name}`, expected bash, zsh or fish"),
+            ));
+        };
+        let path = match matches.take_value("PATH") {
+            Some(path) => PathBuf::from(path.value.unwrap_string().as_str()),
+            None => format.default_path(&home),
+        };
+        let bytes = fs::read(&path).map_err(|e| ShellErrorKind::Io(Some(path), e))?;
+        let count = editor
+            .history_mut()
+            .import(&history_path, format.parse(&bytes))?;
+        ctx.output.push(Value::Int(count as i64))?;
+    } else if matches.conatins("CLEAR") {
+        let history = editor.history_mut();
         let _ = history.clear();
     } else {
-        let history = ctx.shell.editor.history();
-        let mut output = Vec::new();
-        for entry in history {
-            output.push(Value::from(entry.entry.as_str()));
+        let headers: [Rc<str>; 6] = [
+            "command",
+            "directory",
+            "time",
+            "status",
+            "duration",
+            "session",
+        ]
+        .map(Rc::from);
+        let mut table = Table::new();
+        for entry in editor.history() {
+            let time = entry
+                .timestamp
+                .and_then(DateTime::from_timestamp_millis)
+                .map(|time| Value::from(time.with_timezone(&Local).fixed_offset()));
+            let values = [
+                Value::from(entry.entry.as_str()),
+                Value::from(entry.working_dir.as_str()),
+                time.unwrap_or(Value::Null),
+                entry.status.map(Value::Int).unwrap_or(Value::Null),
+                entry.duration.map(Value::Duration).unwrap_or(Value::Null),
+                entry.session.map(Value::Int).unwrap_or(Value::Null),
+            ];
+            table.insert_map(headers.iter().cloned().zip(values).collect());
         }
-        ctx.output.push(output.into())?;
+        ctx.output.push(table.into())?;
     }
 
     Ok(())
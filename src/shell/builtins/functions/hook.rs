@@ -0,0 +1,96 @@
+use std::rc::Rc;
+
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+
+use crate::{
+    argparse::{App, Arg, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::{
+        hooks::HookEvent,
+        value::{table::Table, SpannedValue, Type, Value},
+    },
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("hook")
+        .about("Run closures when the shell reaches an event")
+        .sub_cmd(
+            App::new("add")
+                .about("Add a closure to an event, pre_prompt, pre_exec gets the line, post_exec gets the status and duration, on_cd gets the old and new directory")
+                .arg(
+                    Arg::new("EVENT", Type::STRING)
+                        .required(true)
+                        .help("One of pre_prompt, pre_exec, post_exec or on_cd"),
+                )
+                .arg(
+                    Arg::new("CLOSURE", Type::CLOSURE)
+                        .required(true)
+                        .help("Closure run in the scope it was defined in"),
+                ),
+        )
+        .sub_cmd(App::new("list").about("List the added hooks"))
+        .sub_cmd(
+            App::new("clear")
+                .about("Remove the hooks of an event or all hooks")
+                .arg(Arg::new("EVENT", Type::STRING).help("Only remove the hooks of this event")),
+        )
+});
+
+pub fn hook(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let event = matches
+        .take_value("EVENT")
+        .map(|event| parse_event(&event.value.unwrap_string()))
+        .transpose()?;
+
+    match matches.sub_cmd() {
+        Some("add") => {
+            // safe because the arguments are required
+            let Some(Value::Closure(closure)) = matches.take_value("CLOSURE").map(|c| c.value)
+            else {
+                unreachable!()
+            };
+            ctx.shell.add_hook(event.unwrap(), closure);
+        }
+        Some("list") => {
+            let mut table = Table::new();
+            let event_header: Rc<str> = Rc::from("event");
+            let closure_header: Rc<str> = Rc::from("closure");
+            for (event, closure) in ctx.shell.hooks() {
+                table.insert_map(IndexMap::from([
+                    (event_header.clone(), Value::from(event.name())),
+                    (closure_header.clone(), Value::Closure(closure.clone())),
+                ]));
+            }
+            ctx.output.push(table.into())?;
+        }
+        Some("clear") => ctx.shell.clear_hooks(event),
+        _ => {
+            return Err(ShellErrorKind::Basic(
+                "Hook Error",
+                String::from("Missing subcommand, expected one of add, list or clear"),
+            ))
+        }
+    }
+    Ok(())
+}
+
+fn parse_event(name: &str) -> Result<HookEvent, ShellErrorKind> {
+    HookEvent::from_name(name).ok_or_else(|| {
+        ShellErrorKind::Basic(
+            "Hook Error",
+            format!(
+                "Unknown event `{name}`, expected one of pre_prompt, pre_exec, post_exec or on_cd"
+            ),
+        )
+    })
+}
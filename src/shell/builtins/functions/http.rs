@@ -0,0 +1,186 @@
+use std::{io::Read, rc::Rc};
+
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+use ureq::Response;
+
+use crate::{
+    argparse::{App, Arg, Flag, Opt, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{load::deserialize, SpannedValue, Type, Value},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("http")
+        .about("Send http requests")
+        .sub_cmd(request_app("get", "Send a GET request"))
+        .sub_cmd(request_app("post", "Send a POST request"))
+        .sub_cmd(request_app("put", "Send a PUT request"))
+        .sub_cmd(request_app("delete", "Send a DELETE request"))
+        .sub_cmd(request_app("patch", "Send a PATCH request"))
+});
+
+// every method takes the same arguments, the request body is always read from input
+fn request_app(method: &str, about: &str) -> App {
+    App::new(method)
+        .about(about)
+        .opt(
+            Opt::new("HEADER", Type::STRING)
+                .long("header")
+                .short('H')
+                .help("Header formatted as `Name: value`, can be given multiple times"),
+        )
+        .flag(
+            Flag::new("JSON")
+                .long("json")
+                .short('j')
+                .help("Send the input serialized as json"),
+        )
+        .flag(
+            Flag::new("FORM")
+                .long("form")
+                .short('f')
+                .help("Send the input map url encoded as a form")
+                .conflicts_with("JSON".to_string()),
+        )
+        .flag(
+            Flag::new("RAW")
+                .long("raw")
+                .short('r')
+                .help("Return the body as binary instead of decoding it"),
+        )
+        .arg(
+            Arg::new("URL", Type::STRING)
+                .required(true)
+                .help("The url to send the request to"),
+        )
+}
+
+pub fn http(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let method = match matches.sub_cmd() {
+        Some(method) => method.to_uppercase(),
+        None => {
+            return Err(ShellErrorKind::Basic(
+                "Http Error",
+                String::from("Missing method, expected one of get, post, put, delete or patch"),
+            ))
+        }
+    };
+    let url = matches.take_value("URL").unwrap().value.unwrap_string();
+
+    let mut request = ureq::builder().redirects(10).build().request(&method, &url);
+    for header in matches.take_values("HEADER").unwrap_or_default() {
+        let header = header.value.unwrap_string();
+        let Some((name, value)) = header.split_once(':') else {
+            return Err(ShellErrorKind::Basic(
+                "Http Error",
+                format!("Invalid header `{header}`, expected `Name: value`"),
+            ));
+        };
+        request = request.set(name.trim(), value.trim());
+    }
+
+    let input = ctx.input.take().unpack();
+    let result = if matches.conatins("JSON") {
+        let body = serde_json::to_string(&input)?;
+        request
+            .set("Content-Type", "application/json")
+            .send_string(&body)
+    } else if matches.conatins("FORM") {
+        let Value::Map(map) = input else {
+            return Err(ShellErrorKind::InvalidPipelineInput {
+                expected: Type::MAP,
+                recived: input.to_type(),
+            });
+        };
+        let mut fields = Vec::new();
+        for (key, value) in map.iter() {
+            // lists are sent as repeated fields
+            let mut values = Vec::new();
+            value.clone().try_expand_to_strings_no_span(&mut values)?;
+            fields.extend(values.into_iter().map(|value| (key.clone(), value)));
+        }
+        let fields: Vec<(&str, &str)> = fields
+            .iter()
+            .map(|(key, value)| (&**key, value.as_str()))
+            .collect();
+        request.send_form(&fields)
+    } else {
+        match input {
+            Value::Null => request.call(),
+            Value::String(string) => request.send_string(&string),
+            Value::Binary(data) => request.send_bytes(&data),
+            _ => {
+                return Err(ShellErrorKind::InvalidPipelineInput {
+                    expected: Type::NULL | Type::STRING | Type::BINARY,
+                    recived: input.to_type(),
+                })
+            }
+        }
+    };
+
+    // responses with an error status are still returned so that the status can be inspected
+    let response = match result {
+        Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+        Err(e) => return Err(e.into()),
+    };
+
+    ctx.output
+        .push(response_to_value(response, matches.conatins("RAW"))?)?;
+    Ok(())
+}
+
+fn response_to_value(response: Response, raw: bool) -> Result<Value, ShellErrorKind> {
+    let status = Value::Int(response.status() as i64);
+    let mut headers = IndexMap::new();
+    for name in response.headers_names() {
+        if let Some(value) = response.header(&name) {
+            headers.insert(Rc::from(name), Value::from(value));
+        }
+    }
+    let content_type = response.content_type().to_ascii_lowercase();
+
+    let mut data = Vec::new();
+    response
+        .into_reader()
+        .read_to_end(&mut data)
+        .map_err(|e| ShellErrorKind::Io(None, e))?;
+
+    let body = if raw {
+        Value::from(data)
+    } else {
+        decode_body(&content_type, data)?
+    };
+
+    Ok(Value::from(IndexMap::from([
+        (Rc::from("status"), status),
+        (Rc::from("headers"), Value::from(headers)),
+        (Rc::from("body"), body),
+    ])))
+}
+
+fn decode_body(content_type: &str, data: Vec<u8>) -> Result<Value, ShellErrorKind> {
+    let format = match content_type.split_once('/') {
+        Some((_, subtype)) if subtype == "json" || subtype.ends_with("+json") => Some("json"),
+        Some((_, "toml")) => Some("toml"),
+        Some((_, "yaml" | "x-yaml")) => Some("yaml"),
+        _ => None,
+    };
+
+    match format {
+        Some(format) => deserialize(format, &String::from_utf8_lossy(&data)),
+        None if content_type.starts_with("text/") => {
+            Ok(Value::from(String::from_utf8_lossy(&data).into_owned()))
+        }
+        None => Ok(Value::from(data)),
+    }
+}
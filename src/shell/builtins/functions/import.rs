@@ -12,7 +12,7 @@ use crate::{
     },
 };
 
-static APP: Lazy<App> = Lazy::new(|| {
+pub static APP: Lazy<App> = Lazy::new(|| {
     App::new("import")
         .about("Import file for http url or filepath")
         .arg(
@@ -8,7 +8,7 @@ use crate::{
     shell::value::{SpannedValue, Type},
 };
 
-static APP: Lazy<App> = Lazy::new(|| {
+pub static APP: Lazy<App> = Lazy::new(|| {
     App::new("input")
         .about("Get input from stdin")
         .arg(Arg::new("PROMPT", Type::STRING).help("Prompt to print before input"))
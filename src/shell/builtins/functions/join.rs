@@ -0,0 +1,61 @@
+use once_cell::sync::Lazy;
+
+use crate::{
+    argparse::{App, Arg, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{SpannedValue, Type, Value},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("join")
+        .about("Join a list into a string")
+        .arg(Arg::new("SEPARATOR", Type::STRING).help("String placed between every item"))
+});
+
+pub fn join(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let separator = matches
+        .take_value("SEPARATOR")
+        .map(|value| value.value.unwrap_string());
+
+    let input = ctx.input.take().unpack();
+    let Value::List(list) = input else {
+        return Err(ShellErrorKind::InvalidPipelineInput {
+            expected: Type::LIST,
+            recived: input.to_type(),
+        });
+    };
+
+    let mut output = String::new();
+    for (index, value) in list.iter().enumerate() {
+        if index > 0 {
+            if let Some(separator) = &separator {
+                output.push_str(separator);
+            }
+        }
+        match value {
+            Value::String(string) => output.push_str(string),
+            Value::Int(int) => output.push_str(&int.to_string()),
+            Value::Float(float) => output.push_str(&float.to_string()),
+            Value::Bool(boolean) => output.push_str(&boolean.to_string()),
+            _ => {
+                return Err(ShellErrorKind::InvalidConversionContains {
+                    from: value.to_type(),
+                    to: Type::STRING,
+                    span: None,
+                })
+            }
+        }
+    }
+
+    ctx.output.push(Value::from(output))?;
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use std::rc::Rc;
+
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+
+use crate::{
+    argparse::{App, Arg, Opt, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::{
+        editor::{parse_key, KeyAction, ACTIONS},
+        value::{table::Table, SpannedValue, Type, Value},
+    },
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("keybind")
+        .about("Bind keys of the line editor to actions, text, lines or closures")
+        .sub_cmd(
+            App::new("add")
+                .about("Bind a key, a closure gets the line and returns the new line or nothing to keep it")
+                .arg(
+                    Arg::new("KEY", Type::STRING)
+                        .required(true)
+                        .help("A key like `ctrl-e`, `alt-enter`, `shift-tab`, `f5` or `x`"),
+                )
+                .arg(
+                    Arg::new("ACTION", Type::STRING | Type::CLOSURE)
+                        .help("An action from `keybind actions` or a closure"),
+                )
+                .opt(
+                    Opt::new("INSERT", Type::STRING)
+                        .long("insert")
+                        .short('i')
+                        .help("Insert text at the cursor"),
+                )
+                .opt(
+                    Opt::new("RUN", Type::STRING)
+                        .long("run")
+                        .short('r')
+                        .help("Run a line and keep the line that is being edited"),
+                ),
+        )
+        .sub_cmd(
+            App::new("remove")
+                .about("Make a key do what it does by default")
+                .arg(Arg::new("KEY", Type::STRING).required(true).help("The bound key")),
+        )
+        .sub_cmd(App::new("list").about("List the bound keys"))
+        .sub_cmd(App::new("actions").about("List the actions a key can be bound to"))
+});
+
+pub fn keybind(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let key = matches.take_value("KEY").map(|key| {
+        let name = key.value.unwrap_string();
+        match parse_key(&name) {
+            Some(key) => Ok((key, name.to_string())),
+            None => Err(ShellErrorKind::Basic(
+                "Keybind Error",
+                format!(
+                    "`{name}` is not a key, keys look like ctrl-e, alt-enter, shift-tab, f5 or x"
+                ),
+            )),
+        }
+    });
+    let key = key.transpose()?;
+
+    match matches.sub_cmd() {
+        Some("add") => {
+            let actions = [
+                matches.take_value("ACTION").map(|action| action.value),
+                matches.take_value("INSERT").map(|text| text.value),
+                matches.take_value("RUN").map(|src| src.value),
+            ];
+            let action = match actions {
+                [Some(Value::Closure(closure)), None, None] => KeyAction::Closure(closure),
+                [Some(Value::String(name)), None, None] => {
+                    match ACTIONS.iter().find(|action| ***action == **name) {
+                        Some(action) => KeyAction::Action(action),
+                        None => {
+                            return Err(ShellErrorKind::Basic(
+                                "Keybind Error",
+                                format!("Unknown action `{name}`, `keybind actions` lists them"),
+                            ))
+                        }
+                    }
+                }
+                [None, Some(text), None] => KeyAction::Insert(text.unwrap_string().to_string()),
+                [None, None, Some(src)] => KeyAction::Run(src.unwrap_string().to_string()),
+                _ => {
+                    return Err(ShellErrorKind::Basic(
+                        "Keybind Error",
+                        String::from("Expected one of an action, a closure, --insert or --run"),
+                    ))
+                }
+            };
+            // safe because the key is required
+            let (key, name) = key.unwrap();
+            ctx.shell.bind_key(key, name, action);
+        }
+        Some("remove") => {
+            let (key, name) = key.unwrap();
+            if !ctx.shell.unbind_key(key) {
+                return Err(ShellErrorKind::Basic(
+                    "Keybind Error",
+                    format!("`{name}` is not bound"),
+                ));
+            }
+        }
+        Some("list") => {
+            let mut table = Table::new();
+            let key_header: Rc<str> = Rc::from("key");
+            let action_header: Rc<str> = Rc::from("action");
+            for (_, name, action) in ctx.shell.key_bindings() {
+                table.insert_map(IndexMap::from([
+                    (key_header.clone(), Value::from(name.as_str())),
+                    (action_header.clone(), action.to_value()),
+                ]));
+            }
+            ctx.output.push(table.into())?;
+        }
+        Some("actions") => {
+            let actions: Vec<_> = ACTIONS.iter().map(|action| Value::from(*action)).collect();
+            ctx.output.push(Value::from(actions))?;
+        }
+        _ => {
+            return Err(ShellErrorKind::Basic(
+                "Keybind Error",
+                String::from("Missing subcommand, expected one of add, remove, list or actions"),
+            ))
+        }
+    }
+    Ok(())
+}
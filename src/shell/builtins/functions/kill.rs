@@ -0,0 +1,130 @@
+use once_cell::sync::Lazy;
+
+use crate::{
+    argparse::{App, Arg, Opt, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{SpannedValue, Type, Value},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("kill")
+        .about("Send a signal to processes")
+        .opt(
+            Opt::new("SIGNAL", Type::STRING)
+                .long("signal")
+                .short('s')
+                .help("Name of the signal to send, defaults to TERM"),
+        )
+        .arg(
+            Arg::new("PID", Type::INT | Type::LIST | Type::TABLE)
+                .multiple(true)
+                .help(
+                    "Pids, lists of pids or a table with a pid column, read from input if omitted",
+                ),
+        )
+});
+
+pub fn kill(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let signal = matches
+        .get_str("SIGNAL")
+        .map(|signal| signal.to_uppercase())
+        .unwrap_or_else(|| String::from("TERM"));
+
+    let mut pids = Vec::new();
+    match matches.take_values("PID") {
+        Some(values) => {
+            for value in values {
+                collect_pids(value.value, &mut pids)?;
+            }
+        }
+        None => collect_pids(ctx.input.take().unpack(), &mut pids)?,
+    }
+
+    if pids.is_empty() {
+        return Err(ShellErrorKind::Basic(
+            "Process Error",
+            format!("No pids given\n\n{}", APP.usage()),
+        ));
+    }
+
+    send_signal(&signal, &pids)
+}
+
+fn collect_pids(value: Value, pids: &mut Vec<i64>) -> Result<(), ShellErrorKind> {
+    match value {
+        Value::Int(pid) if pid < 1 => {
+            return Err(ShellErrorKind::Basic(
+                "Process Error",
+                format!("Invalid pid `{pid}`, pids start at 1"),
+            ))
+        }
+        Value::Int(pid) => pids.push(pid),
+        Value::List(list) => {
+            for value in list.iter() {
+                collect_pids(value.clone(), pids)?;
+            }
+        }
+        Value::Table(table) => {
+            for value in table.column("pid")? {
+                collect_pids(value, pids)?;
+            }
+        }
+        Value::Map(map) => match map.get("pid") {
+            Some(value) => collect_pids(value.clone(), pids)?,
+            None => return Err(ShellErrorKind::ColumnNotFound(String::from("pid"))),
+        },
+        value => {
+            return Err(ShellErrorKind::InvalidPipelineInput {
+                expected: Type::INT | Type::LIST | Type::TABLE,
+                recived: value.to_type(),
+            })
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn send_signal(signal: &str, pids: &[i64]) -> Result<(), ShellErrorKind> {
+    use nix::{
+        sys::signal::{self, Signal},
+        unistd::Pid,
+    };
+
+    let name = if signal.starts_with("SIG") {
+        signal.to_string()
+    } else {
+        format!("SIG{signal}")
+    };
+    let signal: Signal = name.parse().map_err(|_| {
+        ShellErrorKind::Basic("Process Error", format!("Unknown signal `{signal}`"))
+    })?;
+
+    for pid in pids {
+        let raw = i32::try_from(*pid)
+            .map_err(|_| ShellErrorKind::Basic("Process Error", format!("Invalid pid `{pid}`")))?;
+        signal::kill(Pid::from_raw(raw), signal).map_err(|e| {
+            ShellErrorKind::Basic(
+                "Process Error",
+                format!("Cannot send {signal} to process {pid}: {}", e.desc()),
+            )
+        })?;
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn send_signal(_: &str, _: &[i64]) -> Result<(), ShellErrorKind> {
+    Err(ShellErrorKind::Basic(
+        "Process Error",
+        String::from("kill is only supported on Unix"),
+    ))
+}
@@ -8,7 +8,7 @@ use crate::{
     shell::value::{SpannedValue, Type, Value},
 };
 
-static APP: Lazy<App> = Lazy::new(|| {
+pub static APP: Lazy<App> = Lazy::new(|| {
     App::new("last")
         .arg(Arg::new("COUNT", Type::INT).help("Number of items to get"))
         .about("Get last n item of sequence")
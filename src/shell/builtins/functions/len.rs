@@ -1,4 +1,5 @@
 use once_cell::sync::Lazy;
+use unicode_segmentation::UnicodeSegmentation;
 
 use crate::{
     argparse::{App, ParseResult},
@@ -6,7 +7,7 @@ use crate::{
     shell::value::{SpannedValue, Value},
 };
 
-static APP: Lazy<App> = Lazy::new(|| App::new("len").about("Get number of items in container"));
+pub static APP: Lazy<App> = Lazy::new(|| App::new("len").about("Get number of items in container"));
 
 pub fn len(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
     let _ = match APP.parse(args) {
@@ -20,7 +21,7 @@ pub fn len(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorK
 
     let input = ctx.input.take().unpack();
     let len = match input {
-        Value::String(string) => string.chars().count() as i64,
+        Value::String(string) => string.graphemes(true).count() as i64,
         Value::List(list) => list.len() as i64,
         Value::Map(map) => map.len() as i64,
         Value::Table(table) => table.len() as i64,
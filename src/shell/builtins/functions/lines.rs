@@ -6,7 +6,7 @@ use crate::{
     shell::value::{SpannedValue, Value},
 };
 
-static APP: Lazy<App> = Lazy::new(|| {
+pub static APP: Lazy<App> = Lazy::new(|| {
     App::new("lines").about("Split a string into lines").flag(
         Flag::new("SKIP")
             .long("skip-empty")
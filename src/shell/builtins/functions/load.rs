@@ -6,10 +6,10 @@ use super::{read_file, read_file_raw};
 use crate::{
     argparse::{App, Arg, Flag, ParseResult},
     parser::{ast::context::Context, shell_error::ShellErrorKind},
-    shell::value::{SpannedValue, Type, Value},
+    shell::value::{load::load_value, SpannedValue, Type, Value},
 };
 
-static APP: Lazy<App> = Lazy::new(|| {
+pub static APP: Lazy<App> = Lazy::new(|| {
     App::new("load")
         .about("Load a data from file")
         .arg(
@@ -22,14 +22,22 @@ static APP: Lazy<App> = Lazy::new(|| {
                 .long("str")
                 .short('s')
                 .help("Load raw text data")
-                .conflicts_with("RAW".into()),
+                .conflicts_with("RAW".into())
+                .conflicts_with("TAGGED".into()),
         )
         .flag(
             Flag::new("RAW")
                 .long("raw")
                 .short('r')
                 .help("Load raw binary data")
-                .conflicts_with("STR".into()),
+                .conflicts_with("STR".into())
+                .conflicts_with("TAGGED".into()),
+        )
+        .flag(
+            Flag::new("TAGGED")
+                .long("tagged")
+                .short('t')
+                .help("Load data saved with `save --tagged`"),
         )
 });
 
@@ -59,31 +67,8 @@ pub fn load(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellError
         let file = read_file_raw(&path)?;
         ctx.output.push(Value::Binary(Rc::new(file)))?;
     } else {
-        let ext = path.extension();
-        if let Some(ext) = ext {
-            let ext = ext.to_string_lossy().to_string();
-            match ext.as_str() {
-                "json" => {
-                    let file = read_file(&path)?;
-                    ctx.output.push(serde_json::from_str(&file)?)?;
-                }
-                "toml" => {
-                    let file = read_file(&path)?;
-                    ctx.output.push(toml::from_str(&file)?)?;
-                }
-                "txt" => {
-                    let file = read_file(&path)?;
-                    ctx.output.push(file.into())?;
-                }
-                _ => return Err(ShellErrorKind::UnknownFileType(ext)),
-            }
-        } else {
-            let file = read_file_raw(&path)?;
-            match String::from_utf8(file) {
-                Ok(string) => ctx.output.push(string.into())?,
-                Err(e) => ctx.output.push(e.into_bytes().into())?,
-            };
-        }
+        ctx.output
+            .push(load_value(&path, matches.conatins("TAGGED"))?)?;
     }
 
     Ok(())
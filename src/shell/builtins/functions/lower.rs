@@ -0,0 +1,26 @@
+use once_cell::sync::Lazy;
+
+use super::map_strings;
+use crate::{
+    argparse::{App, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{SpannedValue, Value},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| App::new("lower").about("Convert a string to lowercase"));
+
+pub fn lower(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let _ = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let input = ctx.input.take().unpack();
+    let output = map_strings(input, |string| Ok(Value::from(string.to_lowercase())))?;
+    ctx.output.push(output)?;
+    Ok(())
+}
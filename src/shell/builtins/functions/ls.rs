@@ -0,0 +1,307 @@
+use std::{
+    collections::HashMap,
+    fs::{self, Metadata},
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use chrono::{DateTime, Local};
+use glob::MatchOptions;
+use indexmap::IndexMap;
+use memchr::memchr3;
+use once_cell::sync::Lazy;
+
+use super::file_err_to_shell_err;
+use crate::{
+    argparse::{App, Arg, Flag, ParseResult},
+    parser::{
+        ast::context::Context,
+        shell_error::{ShellError, ShellErrorKind},
+    },
+    shell::{
+        report_error,
+        value::{table::Table, SpannedValue, Type, Value},
+    },
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("ls")
+        .about("List directory contents")
+        .flag(
+            Flag::new("ALL")
+                .long("all")
+                .short('a')
+                .help("Include hidden files"),
+        )
+        .flag(
+            Flag::new("LONG")
+                .long("long")
+                .short('l')
+                .help("Include permissions, owner and symlink target"),
+        )
+        .flag(
+            Flag::new("RECURSIVE")
+                .long("recursive")
+                .short('r')
+                .help("List the contents of directories recursively"),
+        )
+        .arg(
+            Arg::new("PATH", Type::STRING | Type::LIST)
+                .multiple(true)
+                .help("Directories, files or glob patterns to list"),
+        )
+});
+
+struct Options {
+    all: bool,
+    long: bool,
+    recursive: bool,
+}
+
+pub fn ls(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let options = Options {
+        all: matches.conatins("ALL"),
+        long: matches.conatins("LONG"),
+        recursive: matches.conatins("RECURSIVE"),
+    };
+
+    let mut paths = Vec::new();
+    if let Some(values) = matches.take_values("PATH") {
+        for value in values {
+            value.try_expand_to_strings(&mut paths)?;
+        }
+    }
+
+    let mut entries = Vec::new();
+    let mut errors = Vec::new();
+    if paths.is_empty() {
+        list_dir(Path::new("."), None, &options, &mut entries, &mut errors)?;
+    }
+
+    for path in paths {
+        if memchr3(b'*', b'?', b'[', path.as_bytes()).is_some() {
+            let match_options = MatchOptions {
+                require_literal_leading_dot: !options.all,
+                ..Default::default()
+            };
+            let mut found = false;
+            for entry in glob::glob_with(&path, match_options)? {
+                found = true;
+                push_entry(entry?, &options, &mut entries, &mut errors)?;
+            }
+            if !found {
+                return Err(ShellErrorKind::Basic(
+                    "Glob Error",
+                    format!("No match found for pattern `{path}`"),
+                ));
+            }
+        } else {
+            let path = PathBuf::from(path);
+            let metadata = fs::metadata(&path)
+                .map_err(|e| file_err_to_shell_err(e, path.to_string_lossy().to_string()))?;
+            if metadata.is_dir() {
+                list_dir(&path, Some(&path), &options, &mut entries, &mut errors)?;
+            } else {
+                push_entry(path, &options, &mut entries, &mut errors)?;
+            }
+        }
+    }
+
+    // the directories that could not be read are reported without losing the rest of the listing
+    for error in errors {
+        report_error(ShellError::new(error, ctx.src.clone()));
+    }
+    ctx.output.push(Value::from(to_table(entries, &options)))?;
+    Ok(())
+}
+
+struct Entry {
+    name: String,
+    path: PathBuf,
+    metadata: Metadata,
+}
+
+// pushes a single path given by the user, descending into it if it is a directory and recursive is set
+fn push_entry(
+    path: PathBuf,
+    options: &Options,
+    entries: &mut Vec<Entry>,
+    errors: &mut Vec<ShellErrorKind>,
+) -> Result<(), ShellErrorKind> {
+    let metadata = fs::symlink_metadata(&path)
+        .map_err(|e| file_err_to_shell_err(e, path.to_string_lossy().to_string()))?;
+    let is_dir = metadata.is_dir();
+    entries.push(Entry {
+        name: path.to_string_lossy().to_string(),
+        path: path.clone(),
+        metadata,
+    });
+    if is_dir && options.recursive {
+        if let Err(error) = list_dir(&path, Some(&path), options, entries, errors) {
+            errors.push(error);
+        }
+    }
+    Ok(())
+}
+
+// names are relative to the prefix so that the current directory is listed with bare file names
+fn list_dir(
+    dir: &Path,
+    prefix: Option<&Path>,
+    options: &Options,
+    entries: &mut Vec<Entry>,
+    errors: &mut Vec<ShellErrorKind>,
+) -> Result<(), ShellErrorKind> {
+    let read_dir = fs::read_dir(dir)
+        .map_err(|e| file_err_to_shell_err(e, dir.to_string_lossy().to_string()))?;
+
+    let mut children = Vec::new();
+    for entry in read_dir {
+        let entry = entry.map_err(|e| ShellErrorKind::Io(None, e))?;
+        let file_name = entry.file_name();
+        if !options.all && file_name.to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        let name = match prefix {
+            Some(prefix) => prefix.join(&file_name),
+            None => PathBuf::from(&file_name),
+        };
+        children.push((name, entry.path(), metadata));
+    }
+    children.sort_by(|a, b| a.0.cmp(&b.0));
+
+    for (name, path, metadata) in children {
+        let is_dir = metadata.is_dir();
+        entries.push(Entry {
+            name: name.to_string_lossy().to_string(),
+            path: path.clone(),
+            metadata,
+        });
+        // unreadable subdirectories are collected instead of aborting the whole listing
+        if is_dir && options.recursive {
+            if let Err(error) = list_dir(&path, Some(&name), options, entries, errors) {
+                errors.push(error);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn to_table(entries: Vec<Entry>, options: &Options) -> Table {
+    let name: Rc<str> = Rc::from("name");
+    let kind: Rc<str> = Rc::from("type");
+    let size: Rc<str> = Rc::from("size");
+    let modified: Rc<str> = Rc::from("modified");
+    let permissions: Rc<str> = Rc::from("permissions");
+    let owner: Rc<str> = Rc::from("owner");
+    let target: Rc<str> = Rc::from("target");
+
+    let mut owners = HashMap::new();
+    let mut table = Table::new();
+    for entry in entries {
+        let metadata = &entry.metadata;
+        let mut row = IndexMap::from([
+            (name.clone(), Value::from(entry.name)),
+            (kind.clone(), Value::from(file_type(metadata))),
+            (size.clone(), Value::Filesize(metadata.len() as i64)),
+            (
+                modified.clone(),
+                match metadata.modified() {
+                    Ok(time) => Value::from(DateTime::<Local>::from(time).fixed_offset()),
+                    Err(_) => Value::Null,
+                },
+            ),
+        ]);
+
+        if options.long {
+            row.insert(permissions.clone(), permissions_string(metadata));
+            row.insert(owner.clone(), file_owner(metadata, &mut owners));
+            row.insert(
+                target.clone(),
+                match fs::read_link(&entry.path) {
+                    Ok(link) => Value::from(link.to_string_lossy().to_string()),
+                    Err(_) => Value::Null,
+                },
+            );
+        }
+
+        table.insert_map(row);
+    }
+    table
+}
+
+pub fn file_type(metadata: &Metadata) -> &'static str {
+    let file_type = metadata.file_type();
+    if file_type.is_symlink() {
+        "symlink"
+    } else if file_type.is_dir() {
+        "dir"
+    } else if file_type.is_file() {
+        "file"
+    } else {
+        "other"
+    }
+}
+
+#[cfg(unix)]
+fn permissions_string(metadata: &Metadata) -> Value {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = metadata.permissions().mode();
+    let mut string = String::with_capacity(10);
+    string.push(match file_type(metadata) {
+        "dir" => 'd',
+        "symlink" => 'l',
+        _ => '-',
+    });
+    for shift in [6, 3, 0] {
+        let bits = (mode >> shift) & 0o7;
+        string.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+        string.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+        string.push(if bits & 0o1 != 0 { 'x' } else { '-' });
+    }
+    Value::from(string)
+}
+
+#[cfg(not(unix))]
+fn permissions_string(metadata: &Metadata) -> Value {
+    if metadata.permissions().readonly() {
+        Value::from("readonly")
+    } else {
+        Value::from("readwrite")
+    }
+}
+
+#[cfg(unix)]
+fn file_owner(metadata: &Metadata, owners: &mut HashMap<u32, Value>) -> Value {
+    use std::os::unix::fs::MetadataExt;
+
+    use nix::unistd::{Uid, User};
+
+    let uid = metadata.uid();
+    owners
+        .entry(uid)
+        .or_insert_with(|| match User::from_uid(Uid::from_raw(uid)) {
+            Ok(Some(user)) => Value::from(user.name),
+            _ => Value::from(uid.to_string()),
+        })
+        .clone()
+}
+
+#[cfg(not(unix))]
+fn file_owner(_: &Metadata, _: &mut HashMap<u32, Value>) -> Value {
+    Value::Null
+}
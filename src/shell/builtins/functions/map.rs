@@ -2,6 +2,7 @@ use std::{iter, rc::Rc};
 
 use once_cell::sync::Lazy;
 
+use super::call_closure;
 use crate::{
     argparse::{App, Arg, Flag, ParseResult},
     parser::{
@@ -10,12 +11,11 @@ use crate::{
     },
     shell::{
         frame::Frame,
-        stream::OutputStream,
         value::{SpannedValue, Type, Value},
     },
 };
 
-static APP: Lazy<App> = Lazy::new(|| {
+pub static APP: Lazy<App> = Lazy::new(|| {
     App::new("map")
         .arg(
             Arg::new("CLOSURE", Type::CLOSURE)
@@ -50,16 +50,14 @@ pub fn map(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorK
 
     let value = match ctx.input.take().unpack() {
         Value::Null => Value::Null,
-        Value::Int(int) => apply_closure(ctx, closure, iter::once(int.into()), empty)?,
-        Value::Float(float) => apply_closure(ctx, closure, iter::once(float.into()), empty)?,
-        Value::Bool(boolean) => apply_closure(ctx, closure, iter::once(boolean.into()), empty)?,
-        Value::String(string) => {
-            apply_closure(ctx, closure, string.chars().map(Value::from), empty)?
-        }
+        Value::Int(int) => map_values(ctx, closure, iter::once(int.into()), empty)?,
+        Value::Float(float) => map_values(ctx, closure, iter::once(float.into()), empty)?,
+        Value::Bool(boolean) => map_values(ctx, closure, iter::once(boolean.into()), empty)?,
+        Value::String(string) => map_values(ctx, closure, string.chars().map(Value::from), empty)?,
         Value::List(list) => {
-            apply_closure(ctx, closure, Rc::unwrap_or_clone(list).into_iter(), empty)?
+            map_values(ctx, closure, Rc::unwrap_or_clone(list).into_iter(), empty)?
         }
-        Value::Map(map) => apply_closure(
+        Value::Map(map) => map_values(
             ctx,
             closure,
             Rc::unwrap_or_clone(map)
@@ -67,7 +65,7 @@ pub fn map(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorK
                 .map(|(key, value)| Value::from(vec![Value::from(key.to_string()), value])),
             empty,
         )?,
-        Value::Table(table) => apply_closure(
+        Value::Table(table) => map_values(
             ctx,
             closure,
             Rc::unwrap_or_clone(table)
@@ -75,42 +73,33 @@ pub fn map(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorK
                 .map(|row| Value::Map(row.into())),
             empty,
         )?,
-        Value::Range(range) => {
-            apply_closure(ctx, closure, (*range).clone().map(Value::from), empty)?
-        }
-        Value::Regex(regex) => apply_closure(ctx, closure, iter::once(Value::Regex(regex)), empty)?,
-        Value::Binary(binary) => apply_closure(
+        Value::Range(range) => map_values(ctx, closure, (*range).clone().map(Value::from), empty)?,
+        Value::Regex(regex) => map_values(ctx, closure, iter::once(Value::Regex(regex)), empty)?,
+        Value::Binary(binary) => map_values(
             ctx,
             closure,
             binary.iter().copied().map(|b| Value::from(b as i64)),
             empty,
         )?,
-        Value::Closure(c) => apply_closure(ctx, closure, iter::once(Value::Closure(c)), empty)?,
+        Value::Closure(c) => map_values(ctx, closure, iter::once(Value::Closure(c)), empty)?,
+        value @ (Value::Date(_) | Value::Duration(_) | Value::Filesize(_) | Value::BigInt(_)) => {
+            map_values(ctx, closure, iter::once(value), empty)?
+        }
     };
 
     ctx.output.push(value)?;
     Ok(())
 }
 
-fn apply_closure(
+fn map_values(
     ctx: &mut Context,
     closure: Rc<(Rc<Closure>, Frame)>,
     iter: impl Iterator<Item = Value>,
     keep_empty: bool,
 ) -> Result<Value, ShellErrorKind> {
-    let (closure, frame) = &*closure;
     let mut output = Vec::new();
     for value in iter {
-        let mut capture = OutputStream::new_capture();
-        let mut ctx = Context {
-            shell: ctx.shell,
-            frame: frame.clone(),
-            output: &mut capture,
-            input: ctx.input,
-            src: ctx.src.clone(),
-        };
-        closure.eval(&mut ctx, iter::once(value))?;
-        let item = capture.into_value_stream().unpack();
+        let item = call_closure(ctx, &closure, value)?;
         if keep_empty || item != Value::Null {
             output.push(item);
         }
@@ -0,0 +1,305 @@
+use std::{cmp::Ordering, rc::Rc};
+
+use bigdecimal::{
+    num_bigint::{BigInt, Sign},
+    FromPrimitive, ToPrimitive,
+};
+use num_traits::{One, Signed, Zero};
+use once_cell::sync::Lazy;
+
+use crate::{
+    argparse::{App, Arg, Opt, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{overflow::Overflow, SpannedValue, Type, Value},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("math")
+        .about("Math functions over numbers and lists of numbers")
+        .sub_cmd(App::new("abs").about("Absolute value"))
+        .sub_cmd(App::new("sqrt").about("Square root"))
+        .sub_cmd(App::new("cbrt").about("Cube root"))
+        .sub_cmd(App::new("floor").about("Round down to an int"))
+        .sub_cmd(App::new("ceil").about("Round up to an int"))
+        .sub_cmd(
+            App::new("round").about("Round to the nearest int").opt(
+                Opt::new("DIGITS", Type::INT)
+                    .long("digits")
+                    .short('d')
+                    .help("Round to a number of decimal digits and return a float"),
+            ),
+        )
+        .sub_cmd(App::new("exp").about("e raised to the power of the number"))
+        .sub_cmd(App::new("ln").about("Natural logarithm"))
+        .sub_cmd(
+            App::new("log").about("Logarithm with a given base").arg(
+                Arg::new("BASE", Type::INT | Type::FLOAT)
+                    .required(true)
+                    .help("Base of the logarithm"),
+            ),
+        )
+        .sub_cmd(App::new("sin").about("Sine of an angle in radians"))
+        .sub_cmd(App::new("cos").about("Cosine of an angle in radians"))
+        .sub_cmd(App::new("tan").about("Tangent of an angle in radians"))
+        .sub_cmd(App::new("asin").about("Arcsine in radians"))
+        .sub_cmd(App::new("acos").about("Arccosine in radians"))
+        .sub_cmd(App::new("atan").about("Arctangent in radians"))
+        .sub_cmd(App::new("sum").about("Sum of a list"))
+        .sub_cmd(App::new("product").about("Product of a list"))
+        .sub_cmd(App::new("min").about("Smallest number in a list"))
+        .sub_cmd(App::new("max").about("Largest number in a list"))
+        .sub_cmd(App::new("avg").about("Average of a list"))
+        .sub_cmd(App::new("gcd").about("Greatest common divisor of a list of ints"))
+        .sub_cmd(App::new("lcm").about("Least common multiple of a list of ints"))
+});
+
+pub fn math(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let overflow = ctx.shell.overflow;
+    let input = ctx.input.take().unpack();
+
+    let output = match matches.sub_cmd() {
+        Some("abs") => map_numbers(input, |value| match value {
+            Value::Int(int) => int_result(BigInt::from(int).abs(), overflow, "math abs"),
+            Value::BigInt(int) => Ok(Value::from(int.abs())),
+            value => Ok(Value::Float(as_float(&value).abs())),
+        })?,
+        Some("floor") => round_with(input, overflow, "math floor", f64::floor)?,
+        Some("ceil") => round_with(input, overflow, "math ceil", f64::ceil)?,
+        Some("round") => match matches.take_value("DIGITS") {
+            Some(digits) => {
+                let Value::Int(digits) = digits.value else {
+                    unreachable!()
+                };
+                let scale = 10f64.powi(digits.clamp(i32::MIN as i64, i32::MAX as i64) as i32);
+                map_floats(input, |float| (float * scale).round() / scale)?
+            }
+            None => round_with(input, overflow, "math round", f64::round)?,
+        },
+        Some("sqrt") => map_floats(input, f64::sqrt)?,
+        Some("cbrt") => map_floats(input, f64::cbrt)?,
+        Some("exp") => map_floats(input, f64::exp)?,
+        Some("ln") => map_floats(input, f64::ln)?,
+        Some("log") => {
+            let base = as_float(&matches.take_value("BASE").unwrap().value);
+            // the dedicated functions are exact for powers of their base
+            map_floats(input, |float| match base {
+                2.0 => float.log2(),
+                10.0 => float.log10(),
+                _ => float.log(base),
+            })?
+        }
+        Some("sin") => map_floats(input, f64::sin)?,
+        Some("cos") => map_floats(input, f64::cos)?,
+        Some("tan") => map_floats(input, f64::tan)?,
+        Some("asin") => map_floats(input, f64::asin)?,
+        Some("acos") => map_floats(input, f64::acos)?,
+        Some("atan") => map_floats(input, f64::atan)?,
+        Some("sum") => {
+            let numbers = numbers(input)?;
+            match all_ints(&numbers) {
+                Some(ints) => int_result(ints.into_iter().sum(), overflow, "math sum")?,
+                None => Value::Float(numbers.iter().map(as_float).sum()),
+            }
+        }
+        Some("product") => {
+            let numbers = numbers(input)?;
+            match all_ints(&numbers) {
+                Some(ints) => int_result(ints.into_iter().product(), overflow, "math product")?,
+                None => Value::Float(numbers.iter().map(as_float).product()),
+            }
+        }
+        Some("min") => extreme(numbers(input)?, Ordering::Less),
+        Some("max") => extreme(numbers(input)?, Ordering::Greater),
+        Some("avg") => {
+            let numbers = numbers(input)?;
+            if numbers.is_empty() {
+                Value::Null
+            } else {
+                let sum: f64 = numbers.iter().map(as_float).sum();
+                Value::Float(sum / numbers.len() as f64)
+            }
+        }
+        Some("gcd") => {
+            let ints = ints(input)?;
+            int_result(
+                ints.into_iter().fold(BigInt::zero(), gcd),
+                overflow,
+                "math gcd",
+            )?
+        }
+        Some("lcm") => {
+            let ints = ints(input)?;
+            let lcm = ints.into_iter().fold(BigInt::one(), |lhs, rhs| {
+                if lhs.is_zero() || rhs.is_zero() {
+                    return BigInt::zero();
+                }
+                let divisor = gcd(lhs.clone(), rhs.clone());
+                (lhs * rhs).abs() / divisor
+            });
+            int_result(lcm, overflow, "math lcm")?
+        }
+        _ => {
+            return Err(ShellErrorKind::Basic(
+                "Math Error",
+                String::from("Missing subcommand, run `math --help` to list the functions"),
+            ))
+        }
+    };
+
+    ctx.output.push(output)?;
+    Ok(())
+}
+
+fn as_float(value: &Value) -> f64 {
+    value.try_as_float().unwrap_or(f64::NAN)
+}
+
+fn gcd(mut lhs: BigInt, mut rhs: BigInt) -> BigInt {
+    while !rhs.is_zero() {
+        let rem = &lhs % &rhs;
+        lhs = rhs;
+        rhs = rem;
+    }
+    lhs.abs()
+}
+
+// ints are computed without limits and only the final result is checked against the overflow mode
+fn int_result(int: BigInt, overflow: Overflow, op: &str) -> Result<Value, ShellErrorKind> {
+    if let Some(int) = int.to_i64() {
+        return Ok(Value::Int(int));
+    }
+    let (sign, digits) = int.to_u64_digits();
+    let low = digits.first().copied().unwrap_or(0) as i64;
+    let wrapped = if sign == Sign::Minus {
+        low.wrapping_neg()
+    } else {
+        low
+    };
+    overflow.overflowed(wrapped, int, op, None)
+}
+
+fn round_with(
+    input: Value,
+    overflow: Overflow,
+    op: &str,
+    f: impl Fn(f64) -> f64,
+) -> Result<Value, ShellErrorKind> {
+    map_numbers(input, |value| match value {
+        value @ (Value::Int(_) | Value::BigInt(_)) => Ok(value),
+        value => {
+            let float = f(as_float(&value));
+            // i64::MAX as f64 rounds up to 2^63 which is already out of range
+            if float.is_finite() && float >= i64::MIN as f64 && float < i64::MAX as f64 {
+                Ok(Value::Int(float as i64))
+            } else {
+                match (overflow, BigInt::from_f64(float)) {
+                    (Overflow::Error, _) => {
+                        Err(ShellErrorKind::ArithmeticOverflow(op.to_string(), None))
+                    }
+                    (Overflow::Promote, Some(int)) => Ok(Value::from(int)),
+                    _ => Ok(Value::Float(float)),
+                }
+            }
+        }
+    })
+}
+
+fn map_floats(input: Value, f: impl Fn(f64) -> f64) -> Result<Value, ShellErrorKind> {
+    map_numbers(input, |value| Ok(Value::Float(f(as_float(&value)))))
+}
+
+// functions on single numbers are applied to every number when the input is a list
+fn map_numbers(
+    input: Value,
+    mut f: impl FnMut(Value) -> Result<Value, ShellErrorKind>,
+) -> Result<Value, ShellErrorKind> {
+    match input {
+        Value::Int(_) | Value::Float(_) | Value::BigInt(_) => f(input),
+        Value::List(_) | Value::Range(_) => {
+            let output = numbers(input)?
+                .into_iter()
+                .map(f)
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Value::from(output))
+        }
+        _ => Err(ShellErrorKind::InvalidPipelineInput {
+            expected: Type::INT | Type::FLOAT | Type::LIST | Type::RANGE,
+            recived: input.to_type(),
+        }),
+    }
+}
+
+fn numbers(input: Value) -> Result<Vec<Value>, ShellErrorKind> {
+    match input {
+        Value::List(list) => {
+            let list = Rc::unwrap_or_clone(list);
+            for value in &list {
+                if !matches!(value, Value::Int(_) | Value::Float(_) | Value::BigInt(_)) {
+                    return Err(ShellErrorKind::InvalidPipelineInput {
+                        expected: Type::INT | Type::FLOAT,
+                        recived: value.to_type(),
+                    });
+                }
+            }
+            Ok(list)
+        }
+        Value::Range(range) => Ok((*range).clone().map(Value::Int).collect()),
+        _ => Err(ShellErrorKind::InvalidPipelineInput {
+            expected: Type::LIST | Type::RANGE,
+            recived: input.to_type(),
+        }),
+    }
+}
+
+fn all_ints(numbers: &[Value]) -> Option<Vec<BigInt>> {
+    numbers
+        .iter()
+        .map(|value| match value {
+            Value::Float(_) => None,
+            value => value.try_as_big_int(),
+        })
+        .collect()
+}
+
+fn ints(input: Value) -> Result<Vec<BigInt>, ShellErrorKind> {
+    let numbers = numbers(input)?;
+    for value in &numbers {
+        if !matches!(value, Value::Int(_) | Value::BigInt(_)) {
+            return Err(ShellErrorKind::InvalidPipelineInput {
+                expected: Type::INT,
+                recived: value.to_type(),
+            });
+        }
+    }
+    Ok(all_ints(&numbers).unwrap_or_default())
+}
+
+// ints and floats are compared by value and the original number is returned
+fn extreme(numbers: Vec<Value>, wanted: Ordering) -> Value {
+    numbers
+        .into_iter()
+        .reduce(|best, value| {
+            let ordering = match (&value, &best) {
+                (Value::Int(lhs), Value::Int(rhs)) => lhs.cmp(rhs),
+                (Value::Int(_) | Value::BigInt(_), Value::Int(_) | Value::BigInt(_)) => {
+                    value.try_as_big_int().cmp(&best.try_as_big_int())
+                }
+                (lhs, rhs) => as_float(lhs).total_cmp(&as_float(rhs)),
+            };
+            if ordering == wanted {
+                value
+            } else {
+                best
+            }
+        })
+        .unwrap_or(Value::Null)
+}
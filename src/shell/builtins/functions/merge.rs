@@ -0,0 +1,104 @@
+use std::rc::Rc;
+
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+
+use crate::{
+    argparse::{App, Arg, Flag, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{table::Table, SpannedValue, Type, Value},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("merge")
+        .about("Merge a map or table into the input, values from OTHER take precedence")
+        .arg(
+            Arg::new("OTHER", Type::MAP | Type::TABLE)
+                .required(true)
+                .help("Map that is merged into every row, or table that is merged row by row"),
+        )
+        .flag(
+            Flag::new("DEEP")
+                .long("deep")
+                .short('d')
+                .help("Merge nested maps instead of replacing them"),
+        )
+});
+
+pub fn merge(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let deep = matches.conatins("DEEP");
+    let other = matches.take_value("OTHER").unwrap().value;
+    let input = ctx.input.take().unpack();
+
+    let output = match (input, other) {
+        (Value::Map(map), Value::Map(other)) => {
+            Value::from(merge_maps(Rc::unwrap_or_clone(map), &other, deep))
+        }
+        (Value::Table(table), Value::Map(other)) => {
+            let mut output = Table::new();
+            for row in table.iter() {
+                output.insert_map(merge_maps(row, &other, deep));
+            }
+            Value::from(output)
+        }
+        (Value::Table(table), Value::Table(other)) => {
+            let mut output = Table::new();
+            let mut other = other.iter();
+            // rows without a partner in the shorter table are kept as they are
+            for row in table.iter() {
+                match other.next() {
+                    Some(other) => output.insert_map(merge_maps(row, &other, deep)),
+                    None => output.insert_map(row),
+                }
+            }
+            for row in other {
+                output.insert_map(row);
+            }
+            Value::from(output)
+        }
+        (Value::Map(_), other) => {
+            return Err(ShellErrorKind::Basic(
+                "Type Error",
+                format!("Can't merge a {} into a map", other.to_type()),
+            ))
+        }
+        (input, _) => {
+            return Err(ShellErrorKind::InvalidPipelineInput {
+                expected: Type::MAP | Type::TABLE,
+                recived: input.to_type(),
+            })
+        }
+    };
+
+    ctx.output.push(output)?;
+    Ok(())
+}
+
+fn merge_maps(
+    mut map: IndexMap<Rc<str>, Value>,
+    other: &IndexMap<Rc<str>, Value>,
+    deep: bool,
+) -> IndexMap<Rc<str>, Value> {
+    for (key, value) in other {
+        match (map.get_mut(key), value) {
+            (Some(Value::Map(existing)), Value::Map(value)) if deep => {
+                let merged = merge_maps(Rc::unwrap_or_clone(existing.clone()), value, deep);
+                *existing = Rc::new(merged);
+            }
+            (Some(existing), value) => *existing = value.clone(),
+            (None, value) => {
+                map.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    map
+}
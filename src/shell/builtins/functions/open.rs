@@ -6,7 +6,7 @@ use crate::{
     shell::value::{SpannedValue, Type},
 };
 
-static APP: Lazy<App> = Lazy::new(|| {
+pub static APP: Lazy<App> = Lazy::new(|| {
     App::new("open")
         .about("Open a file or url with the default program")
         .arg(
@@ -0,0 +1,69 @@
+use once_cell::sync::Lazy;
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::map_strings;
+use crate::{
+    argparse::{App, Arg, Flag, Opt, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{SpannedValue, Type, Value},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("pad")
+        .about("Pad a string to a length")
+        .flag(
+            Flag::new("START")
+                .long("start")
+                .short('s')
+                .help("Pad the start of the string instead of the end"),
+        )
+        .opt(
+            Opt::new("CHAR", Type::STRING)
+                .long("char")
+                .short('c')
+                .help("The string to pad with, defaults to a space"),
+        )
+        .arg(
+            Arg::new("LENGTH", Type::INT)
+                .required(true)
+                .help("Minimum length of the output in graphemes"),
+        )
+});
+
+pub fn pad(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let start = matches.conatins("START");
+    let fill = matches
+        .take_value("CHAR")
+        .map(|value| value.value.unwrap_string().to_string())
+        .unwrap_or_else(|| String::from(" "));
+    if fill.is_empty() {
+        return Err(ShellErrorKind::Basic(
+            "Argument Error",
+            format!("Cannot pad with an empty string\n\n{}", APP.usage()),
+        ));
+    }
+    let length = matches.value("LENGTH").unwrap().value.unwrap_int().max(0) as usize;
+
+    let input = ctx.input.take().unpack();
+    let output = map_strings(input, |string| {
+        let missing = length.saturating_sub(string.graphemes(true).count());
+        let padding: String = fill.graphemes(true).cycle().take(missing).collect();
+        Ok(Value::from(if start {
+            padding + string
+        } else {
+            format!("{string}{padding}")
+        }))
+    })?;
+
+    ctx.output.push(output)?;
+    Ok(())
+}
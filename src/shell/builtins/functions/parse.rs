@@ -0,0 +1,114 @@
+use std::rc::Rc;
+
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::{
+    argparse::{App, Arg, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{table::Table, SpannedValue, Type, Value},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("parse")
+        .about("Parse lines of text into a table")
+        .arg(
+            Arg::new("PATTERN", Type::STRING | Type::REGEX)
+                .required(true)
+                .help("A template like \"{name}: {value}\" or a regex with named groups"),
+        )
+});
+
+pub fn parse(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let regex = match matches.take_value("PATTERN").unwrap().value {
+        Value::Regex(regex) => regex.0.clone(),
+        Value::String(template) => template_to_regex(&template)?,
+        _ => unreachable!(),
+    };
+
+    // unnamed groups become columns named after their index
+    let columns: Vec<(usize, Rc<str>)> = regex
+        .capture_names()
+        .enumerate()
+        .skip(1)
+        .map(|(index, name)| match name {
+            Some(name) => (index, Rc::from(name)),
+            None => (index, Rc::from(index.to_string())),
+        })
+        .collect();
+
+    let input = ctx.input.take().unpack();
+    let mut lines = Vec::new();
+    match input {
+        Value::String(string) => lines.extend(string.lines().map(String::from)),
+        Value::List(list) => {
+            for value in list.iter() {
+                match value {
+                    Value::String(string) => lines.extend(string.lines().map(String::from)),
+                    _ => {
+                        return Err(ShellErrorKind::InvalidPipelineInput {
+                            expected: Type::STRING,
+                            recived: value.to_type(),
+                        })
+                    }
+                }
+            }
+        }
+        _ => {
+            return Err(ShellErrorKind::InvalidPipelineInput {
+                expected: Type::STRING | Type::LIST,
+                recived: input.to_type(),
+            })
+        }
+    }
+
+    let mut table = Table::new();
+    for line in lines {
+        let Some(captures) = regex.captures(&line) else {
+            continue;
+        };
+        let mut row = IndexMap::new();
+        for (index, column) in &columns {
+            let value = match captures.get(*index) {
+                Some(group) => Value::from(group.as_str()),
+                None => Value::Null,
+            };
+            row.insert(column.clone(), value);
+        }
+        table.insert_map(row);
+    }
+
+    ctx.output.push(Value::from(table))?;
+    Ok(())
+}
+
+// every `{name}` becomes a lazy named group and everything else is matched literally
+fn template_to_regex(template: &str) -> Result<Regex, ShellErrorKind> {
+    let mut pattern = String::from("^");
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 1..start + len];
+        pattern.push_str(&regex::escape(&rest[..start]));
+        pattern.push_str(&format!("(?P<{name}>.*?)"));
+        rest = &rest[start + len + 1..];
+    }
+    pattern.push_str(&regex::escape(rest));
+    pattern.push('$');
+
+    Regex::new(&pattern).map_err(|e| {
+        ShellErrorKind::Basic("Parse Error", format!("Invalid template `{template}`: {e}"))
+    })
+}
@@ -0,0 +1,278 @@
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+    rc::Rc,
+};
+
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+
+use super::{ls::file_type, map_strings};
+use crate::{
+    argparse::{App, Arg, Opt, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::{
+        current_dir_path,
+        value::{SpannedValue, Type, Value},
+    },
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("path")
+        .about("Work with file paths")
+        .sub_cmd(
+            column_app("join", "Join a path or a list of parts into one path").arg(
+                Arg::new("PARTS", Type::STRING)
+                    .multiple(true)
+                    .help("Parts appended to the path"),
+            ),
+        )
+        .sub_cmd(column_app(
+            "parse",
+            "Split a path into its parent, stem, extension and components",
+        ))
+        .sub_cmd(column_app(
+            "expand",
+            "Expand `~` and resolve the absolute path following symlinks",
+        ))
+        .sub_cmd(
+            column_app("relative-to", "Get the path relative to another path").arg(
+                Arg::new("BASE", Type::STRING)
+                    .required(true)
+                    .help("The path the result is relative to"),
+            ),
+        )
+        .sub_cmd(column_app("exists", "Check if a path exists"))
+        .sub_cmd(column_app(
+            "type",
+            "Get the type of the file a path points to, null if it does not exist",
+        ))
+});
+
+// every subcommand takes a path, a list of paths or a table with a column of paths
+fn column_app(name: &str, about: &str) -> App {
+    App::new(name).about(about).opt(
+        Opt::new("COLUMN", Type::STRING)
+            .long("column")
+            .short('c')
+            .help("Column of paths to use when the input is a table"),
+    )
+}
+
+pub fn path(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let column = matches
+        .take_value("COLUMN")
+        .map(|v| v.value.unwrap_string());
+    let column = column.as_deref().map(|column| column.as_str());
+    let input = ctx.input.take().unpack();
+    let home = ctx.shell.home_dir();
+
+    let output = match matches.sub_cmd() {
+        Some("join") => {
+            let parts: Vec<Rc<String>> = matches
+                .take_values("PARTS")
+                .unwrap_or_default()
+                .into_iter()
+                .map(|part| part.value.unwrap_string())
+                .collect();
+            // a list is the parts of a single path here, not a list of paths
+            map_column(input, column, |value| {
+                let mut path = match value {
+                    Value::String(string) => PathBuf::from(string.as_str()),
+                    Value::List(list) => {
+                        let mut path = PathBuf::new();
+                        for part in list.iter() {
+                            match part {
+                                Value::String(part) => path.push(part.as_str()),
+                                _ => {
+                                    return Err(ShellErrorKind::InvalidPipelineInput {
+                                        expected: Type::STRING,
+                                        recived: part.to_type(),
+                                    })
+                                }
+                            }
+                        }
+                        path
+                    }
+                    _ => {
+                        return Err(ShellErrorKind::InvalidPipelineInput {
+                            expected: Type::STRING | Type::LIST,
+                            recived: value.to_type(),
+                        })
+                    }
+                };
+                for part in &parts {
+                    path.push(part.as_str());
+                }
+                Ok(path_to_value(&path))
+            })?
+        }
+        Some("parse") => map_paths(input, column, |path| Ok(parse(Path::new(path))))?,
+        Some("expand") => map_paths(input, column, |path| {
+            Ok(path_to_value(&expand(Path::new(path), &home)))
+        })?,
+        Some("relative-to") => {
+            let base = matches.take_value("BASE").unwrap().value.unwrap_string();
+            let base = absolute(&expand_tilde(Path::new(base.as_str()), &home));
+            map_paths(input, column, |path| {
+                let path = absolute(&expand_tilde(Path::new(path), &home));
+                match relative_to(&path, &base) {
+                    Some(relative) => Ok(path_to_value(&relative)),
+                    None => Err(ShellErrorKind::Basic(
+                        "Path Error",
+                        format!(
+                            "`{}` has no relative path to `{}`",
+                            path.display(),
+                            base.display()
+                        ),
+                    )),
+                }
+            })?
+        }
+        Some("exists") => map_paths(input, column, |path| {
+            Ok(Value::Bool(fs::symlink_metadata(path).is_ok()))
+        })?,
+        Some("type") => map_paths(input, column, |path| match fs::symlink_metadata(path) {
+            Ok(metadata) => Ok(Value::from(file_type(&metadata))),
+            Err(_) => Ok(Value::Null),
+        })?,
+        _ => {
+            return Err(ShellErrorKind::Basic(
+                "Path Error",
+                String::from(
+                    "Missing subcommand, expected one of join, parse, expand, relative-to, exists or type",
+                ),
+            ))
+        }
+    };
+
+    ctx.output.push(output)?;
+    Ok(())
+}
+
+fn map_column(
+    input: Value,
+    column: Option<&str>,
+    mut f: impl FnMut(Value) -> Result<Value, ShellErrorKind>,
+) -> Result<Value, ShellErrorKind> {
+    match (input, column) {
+        (Value::Table(table), Some(column)) => {
+            let mut table = Rc::unwrap_or_clone(table);
+            table.map_column(column, f)?;
+            Ok(Value::from(table))
+        }
+        (Value::Table(_), None) => Err(ShellErrorKind::Basic(
+            "Path Error",
+            String::from("A column has to be given with `--column` when the input is a table"),
+        )),
+        (input, _) => f(input),
+    }
+}
+
+fn map_paths(
+    input: Value,
+    column: Option<&str>,
+    mut f: impl FnMut(&str) -> Result<Value, ShellErrorKind>,
+) -> Result<Value, ShellErrorKind> {
+    match input {
+        Value::Table(_) => map_column(input, column, |value| match value {
+            Value::String(path) => f(&path),
+            _ => Err(ShellErrorKind::InvalidPipelineInput {
+                expected: Type::STRING,
+                recived: value.to_type(),
+            }),
+        }),
+        input => map_strings(input, f),
+    }
+}
+
+fn path_to_value(path: &Path) -> Value {
+    Value::from(path.to_string_lossy().into_owned())
+}
+
+fn parse(path: &Path) -> Value {
+    let optional = |part: Option<&std::ffi::OsStr>| match part {
+        Some(part) => Value::from(part.to_string_lossy().into_owned()),
+        None => Value::Null,
+    };
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => path_to_value(parent),
+        _ => Value::Null,
+    };
+    let components: Vec<Value> = path
+        .components()
+        .map(|component| Value::from(component.as_os_str().to_string_lossy().into_owned()))
+        .collect();
+
+    Value::from(IndexMap::from([
+        (Rc::from("parent"), parent),
+        (Rc::from("stem"), optional(path.file_stem())),
+        (Rc::from("extension"), optional(path.extension())),
+        (Rc::from("components"), Value::from(components)),
+    ]))
+}
+
+fn expand_tilde(path: &Path, home: &Path) -> PathBuf {
+    match path.strip_prefix("~") {
+        Ok(rest) => home.join(rest),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+// `.` and `..` are resolved without touching the filesystem
+fn absolute(path: &Path) -> PathBuf {
+    let mut absolute = PathBuf::new();
+    for component in current_dir_path().join(path).components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir => {
+                absolute.pop();
+            }
+            component => absolute.push(component),
+        }
+    }
+    absolute
+}
+
+// `..` after a symlink leads to the parent of its target so paths are resolved by the filesystem,
+// only paths that do not exist are normalized as text
+fn expand(path: &Path, home: &Path) -> PathBuf {
+    let path = current_dir_path().join(expand_tilde(path, home));
+    fs::canonicalize(&path).unwrap_or_else(|_| absolute(&path))
+}
+
+fn relative_to(path: &Path, base: &Path) -> Option<PathBuf> {
+    let mut path_components = path.components().peekable();
+    let mut base_components = base.components().peekable();
+    while let (Some(lhs), Some(rhs)) = (path_components.peek(), base_components.peek()) {
+        if lhs != rhs {
+            break;
+        }
+        path_components.next();
+        base_components.next();
+    }
+
+    // paths on different windows drives share nothing
+    if path_components
+        .peek()
+        .is_some_and(|c| matches!(c, Component::Prefix(_)))
+    {
+        return None;
+    }
+
+    let mut relative: PathBuf = base_components.map(|_| Component::ParentDir).collect();
+    relative.extend(path_components);
+    if relative.as_os_str().is_empty() {
+        relative.push(".");
+    }
+    Some(relative)
+}
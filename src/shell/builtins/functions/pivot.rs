@@ -0,0 +1,86 @@
+use std::rc::Rc;
+
+use indexmap::{IndexMap, IndexSet};
+use once_cell::sync::Lazy;
+
+use crate::{
+    argparse::{App, Arg, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{hashable::HashableValue, table::Table, SpannedValue, Type, Value},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("pivot")
+        .about("Turn a long table into a wide one with a row per ROW value and a column per COLUMN value")
+        .arg(
+            Arg::new("ROW", Type::STRING)
+                .required(true)
+                .help("Column whose unique values become the rows"),
+        )
+        .arg(
+            Arg::new("COLUMN", Type::STRING)
+                .required(true)
+                .help("Column whose unique values become the new columns"),
+        )
+        .arg(
+            Arg::new("VALUE", Type::STRING)
+                .required(true)
+                .help("Column holding the cell values, later rows win when a cell is set twice"),
+        )
+});
+
+pub fn pivot(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let row_column = matches.take_value("ROW").unwrap().value.unwrap_string();
+    let column_column = matches.take_value("COLUMN").unwrap().value.unwrap_string();
+    let value_column = matches.take_value("VALUE").unwrap().value.unwrap_string();
+
+    let input = ctx.input.take().unpack();
+    let Value::Table(table) = input else {
+        return Err(ShellErrorKind::InvalidPipelineInput {
+            expected: Type::TABLE,
+            recived: input.to_type(),
+        });
+    };
+
+    let row_keys = table.column(&row_column)?;
+    let column_keys = table.column(&column_column)?;
+    let values = table.column(&value_column)?;
+
+    let mut columns: IndexSet<Rc<str>> = IndexSet::new();
+    let mut rows: IndexMap<HashableValue, IndexMap<Rc<str>, Value>> = IndexMap::new();
+    for ((row, column), value) in row_keys.into_iter().zip(column_keys).zip(values) {
+        let column: Rc<str> = Rc::from(column.to_string());
+        columns.insert(column.clone());
+        rows.entry(row.into_hashable())
+            .or_default()
+            .insert(column, value);
+    }
+
+    let mut headers = vec![Rc::from(row_column.as_str())];
+    headers.extend(columns.iter().cloned());
+    let rows = rows
+        .into_iter()
+        .map(|(row, mut cells)| {
+            let mut output = vec![Value::from(row)];
+            output.extend(
+                columns
+                    .iter()
+                    .map(|column| cells.shift_remove(column).unwrap_or(Value::Null)),
+            );
+            output
+        })
+        .collect();
+
+    ctx.output
+        .push(Value::from(Table::from_parts(headers, rows)))?;
+    Ok(())
+}
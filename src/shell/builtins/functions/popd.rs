@@ -0,0 +1,18 @@
+use once_cell::sync::Lazy;
+
+use crate::{
+    argparse::{App, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::{dir_history::DirHistory, value::SpannedValue},
+};
+
+pub static APP: Lazy<App> =
+    Lazy::new(|| App::new("popd").about("Remove the top of the directory stack and change to it"));
+
+pub fn popd(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    match APP.parse(args) {
+        Ok(ParseResult::Matches(_)) => DirHistory::pop(ctx.shell),
+        Ok(ParseResult::Info(info)) => ctx.output.push(info),
+        Err(e) => Err(e.into()),
+    }
+}
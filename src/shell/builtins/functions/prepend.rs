@@ -0,0 +1,42 @@
+use once_cell::sync::Lazy;
+
+use super::{from_items, into_items};
+use crate::{
+    argparse::{App, Arg, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{SpannedValue, Type, Value},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("prepend")
+        .about("Add items to the start of a sequence")
+        .arg(
+            Arg::new("VALUES", Type::ANY)
+                .required(true)
+                .multiple(true)
+                .help("Items to add, lists are added as a single item"),
+        )
+});
+
+pub fn prepend(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let values = matches
+        .take_values("VALUES")
+        .unwrap()
+        .into_iter()
+        .map(|value| value.value);
+    let input = into_items(ctx.input.take().unpack())?;
+    let mut output: Vec<Value> = values.collect();
+    output.extend(input);
+
+    ctx.output.push(from_items(output))?;
+    Ok(())
+}
@@ -0,0 +1,177 @@
+use once_cell::sync::Lazy;
+
+use crate::{
+    argparse::{App, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{SpannedValue, Value},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| App::new("ps").about("List running processes"));
+
+pub fn ps(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let _ = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            ctx.output.push(Value::from(linux::processes()?))?;
+            Ok(())
+        } else {
+            Err(ShellErrorKind::Basic(
+                "Process Error",
+                String::from("ps is only supported on Linux"),
+            ))
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::{collections::HashMap, fs, rc::Rc};
+
+    use indexmap::IndexMap;
+    use nix::unistd::{sysconf, SysconfVar, Uid, User};
+
+    use crate::{
+        parser::shell_error::ShellErrorKind,
+        shell::{
+            builtins::functions::file_err_to_shell_err,
+            value::{table::Table, Value},
+        },
+    };
+
+    struct Process {
+        pid: i64,
+        ppid: i64,
+        name: String,
+        command: String,
+        state: &'static str,
+        cpu: f64,
+        rss: i64,
+        uid: Option<u32>,
+    }
+
+    pub fn processes() -> Result<Table, ShellErrorKind> {
+        let ticks = sysconf(SysconfVar::CLK_TCK).ok().flatten().unwrap_or(100) as f64;
+        let page_size = sysconf(SysconfVar::PAGE_SIZE)
+            .ok()
+            .flatten()
+            .unwrap_or(4096) as i64;
+
+        let mut processes = Vec::new();
+        let read_dir =
+            fs::read_dir("/proc").map_err(|e| file_err_to_shell_err(e, String::from("/proc")))?;
+        for entry in read_dir.flatten() {
+            let Ok(pid) = entry.file_name().to_string_lossy().parse::<i64>() else {
+                continue;
+            };
+            // processes can exit while we are reading them so any failure just skips the process
+            if let Some(process) = read_process(pid, ticks, page_size) {
+                processes.push(process);
+            }
+        }
+        processes.sort_by_key(|process| process.pid);
+
+        let pid: Rc<str> = Rc::from("pid");
+        let ppid: Rc<str> = Rc::from("ppid");
+        let name: Rc<str> = Rc::from("name");
+        let command: Rc<str> = Rc::from("command");
+        let state: Rc<str> = Rc::from("state");
+        let cpu: Rc<str> = Rc::from("cpu");
+        let rss: Rc<str> = Rc::from("rss");
+        let user: Rc<str> = Rc::from("user");
+
+        let mut users = HashMap::new();
+        let mut table = Table::new();
+        for process in processes {
+            let user_name = match process.uid {
+                Some(uid) => users
+                    .entry(uid)
+                    .or_insert_with(|| match User::from_uid(Uid::from_raw(uid)) {
+                        Ok(Some(user)) => Value::from(user.name),
+                        _ => Value::from(uid.to_string()),
+                    })
+                    .clone(),
+                None => Value::Null,
+            };
+
+            table.insert_map(IndexMap::from([
+                (pid.clone(), Value::Int(process.pid)),
+                (ppid.clone(), Value::Int(process.ppid)),
+                (name.clone(), Value::from(process.name)),
+                (command.clone(), Value::from(process.command)),
+                (state.clone(), Value::from(process.state)),
+                (cpu.clone(), Value::Float(process.cpu)),
+                (rss.clone(), Value::Filesize(process.rss)),
+                (user.clone(), user_name),
+            ]));
+        }
+        Ok(table)
+    }
+
+    fn read_process(pid: i64, ticks: f64, page_size: i64) -> Option<Process> {
+        let stat = fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+        // the name is wrapped in parentheses and can itself contain both spaces and parentheses
+        let name_start = stat.find('(')?;
+        let name_end = stat.rfind(')')?;
+        let name = stat[name_start + 1..name_end].to_string();
+        let fields: Vec<&str> = stat[name_end + 1..].split_whitespace().collect();
+
+        // field indices are offset by the pid and name which come before the state
+        let state = match *fields.first()? {
+            "R" => "running",
+            "S" => "sleeping",
+            "D" => "disk sleep",
+            "Z" => "zombie",
+            "T" => "stopped",
+            "t" => "tracing stop",
+            "X" | "x" => "dead",
+            "I" => "idle",
+            "P" => "parked",
+            "W" => "waking",
+            _ => "unknown",
+        };
+        let ppid = fields.get(1)?.parse().ok()?;
+        let utime: u64 = fields.get(11)?.parse().ok()?;
+        let stime: u64 = fields.get(12)?.parse().ok()?;
+        let rss: i64 = fields.get(21)?.parse().ok()?;
+
+        let command = match fs::read(format!("/proc/{pid}/cmdline")) {
+            Ok(cmdline) if !cmdline.is_empty() => cmdline
+                .split(|b| *b == 0)
+                .filter(|arg| !arg.is_empty())
+                .map(String::from_utf8_lossy)
+                .collect::<Vec<_>>()
+                .join(" "),
+            // kernel threads have no command line
+            _ => format!("[{name}]"),
+        };
+
+        let uid = fs::read_to_string(format!("/proc/{pid}/status"))
+            .ok()
+            .and_then(|status| {
+                status
+                    .lines()
+                    .find_map(|line| line.strip_prefix("Uid:"))
+                    .and_then(|uids| uids.split_whitespace().next())
+                    .and_then(|uid| uid.parse().ok())
+            });
+
+        Some(Process {
+            pid,
+            ppid,
+            name,
+            command,
+            state,
+            cpu: (utime + stime) as f64 / ticks,
+            rss: rss * page_size,
+            uid,
+        })
+    }
+}
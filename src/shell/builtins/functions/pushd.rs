@@ -0,0 +1,35 @@
+use once_cell::sync::Lazy;
+
+use crate::{
+    argparse::{App, Arg, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::{
+        dir_history::DirHistory,
+        value::{SpannedValue, Type},
+    },
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("pushd")
+        .about("Save the working directory on the directory stack and change to another")
+        .arg(
+            Arg::new("DIRECTORY", Type::STRING)
+                .help("The new working directory, swaps with the top of the stack if omitted"),
+        )
+});
+
+pub fn pushd(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    match matches.take_value("DIRECTORY") {
+        Some(dir) => DirHistory::push(ctx.shell, &*dir.value.unwrap_string()),
+        None => DirHistory::swap(ctx.shell),
+    }
+}
@@ -6,7 +6,7 @@ use crate::{
     shell::{current_dir_str, value::SpannedValue, Value},
 };
 
-static APP: Lazy<App> = Lazy::new(|| {
+pub static APP: Lazy<App> = Lazy::new(|| {
     App::new("pwd")
         .about("Print current working directory")
         .flag(Flag::new("PHYSICAL").short('p').help("Resolve symlinks"))
@@ -0,0 +1,61 @@
+use once_cell::sync::Lazy;
+
+use super::map_strings;
+use crate::{
+    argparse::{App, Arg, Flag, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{SpannedValue, Type, Value},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("replace")
+        .about("Replace matches in a string")
+        .flag(
+            Flag::new("ALL")
+                .long("all")
+                .short('a')
+                .help("Replace every match instead of only the first"),
+        )
+        .arg(
+            Arg::new("PATTERN", Type::STRING | Type::REGEX)
+                .required(true)
+                .help("String or regex to replace"),
+        )
+        .arg(Arg::new("REPLACEMENT", Type::STRING).required(true).help(
+            "Replacement, capture groups can be used with $1 or ${name} when PATTERN is a regex",
+        ))
+});
+
+pub fn replace(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let all = matches.conatins("ALL");
+    let pattern = matches.take_value("PATTERN").unwrap().value;
+    let replacement = matches
+        .take_value("REPLACEMENT")
+        .unwrap()
+        .value
+        .unwrap_string();
+
+    let input = ctx.input.take().unpack();
+    let output = map_strings(input, |string| {
+        let replaced = match &pattern {
+            Value::Regex(regex) if all => regex.0.replace_all(string, replacement.as_str()),
+            Value::Regex(regex) => regex.0.replace(string, replacement.as_str()),
+            Value::String(pattern) if all => string.replace(pattern.as_str(), &replacement).into(),
+            Value::String(pattern) => string.replacen(pattern.as_str(), &replacement, 1).into(),
+            _ => unreachable!(),
+        };
+        Ok(Value::from(replaced.into_owned()))
+    })?;
+
+    ctx.output.push(output)?;
+    Ok(())
+}
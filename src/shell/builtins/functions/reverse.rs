@@ -0,0 +1,50 @@
+use std::rc::Rc;
+
+use once_cell::sync::Lazy;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    argparse::{App, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{SpannedValue, Type, Value},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("reverse").about("Reverse a string or the order of a list, range or table")
+});
+
+pub fn reverse(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let _ = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let input = ctx.input.take().unpack();
+    let output = match input {
+        Value::String(string) => Value::from(string.graphemes(true).rev().collect::<String>()),
+        Value::List(mut list) => {
+            Rc::make_mut(&mut list).reverse();
+            Value::List(list)
+        }
+        Value::Range(range) => {
+            Value::from((*range).clone().rev().map(Value::from).collect::<Vec<_>>())
+        }
+        Value::Table(mut table) => {
+            Rc::make_mut(&mut table).rows_mut().reverse();
+            Value::Table(table)
+        }
+        _ => {
+            return Err(ShellErrorKind::InvalidPipelineInput {
+                expected: Type::STRING | Type::LIST | Type::RANGE | Type::TABLE,
+                recived: input.to_type(),
+            })
+        }
+    };
+
+    ctx.output.push(output)?;
+    Ok(())
+}
@@ -10,7 +10,7 @@ use crate::{
     shell::value::{save::save_value, SpannedValue, Type, Value},
 };
 
-static APP: Lazy<App> = Lazy::new(|| {
+pub static APP: Lazy<App> = Lazy::new(|| {
     App::new("save")
         .about("Save data to file")
         .arg(
@@ -23,14 +23,22 @@ static APP: Lazy<App> = Lazy::new(|| {
                 .long("str")
                 .short('s')
                 .help("Save raw text data")
-                .conflicts_with("RAW".into()),
+                .conflicts_with("RAW".into())
+                .conflicts_with("TAGGED".into()),
         )
         .flag(
             Flag::new("RAW")
                 .long("raw")
                 .short('r')
                 .help("Save raw binary data")
-                .conflicts_with("STR".into()),
+                .conflicts_with("STR".into())
+                .conflicts_with("TAGGED".into()),
+        )
+        .flag(
+            Flag::new("TAGGED")
+                .long("tagged")
+                .short('t')
+                .help("Save every type except closures exactly so it can be loaded again with `load --tagged`"),
         )
         .flag(
             Flag::new("PRETTY")
@@ -67,6 +75,7 @@ pub fn save(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellError
 
     let pretty = matches.conatins(&String::from("PRETTY"));
     let append = matches.conatins(&String::from("APPEND"));
+    let tagged = matches.conatins("TAGGED");
 
     let input = ctx.input.take();
 
@@ -116,7 +125,7 @@ pub fn save(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellError
         };
         save_file(path, &data, append)?;
     } else {
-        save_value(path, input, append, pretty)?;
+        save_value(path, input, append, pretty, tagged)?;
     }
 
     Ok(())
@@ -0,0 +1,42 @@
+use once_cell::sync::Lazy;
+
+use super::cell_path_arg;
+use crate::{
+    argparse::{App, Arg, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{cell_path::set_path, SpannedValue, Type},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("set")
+        .about("Replace the value at a cell path, the path has to exist")
+        .arg(
+            Arg::new("PATH", Type::STRING | Type::INT)
+                .required(true)
+                .help("Keys, columns and indexes separated by dots"),
+        )
+        .arg(
+            Arg::new("VALUE", Type::ANY)
+                .required(true)
+                .help("The new value"),
+        )
+});
+
+pub fn set(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let (path, span) = cell_path_arg(matches.take_value("PATH").unwrap());
+    let value = matches.take_value("VALUE").unwrap().value;
+    let mut input = ctx.input.take().unpack();
+    set_path(&mut input, &path, value, false, span)?;
+
+    ctx.output.push(input)?;
+    Ok(())
+}
@@ -9,7 +9,7 @@ use crate::{
     shell::value::{SpannedValue, Value},
 };
 
-static APP: Lazy<App> = Lazy::new(|| App::new("shuffle").about("Shuffle items in container"));
+pub static APP: Lazy<App> = Lazy::new(|| App::new("shuffle").about("Shuffle items in container"));
 
 pub fn shuffle(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
     let _ = match APP.parse(args) {
@@ -0,0 +1,42 @@
+use once_cell::sync::Lazy;
+
+use super::map_strings;
+use crate::{
+    argparse::{App, Arg, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{SpannedValue, Type, Value},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("split").about("Split a string into a list").arg(
+        Arg::new("SEPARATOR", Type::STRING | Type::REGEX)
+            .help("String or regex to split on, splits on whitespace if omitted"),
+    )
+});
+
+pub fn split(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let separator = matches.take_value("SEPARATOR").map(|value| value.value);
+    let input = ctx.input.take().unpack();
+    let output = map_strings(input, |string| {
+        let parts: Vec<Value> = match &separator {
+            Some(Value::String(separator)) => {
+                string.split(separator.as_str()).map(Value::from).collect()
+            }
+            Some(Value::Regex(regex)) => regex.0.split(string).map(Value::from).collect(),
+            _ => string.split_whitespace().map(Value::from).collect(),
+        };
+        Ok(Value::from(parts))
+    })?;
+
+    ctx.output.push(output)?;
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use once_cell::sync::Lazy;
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::map_strings;
+use crate::{
+    argparse::{App, Arg, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{SpannedValue, Type, Value},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("substring")
+        .about("Get part of a string")
+        .arg(
+            Arg::new("START", Type::INT)
+                .required(true)
+                .help("Index of the first grapheme, negative indices count from the end"),
+        )
+        .arg(
+            Arg::new("END", Type::INT)
+                .help("Index after the last grapheme, defaults to the end of the string"),
+        )
+});
+
+pub fn substring(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let start = matches.value("START").unwrap().value.unwrap_int();
+    let end = matches.value("END").map(|value| value.value.unwrap_int());
+
+    let input = ctx.input.take().unpack();
+    let output = map_strings(input, |string| {
+        let graphemes: Vec<&str> = string.graphemes(true).collect();
+        let len = graphemes.len();
+        let start = clamp_index(start, len);
+        let end = end.map(|end| clamp_index(end, len)).unwrap_or(len);
+        if start >= end {
+            return Ok(Value::from(""));
+        }
+        Ok(Value::from(graphemes[start..end].concat()))
+    })?;
+
+    ctx.output.push(output)?;
+    Ok(())
+}
+
+// out of bounds indices are clamped so that substring never fails on short strings
+fn clamp_index(index: i64, len: usize) -> usize {
+    if index < 0 {
+        len.saturating_sub(index.unsigned_abs() as usize)
+    } else {
+        (index as usize).min(len)
+    }
+}
@@ -9,7 +9,7 @@ use crate::{
 };
 
 // TODO add output formats
-static APP: Lazy<App> = Lazy::new(|| {
+pub static APP: Lazy<App> = Lazy::new(|| {
     App::new("time")
         .about("Measure time it takes to execute closure")
         .arg(
@@ -45,7 +45,9 @@ pub fn time(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellError
     let before = Instant::now();
     closure.eval(&mut ctx, iter::empty())?;
     let duration = Instant::now().duration_since(before);
-    ctx.output.push(Value::from(format!("{:?}", duration)))?;
+    ctx.output.push(Value::Duration(
+        duration.as_nanos().try_into().unwrap_or(i64::MAX),
+    ))?;
 
     Ok(())
 }
@@ -0,0 +1,52 @@
+use once_cell::sync::Lazy;
+
+use crate::{
+    argparse::{App, Arg, Flag, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{
+        save::{serialize, serialize_tagged},
+        SpannedValue, Type, Value,
+    },
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("to")
+        .about("Serialize data to a string")
+        .arg(
+            Arg::new("FORMAT", Type::STRING)
+                .help("Format of the data (json, toml, yaml or ini)")
+                .required(true),
+        )
+        .flag(
+            Flag::new("PRETTY")
+                .long("pretty")
+                .short('p')
+                .help("Prettify the output"),
+        )
+        .flag(Flag::new("TAGGED").long("tagged").short('t').help(
+            "Serialize every type except closures exactly so it can be parsed with `from --tagged`",
+        ))
+});
+
+pub fn to(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let format = matches.take_value("FORMAT").unwrap().value.unwrap_string();
+    let pretty = matches.conatins("PRETTY");
+
+    let input = ctx.input.take().unpack();
+    let data = if matches.conatins("TAGGED") {
+        serialize_tagged(&format, &input, pretty)?
+    } else {
+        serialize(&format, &input, pretty)?
+    };
+    ctx.output.push(Value::from(data))?;
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use std::rc::Rc;
+
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+
+use crate::{
+    argparse::{App, Flag, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{table::Table, SpannedValue, Type, Value},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("transpose")
+        .about("Turn a map into a key and value table, or swap the rows and columns of a table")
+        .flag(
+            Flag::new("MAP")
+                .long("map")
+                .short('m')
+                .help("Turn a table with two columns into a map of the first column to the second"),
+        )
+});
+
+pub fn transpose(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let input = ctx.input.take().unpack();
+    let output = match input {
+        Value::Map(map) => {
+            let headers = vec![Rc::from("key"), Rc::from("value")];
+            let rows = map
+                .iter()
+                .map(|(key, value)| vec![Value::from(key.to_string()), value.clone()])
+                .collect();
+            Value::from(Table::from_parts(headers, rows))
+        }
+        Value::Table(table) if matches.conatins("MAP") => {
+            if table.headers().len() != 2 {
+                return Err(ShellErrorKind::Basic(
+                    "Value Error",
+                    format!(
+                        "Expected a table with 2 columns but it has {}",
+                        table.headers().len()
+                    ),
+                ));
+            }
+            let mut map = IndexMap::new();
+            for row in table.rows() {
+                map.insert(Rc::from(row[0].to_string()), row[1].clone());
+            }
+            Value::from(map)
+        }
+        // the header names become the first column and every row becomes a column named after its index
+        Value::Table(table) => {
+            let mut headers = vec![Rc::from("column")];
+            headers.extend((0..table.len()).map(|index| Rc::from(index.to_string())));
+            let rows = table
+                .headers()
+                .iter()
+                .enumerate()
+                .map(|(column, header)| {
+                    let mut row = vec![Value::from(header.to_string())];
+                    row.extend(table.rows().iter().map(|row| row[column].clone()));
+                    row
+                })
+                .collect();
+            Value::from(Table::from_parts(headers, rows))
+        }
+        input => {
+            return Err(ShellErrorKind::InvalidPipelineInput {
+                expected: Type::MAP | Type::TABLE,
+                recived: input.to_type(),
+            })
+        }
+    };
+
+    ctx.output.push(output)?;
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use once_cell::sync::Lazy;
+
+use super::map_strings;
+use crate::{
+    argparse::{App, Arg, Flag, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{SpannedValue, Type, Value},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("trim")
+        .about("Remove leading and trailing characters from a string")
+        .flag(
+            Flag::new("START")
+                .long("start")
+                .short('s')
+                .help("Only trim the start of the string")
+                .conflicts_with("END".into()),
+        )
+        .flag(
+            Flag::new("END")
+                .long("end")
+                .short('e')
+                .help("Only trim the end of the string")
+                .conflicts_with("START".into()),
+        )
+        .arg(
+            Arg::new("CHARS", Type::STRING)
+                .help("Characters to remove, removes whitespace if omitted"),
+        )
+});
+
+pub fn trim(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let start = !matches.conatins("END");
+    let end = !matches.conatins("START");
+    let chars: Option<Vec<char>> = matches
+        .take_value("CHARS")
+        .map(|value| value.value.unwrap_string().chars().collect());
+
+    let pred = |c: char| match &chars {
+        Some(chars) => chars.contains(&c),
+        None => c.is_whitespace(),
+    };
+
+    let input = ctx.input.take().unpack();
+    let output = map_strings(input, |mut string| {
+        if start {
+            string = string.trim_start_matches(pred);
+        }
+        if end {
+            string = string.trim_end_matches(pred);
+        }
+        Ok(Value::from(string))
+    })?;
+
+    ctx.output.push(output)?;
+    Ok(())
+}
@@ -6,7 +6,7 @@ use crate::{
     shell::value::{SpannedValue, Type},
 };
 
-static APP: Lazy<App> = Lazy::new(|| {
+pub static APP: Lazy<App> = Lazy::new(|| {
     App::new("unalias").about("Remove alias").arg(
         Arg::new("NAME", Type::STRING)
             .help("Name of the alias")
@@ -0,0 +1,39 @@
+use indexmap::IndexSet;
+use once_cell::sync::Lazy;
+
+use super::{from_items, into_items};
+use crate::{
+    argparse::{App, Arg, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{hashable::HashableValue, SpannedValue, Type, Value},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("union")
+        .about("Items that are in the input or the other sequence, without duplicates")
+        .arg(
+            Arg::new("OTHER", Type::LIST | Type::RANGE | Type::TABLE)
+                .required(true)
+                .help("The other sequence"),
+        )
+});
+
+pub fn union(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let other = into_items(matches.take_value("OTHER").unwrap().value)?;
+    let input = into_items(ctx.input.take().unpack())?;
+    let mut set: IndexSet<HashableValue> = input.into_iter().map(Value::into_hashable).collect();
+    set.extend(other.into_iter().map(Value::into_hashable));
+
+    ctx.output
+        .push(from_items(set.into_iter().map(Value::from).collect()))?;
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use indexmap::IndexSet;
+use once_cell::sync::Lazy;
+
+use super::{call_closure, from_items, into_items};
+use crate::{
+    argparse::{App, Arg, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{SpannedValue, Type, Value},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("uniq-by")
+        .about("Keep the first item for every distinct key")
+        .arg(
+            Arg::new("KEY", Type::CLOSURE | Type::STRING)
+                .required(true)
+                .help("Closure returning the key of an item or the name of a column"),
+        )
+});
+
+pub fn uniq_by(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let key = matches.take_value("KEY").unwrap().value;
+    let input = into_items(ctx.input.take().unpack())?;
+
+    let mut seen = IndexSet::new();
+    let mut output = Vec::new();
+    for item in input {
+        let key = match &key {
+            Value::Closure(closure) => call_closure(ctx, closure, item.clone())?,
+            Value::String(column) => match &item {
+                Value::Map(map) => match map.get(column.as_str()) {
+                    Some(value) => value.clone(),
+                    None => return Err(ShellErrorKind::ColumnNotFound(column.to_string())),
+                },
+                _ => return Err(ShellErrorKind::NoColumns(item.to_type())),
+            },
+            _ => unreachable!(),
+        };
+        if seen.insert(key.into_hashable()) {
+            output.push(item);
+        }
+    }
+
+    ctx.output.push(from_items(output))?;
+    Ok(())
+}
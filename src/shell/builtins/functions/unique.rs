@@ -9,7 +9,7 @@ use crate::{
     shell::value::{SpannedValue, Value},
 };
 
-static APP: Lazy<App> = Lazy::new(|| App::new("unique").about("Unique values in sequence"));
+pub static APP: Lazy<App> = Lazy::new(|| App::new("unique").about("Unique values in sequence"));
 
 pub fn unique(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
     let _ = match APP.parse(args) {
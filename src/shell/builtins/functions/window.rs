@@ -0,0 +1,46 @@
+use once_cell::sync::Lazy;
+
+use super::{from_items, into_items};
+use crate::{
+    argparse::{App, Arg, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{SpannedValue, Type, Value},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("window")
+        .about("Get every overlapping window of a sequence")
+        .arg(
+            Arg::new("SIZE", Type::INT)
+                .required(true)
+                .help("Number of items in each window"),
+        )
+});
+
+pub fn window(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let size = matches.value("SIZE").unwrap().value.unwrap_int();
+    if size < 1 {
+        return Err(ShellErrorKind::Basic(
+            "Value Error",
+            format!("Window size must be at least 1, got {size}"),
+        ));
+    }
+
+    let input = into_items(ctx.input.take().unpack())?;
+    let output = input
+        .windows(size as usize)
+        .map(|window| from_items(window.to_vec()))
+        .collect::<Vec<_>>();
+
+    ctx.output.push(Value::from(output))?;
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use std::path::Path;
+
+use once_cell::sync::Lazy;
+
+use crate::{
+    argparse::{App, Arg, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::{
+        dir_history::DirHistory,
+        value::{SpannedValue, Type},
+    },
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("z")
+        .about("Jump to the most frecent directory that matches all fragments")
+        .arg(
+            Arg::new("FRAGMENTS", Type::STRING)
+                .multiple(true)
+                .help("Parts of the path in order, the last one has to be in the last component"),
+        )
+});
+
+pub fn z(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let fragments: Vec<_> = matches
+        .take_values("FRAGMENTS")
+        .unwrap_or_default()
+        .into_iter()
+        .map(|fragment| fragment.value.unwrap_string())
+        .collect();
+    let fragments: Vec<_> = fragments.iter().map(|fragment| fragment.as_str()).collect();
+
+    // works like `cd` when given nothing, `-` or a directory
+    match fragments[..] {
+        [] => return DirHistory::change_dir(ctx.shell, ctx.shell.home_dir()),
+        ["-"] => return DirHistory::back(ctx.shell),
+        [dir] if Path::new(dir).is_dir() => return DirHistory::change_dir(ctx.shell, dir),
+        _ => (),
+    }
+
+    let dirs = ctx
+        .shell
+        .dir_history
+        .frecency
+        .borrow_mut()
+        .query(&fragments);
+    match dirs.first() {
+        Some(dir) => DirHistory::change_dir(ctx.shell, dir),
+        None => Err(no_match(&fragments)),
+    }
+}
+
+pub fn no_match(fragments: &[&str]) -> ShellErrorKind {
+    ShellErrorKind::Basic(
+        "Jump Error",
+        format!("No visited directory matches `{}`", fragments.join(" ")),
+    )
+}
@@ -0,0 +1,64 @@
+use once_cell::sync::Lazy;
+
+use super::z::no_match;
+use crate::{
+    argparse::{App, Arg, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::{
+        dir_history::DirHistory,
+        helper::pick,
+        value::{SpannedValue, Type},
+    },
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("zi")
+        .about("Pick a visited directory with a fuzzy search and jump to it")
+        .arg(
+            Arg::new("FRAGMENTS", Type::STRING)
+                .multiple(true)
+                .help("Only list directories that match all fragments like `z` does"),
+        )
+});
+
+pub fn zi(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let fragments: Vec<_> = matches
+        .take_values("FRAGMENTS")
+        .unwrap_or_default()
+        .into_iter()
+        .map(|fragment| fragment.value.unwrap_string())
+        .collect();
+    let fragments: Vec<_> = fragments.iter().map(|fragment| fragment.as_str()).collect();
+
+    let dirs = ctx
+        .shell
+        .dir_history
+        .frecency
+        .borrow_mut()
+        .query(&fragments);
+    if dirs.is_empty() {
+        return Err(no_match(&fragments));
+    }
+
+    let home = ctx.shell.home_dir();
+    let items: Vec<_> = dirs
+        .iter()
+        .map(|dir| match dir.strip_prefix(&home) {
+            Ok(rest) => format!("~/{}", rest.to_string_lossy()),
+            Err(_) => dir.to_string_lossy().to_string(),
+        })
+        .collect();
+    match pick(&items).map_err(|e| ShellErrorKind::Io(None, e))? {
+        Some(index) => DirHistory::change_dir(ctx.shell, &dirs[index]),
+        None => Ok(()),
+    }
+}
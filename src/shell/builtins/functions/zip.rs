@@ -0,0 +1,40 @@
+use once_cell::sync::Lazy;
+
+use super::{from_items, into_items};
+use crate::{
+    argparse::{App, Arg, ParseResult},
+    parser::{ast::context::Context, shell_error::ShellErrorKind},
+    shell::value::{SpannedValue, Type, Value},
+};
+
+pub static APP: Lazy<App> = Lazy::new(|| {
+    App::new("zip")
+        .about("Pair up the items of the input with the items of another sequence")
+        .arg(
+            Arg::new("OTHER", Type::LIST | Type::RANGE | Type::TABLE)
+                .required(true)
+                .help("Sequence to zip with, the output is as long as the shortest one"),
+        )
+});
+
+pub fn zip(ctx: &mut Context, args: Vec<SpannedValue>) -> Result<(), ShellErrorKind> {
+    let mut matches = match APP.parse(args) {
+        Ok(ParseResult::Matches(m)) => m,
+        Ok(ParseResult::Info(info)) => {
+            ctx.output.push(info)?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let other = into_items(matches.take_value("OTHER").unwrap().value)?;
+    let input = into_items(ctx.input.take().unpack())?;
+    let output = input
+        .into_iter()
+        .zip(other)
+        .map(|(lhs, rhs)| Value::from(vec![lhs, rhs]))
+        .collect();
+
+    ctx.output.push(from_items(output))?;
+    Ok(())
+}
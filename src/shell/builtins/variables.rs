@@ -4,10 +4,15 @@ use phf::*;
 use rand::Rng;
 
 use crate::{
-    parser::{ast::context::Context, lexer::token::span::Span, shell_error::ShellErrorKind},
+    parser::{
+        ast::{context::Context, expr::closure::Closure},
+        lexer::token::span::Span,
+        shell_error::ShellErrorKind,
+    },
     shell::{
         current_dir_str,
-        value::{SpannedValue, Value},
+        frame::Frame,
+        value::{overflow::Overflow, SpannedValue, Value},
     },
 };
 
@@ -26,21 +31,27 @@ static BUILTIN_VARS: phf::Map<&'static str, Builtins> = phf_map! {
     "desktop" => Builtins(desktop, None),
     "distro" => Builtins(distro, None),
     "e" => Builtins(e, None),
+    "editor" => Builtins(get_editor, Some(set_editor)),
     "family" => Builtins(family, None),
     "home" => Builtins(home, None),
     "hostname" => Builtins(hostname, None),
     "interactive" => Builtins(interactive, None),
     "lines" => Builtins(lines, None),
+    "match" => Builtins(last_match, None),
     "null" => Builtins(null, None),
     "os" => Builtins(os, None),
+    "overflow" => Builtins(get_overflow, Some(set_overflow)),
     "path_sep" => Builtins(path_sep, None),
     "pi" => Builtins(pi, None),
     "pid" => Builtins(pid, None),
     "print_ast" => Builtins(get_print_ast, Some(set_print_ast)),
     "prompt" => Builtins(get_prompt, Some(set_prompt)),
+    "prompt_continuation" => Builtins(get_prompt_continuation, Some(set_prompt_continuation)),
     "pwd" => Builtins(pwd, None),
     "random" => Builtins(random, None),
+    "rprompt" => Builtins(get_rprompt, Some(set_rprompt)),
     "tau" => Builtins(tau, None),
+    "transient_prompt" => Builtins(get_transient_prompt, Some(set_transient_prompt)),
     "unix_epoch" => Builtins(epoch, None),
     "user" => Builtins(user, None),
     "version" => Builtins(version, None),
@@ -82,12 +93,13 @@ pub fn set_var(ctx: &mut Context, name: &str, span: Span, value: SpannedValue) -
     SetResult::Success
 }
 
-pub fn set_prompt(ctx: &mut Context, value: SpannedValue) -> Result<(), ShellErrorKind> {
+type PromptClosure = Rc<(Rc<Closure>, Frame)>;
+
+// prompts are closures, setting one to null goes back to the default
+fn prompt_closure(value: SpannedValue) -> Result<Option<PromptClosure>, ShellErrorKind> {
     match value.value {
-        Value::Closure(closure) => {
-            ctx.shell.prompt = Some(closure);
-            Ok(())
-        }
+        Value::Closure(closure) => Ok(Some(closure)),
+        Value::Null => Ok(None),
         // TODO make error nicer
         _ => Err(ShellErrorKind::Basic(
             "Type Error",
@@ -96,16 +108,51 @@ pub fn set_prompt(ctx: &mut Context, value: SpannedValue) -> Result<(), ShellErr
     }
 }
 
+fn prompt_value(prompt: &Option<PromptClosure>) -> Value {
+    prompt.clone().map(Value::Closure).unwrap_or(Value::Null)
+}
+
+pub fn set_prompt(ctx: &mut Context, value: SpannedValue) -> Result<(), ShellErrorKind> {
+    ctx.shell.prompt = prompt_closure(value)?;
+    Ok(())
+}
+
+pub fn set_rprompt(ctx: &mut Context, value: SpannedValue) -> Result<(), ShellErrorKind> {
+    ctx.shell.rprompt = prompt_closure(value)?;
+    Ok(())
+}
+
+pub fn set_transient_prompt(ctx: &mut Context, value: SpannedValue) -> Result<(), ShellErrorKind> {
+    ctx.shell.transient_prompt = prompt_closure(value)?;
+    Ok(())
+}
+
+pub fn set_prompt_continuation(
+    ctx: &mut Context,
+    value: SpannedValue,
+) -> Result<(), ShellErrorKind> {
+    ctx.shell.prompt_continuation = prompt_closure(value)?;
+    Ok(())
+}
+
 pub fn get_input_stream(ctx: &mut Context) -> Value {
     ctx.input.take().unpack()
 }
 
 pub fn get_prompt(ctx: &mut Context) -> Value {
-    ctx.shell
-        .prompt
-        .clone()
-        .map(Value::Closure)
-        .unwrap_or(Value::Null)
+    prompt_value(&ctx.shell.prompt)
+}
+
+pub fn get_rprompt(ctx: &mut Context) -> Value {
+    prompt_value(&ctx.shell.rprompt)
+}
+
+pub fn get_transient_prompt(ctx: &mut Context) -> Value {
+    prompt_value(&ctx.shell.transient_prompt)
+}
+
+pub fn get_prompt_continuation(ctx: &mut Context) -> Value {
+    prompt_value(&ctx.shell.prompt_continuation)
 }
 
 pub fn set_print_ast(ctx: &mut Context, value: SpannedValue) -> Result<(), ShellErrorKind> {
@@ -117,6 +164,42 @@ pub fn get_print_ast(ctx: &mut Context) -> Value {
     ctx.shell.print_ast.into()
 }
 
+pub fn set_overflow(ctx: &mut Context, value: SpannedValue) -> Result<(), ShellErrorKind> {
+    let mode = match &value.value {
+        Value::String(name) => Overflow::from_name(name),
+        _ => None,
+    };
+    match mode {
+        Some(mode) => {
+            ctx.shell.overflow = mode;
+            Ok(())
+        }
+        None => Err(ShellErrorKind::Basic(
+            "Type Error",
+            "Overflow must be one of \"wrap\", \"error\" or \"promote\"".into(),
+        )),
+    }
+}
+
+pub fn get_overflow(ctx: &mut Context) -> Value {
+    Value::from(ctx.shell.overflow.name())
+}
+
+// only the options in the map are changed
+pub fn set_editor(ctx: &mut Context, value: SpannedValue) -> Result<(), ShellErrorKind> {
+    match &value.value {
+        Value::Map(map) => ctx.shell.editor_options.update(map),
+        value => Err(ShellErrorKind::Basic(
+            "Type Error",
+            format!("Editor options must be a map, not a {}", value.to_type()),
+        )),
+    }
+}
+
+pub fn get_editor(ctx: &mut Context) -> Value {
+    ctx.shell.editor_options.to_value()
+}
+
 pub fn args(ctx: &mut Context) -> Value {
     Value::from(
         ctx.shell
@@ -131,6 +214,10 @@ pub fn config(ctx: &mut Context) -> Value {
     Value::from(ctx.shell.config_path().to_string_lossy().to_string())
 }
 
+pub fn last_match(ctx: &mut Context) -> Value {
+    ctx.shell.last_match.clone()
+}
+
 pub fn null(_: &mut Context) -> Value {
     Value::Null
 }
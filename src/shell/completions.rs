@@ -0,0 +1,428 @@
+use std::{iter, rc::Rc};
+
+use super::{frame::Frame, parser::ast::expr::closure::Closure, value::Value, Shell};
+
+/// A completer for an external command registered with the `complete` builtin
+#[derive(Debug)]
+pub enum ExternalCompleter {
+    /// Called with the line up to the cursor and the cursor position
+    Closure(Rc<(Rc<Closure>, Frame)>),
+    /// Imported from fish completion files
+    Fish(Vec<FishCompletion>),
+}
+
+impl ExternalCompleter {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Closure(_) => "closure",
+            Self::Fish(_) => "fish",
+        }
+    }
+}
+
+/// Candidates for the word under the cursor, `files` is false when file names should not be
+/// completed along with them
+pub struct ExternalCompletion {
+    pub candidates: Vec<(String, String)>,
+    pub files: bool,
+}
+
+/// One `complete` line of a fish completion file
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FishCompletion {
+    pub short: Option<char>,
+    pub long: Option<String>,
+    pub old: Option<String>,
+    pub arguments: Vec<(String, String)>,
+    pub description: String,
+    pub condition: Option<String>,
+    pub no_files: bool,
+}
+
+impl FishCompletion {
+    // only the conditions used by most completion files are understood, others always apply
+    fn applies(&self, args: &[String]) -> bool {
+        let Some(condition) = &self.condition else {
+            return true;
+        };
+
+        let (negated, condition) = match condition.strip_prefix("not ") {
+            Some(condition) => (true, condition.trim()),
+            None => (false, condition.trim()),
+        };
+        let mut words = condition.split_whitespace();
+        let applies = match words.next() {
+            Some("__fish_use_subcommand") => !args.iter().any(|arg| !arg.starts_with('-')),
+            Some("__fish_seen_subcommand_from") => {
+                let subcommands: Vec<&str> = words.collect();
+                args.iter().any(|arg| subcommands.contains(&arg.as_str()))
+            }
+            _ => return true,
+        };
+        applies != negated
+    }
+
+    fn switches(&self) -> impl Iterator<Item = (String, String)> + '_ {
+        let long = self.long.iter().map(|long| format!("--{long}"));
+        let short = self.short.iter().map(|short| format!("-{short}"));
+        let old = self.old.iter().map(|old| format!("-{old}"));
+        long.chain(short)
+            .chain(old)
+            .map(|switch| (switch, self.description.clone()))
+    }
+}
+
+impl Shell {
+    pub fn complete_external(
+        &mut self,
+        command: &str,
+        args: &[String],
+        word: &str,
+        line: &str,
+    ) -> Option<ExternalCompletion> {
+        let completion = match self.completers.get(command)? {
+            ExternalCompleter::Closure(closure) => {
+                let closure = closure.clone();
+                self.run_completer(&closure, line)
+            }
+            ExternalCompleter::Fish(completions) => fish_complete(completions, args, word),
+        };
+
+        let candidates: Vec<_> = completion
+            .candidates
+            .into_iter()
+            .filter(|(candidate, _)| candidate.starts_with(word))
+            .collect();
+        Some(ExternalCompletion {
+            files: completion.files || candidates.is_empty(),
+            candidates,
+        })
+    }
+
+    fn run_completer(
+        &mut self,
+        completer: &(Rc<Closure>, Frame),
+        line: &str,
+    ) -> ExternalCompletion {
+        let arguments = vec![Value::from(line), Value::Int(line.len() as i64)];
+        let candidates = match self.eval_closure(completer, arguments) {
+            Ok(output) => candidates(output.unpack()),
+            Err(()) => Vec::new(),
+        };
+        ExternalCompletion {
+            candidates,
+            files: false,
+        }
+    }
+}
+
+// a completer returns a list of strings or a table with a `value` and a `description` column
+fn candidates(value: Value) -> Vec<(String, String)> {
+    let items: Box<dyn Iterator<Item = Value>> = match value {
+        Value::List(list) => Box::new(Rc::unwrap_or_clone(list).into_iter()),
+        Value::Table(table) => Box::new(
+            table
+                .iter()
+                .map(Value::from)
+                .collect::<Vec<_>>()
+                .into_iter(),
+        ),
+        Value::Null => Box::new(iter::empty()),
+        value => Box::new(iter::once(value)),
+    };
+
+    items
+        .map(|item| match item {
+            Value::Map(map) => {
+                let value = map.get("value").map(Value::to_string).unwrap_or_default();
+                let description = map
+                    .get("description")
+                    .map(Value::to_string)
+                    .unwrap_or_default();
+                (value, description)
+            }
+            item => (item.to_string(), String::new()),
+        })
+        .filter(|(value, _)| !value.is_empty())
+        .collect()
+}
+
+fn fish_complete(
+    completions: &[FishCompletion],
+    args: &[String],
+    word: &str,
+) -> ExternalCompletion {
+    let applying = completions
+        .iter()
+        .filter(|completion| completion.applies(args));
+
+    if word.starts_with('-') {
+        return ExternalCompletion {
+            candidates: applying.flat_map(FishCompletion::switches).collect(),
+            files: false,
+        };
+    }
+
+    let mut files = true;
+    let mut candidates = Vec::new();
+    for completion in applying {
+        // arguments of an option are only offered in the position of that option
+        if completion.long.is_some() || completion.short.is_some() || completion.old.is_some() {
+            continue;
+        }
+        if completion.no_files {
+            files = false;
+        }
+        candidates.extend(completion.arguments.iter().cloned());
+    }
+    ExternalCompletion { candidates, files }
+}
+
+/// Read the `complete` lines of a fish completion file, other lines are ignored
+pub fn parse_fish(src: &str) -> Vec<(String, FishCompletion)> {
+    let src = src.replace("\\\n", " ");
+    let mut output = Vec::new();
+    for line in src.lines() {
+        let words = split_fish_words(line);
+        let Some((first, options)) = words.split_first() else {
+            continue;
+        };
+        if first != "complete" {
+            continue;
+        }
+        if let Some(completion) = parse_fish_complete(options) {
+            output.push(completion);
+        }
+    }
+    output
+}
+
+fn parse_fish_complete(words: &[String]) -> Option<(String, FishCompletion)> {
+    let mut command = None;
+    let mut completion = FishCompletion::default();
+    let mut words = words.iter();
+
+    while let Some(word) = words.next() {
+        // options are given as `--name value`, `--name=value`, `-x value` or `-xvalue`
+        let (name, inline_value) = if let Some(long) = word.strip_prefix("--") {
+            match long.split_once('=') {
+                Some((name, value)) => (long_name(name)?, Some(value.to_string())),
+                None => (long_name(long)?, None),
+            }
+        } else if let Some(short) = word.strip_prefix('-') {
+            let mut chars = short.chars();
+            let mut name = chars.next()?;
+            // flags without a value can be grouped like `-fa`
+            while !takes_value(name) {
+                apply_fish_flag(&mut completion, name);
+                match chars.next() {
+                    Some(next) => name = next,
+                    None => break,
+                }
+            }
+            if !takes_value(name) {
+                continue;
+            }
+            let rest = chars.as_str();
+            (name, (!rest.is_empty()).then(|| rest.to_string()))
+        } else {
+            continue;
+        };
+
+        if !takes_value(name) {
+            apply_fish_flag(&mut completion, name);
+            continue;
+        }
+        let value = match inline_value {
+            Some(value) => value,
+            None => words.next()?.clone(),
+        };
+        match name {
+            'c' => command = Some(value),
+            's' => completion.short = value.chars().next(),
+            'l' => completion.long = Some(value),
+            'o' => completion.old = Some(value),
+            'd' => completion.description = value,
+            'n' => completion.condition = Some(value),
+            'a' => completion.arguments = fish_arguments(&value),
+            _ => (),
+        }
+    }
+
+    Some((command?, completion))
+}
+
+fn long_name(name: &str) -> Option<char> {
+    Some(match name {
+        "command" => 'c',
+        "path" => 'p',
+        "short-option" => 's',
+        "long-option" => 'l',
+        "old-option" => 'o',
+        "description" => 'd',
+        "condition" => 'n',
+        "arguments" => 'a',
+        "wraps" => 'w',
+        "no-files" => 'f',
+        "force-files" => 'F',
+        "require-parameter" => 'r',
+        "exclusive" => 'x',
+        "keep-order" => 'k',
+        "erase" => 'e',
+        _ => return None,
+    })
+}
+
+fn takes_value(name: char) -> bool {
+    matches!(name, 'c' | 'p' | 's' | 'l' | 'o' | 'd' | 'n' | 'a' | 'w')
+}
+
+fn apply_fish_flag(completion: &mut FishCompletion, name: char) {
+    if matches!(name, 'f' | 'x') {
+        completion.no_files = true;
+    }
+}
+
+// arguments are separated by spaces and can have a tab separated description,
+// command substitutions can't be run so they are skipped
+fn fish_arguments(arguments: &str) -> Vec<(String, String)> {
+    if arguments.contains('(') {
+        return Vec::new();
+    }
+    split_fish_words(arguments)
+        .into_iter()
+        .map(|argument| match argument.split_once('\t') {
+            Some((value, description)) => (value.to_string(), description.to_string()),
+            None => (argument, String::new()),
+        })
+        .collect()
+}
+
+// splits a line into words the way fish does, without expanding variables
+fn split_fish_words(line: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = line.chars();
+
+    while let Some(char) = chars.next() {
+        match char {
+            '#' if !in_word => break,
+            ' ' | '\t' => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            '\'' => {
+                in_word = true;
+                while let Some(char) = chars.next() {
+                    match char {
+                        '\'' => break,
+                        '\\' => match chars.next() {
+                            Some(escaped @ ('\'' | '\\')) => word.push(escaped),
+                            Some(other) => {
+                                word.push('\\');
+                                word.push(other);
+                            }
+                            None => word.push('\\'),
+                        },
+                        char => word.push(char),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                while let Some(char) = chars.next() {
+                    match char {
+                        '"' => break,
+                        '\\' => match chars.next() {
+                            Some(escaped @ ('"' | '\\' | '$')) => word.push(escaped),
+                            Some(other) => {
+                                word.push('\\');
+                                word.push(other);
+                            }
+                            None => word.push('\\'),
+                        },
+                        char => word.push(char),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                match chars.next() {
+                    Some('t') => word.push('\t'),
+                    Some('n') => word.push('\n'),
+                    Some(other) => word.push(other),
+                    None => (),
+                }
+            }
+            char => {
+                in_word = true;
+                word.push(char);
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fish_parse_test() {
+        let src = r#"
+# git completions
+complete -c git -n '__fish_use_subcommand' -xa 'add\tStage\ files commit'
+complete -c git -n '__fish_seen_subcommand_from commit' -s m -l message -d "Commit \"message\""
+complete --command=git --long-option=verbose -d 'Be verbose' \
+    -s v
+complete -c git -a '(__fish_git_branches)'
+set -l foo bar
+"#;
+        let completions = parse_fish(src);
+        assert_eq!(completions.len(), 4);
+        assert!(completions.iter().all(|(command, _)| command == "git"));
+
+        let (_, add) = &completions[0];
+        assert!(add.no_files);
+        assert_eq!(
+            add.arguments,
+            vec![
+                (String::from("add"), String::from("Stage files")),
+                (String::from("commit"), String::new())
+            ]
+        );
+
+        let (_, message) = &completions[1];
+        assert_eq!(message.short, Some('m'));
+        assert_eq!(message.long.as_deref(), Some("message"));
+        assert_eq!(message.description, "Commit \"message\"");
+
+        let (_, verbose) = &completions[2];
+        assert_eq!(verbose.long.as_deref(), Some("verbose"));
+        assert_eq!(verbose.short, Some('v'));
+        assert!(completions[3].1.arguments.is_empty());
+
+        let completions: Vec<_> = completions.into_iter().map(|(_, c)| c).collect();
+        let subcommands = fish_complete(&completions, &[], "");
+        assert!(!subcommands.files);
+        assert_eq!(subcommands.candidates.len(), 2);
+
+        let commit = [String::from("commit")];
+        let switches = fish_complete(&completions, &commit, "-");
+        assert!(switches
+            .candidates
+            .iter()
+            .any(|(switch, _)| switch == "--message"));
+        let switches = fish_complete(&completions, &[], "-");
+        assert!(!switches
+            .candidates
+            .iter()
+            .any(|(switch, _)| switch == "--message"));
+    }
+}
@@ -1,35 +1,106 @@
-use std::path::{Path, PathBuf};
+use std::{
+    cell::RefCell,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
 
+use super::{hooks::HookEvent, value::Value, Shell};
 use crate::parser::shell_error::ShellErrorKind;
 
+pub mod frecency;
+use frecency::Frecency;
+
 pub struct DirHistory {
     dir_history: Vec<PathBuf>,
+    /// Directories saved by `pushd`, the top is last
+    stack: Vec<PathBuf>,
+    /// Directories visited by interactive shells, shared with the line editor to complete `cd`
+    pub frecency: Rc<RefCell<Frecency>>,
 }
 
 impl DirHistory {
-    pub fn new() -> Self {
+    pub fn new(frecency_path: PathBuf) -> Self {
         Self {
             dir_history: Vec::new(),
+            stack: Vec::new(),
+            frecency: Rc::new(RefCell::new(Frecency::new(frecency_path))),
         }
     }
 
-    pub fn change_dir(&mut self, dir: impl AsRef<Path>) -> Result<(), ShellErrorKind> {
-        let old_dir = std::env::current_dir().map_err(|err| ShellErrorKind::Io(None, err))?;
-        self.dir_history.push(old_dir);
+    pub fn stack(&self) -> &[PathBuf] {
+        &self.stack
+    }
 
-        std::env::set_current_dir(dir).map_err(|err| ShellErrorKind::Io(None, err))
+    pub fn clear_stack(&mut self) {
+        self.stack.clear();
+    }
+
+    /// Change the working directory and run the `on_cd` hooks with the old and the new directory
+    pub fn change_dir(shell: &mut Shell, dir: impl AsRef<Path>) -> Result<(), ShellErrorKind> {
+        let old_dir = std::env::current_dir().map_err(|err| ShellErrorKind::Io(None, err))?;
+        std::env::set_current_dir(dir).map_err(|err| ShellErrorKind::Io(None, err))?;
+        shell.dir_history.dir_history.push(old_dir.clone());
+        Self::changed(shell, old_dir)
     }
 
-    pub fn back(&mut self) -> Result<(), ShellErrorKind> {
-        if let Some(path) = self.dir_history.pop() {
+    /// Go back to the previous working directory, does nothing if there is none
+    pub fn back(shell: &mut Shell) -> Result<(), ShellErrorKind> {
+        if let Some(path) = shell.dir_history.dir_history.pop() {
+            let old_dir = std::env::current_dir().map_err(|err| ShellErrorKind::Io(None, err))?;
             std::env::set_current_dir(path).map_err(|err| ShellErrorKind::Io(None, err))?;
+            Self::changed(shell, old_dir)?;
         }
         Ok(())
     }
-}
 
-impl Default for DirHistory {
-    fn default() -> Self {
-        Self::new()
+    /// Save the working directory on the stack and change to `dir`
+    pub fn push(shell: &mut Shell, dir: impl AsRef<Path>) -> Result<(), ShellErrorKind> {
+        let old_dir = std::env::current_dir().map_err(|err| ShellErrorKind::Io(None, err))?;
+        Self::change_dir(shell, dir)?;
+        shell.dir_history.stack.push(old_dir);
+        Ok(())
+    }
+
+    /// Swap the working directory with the top of the stack
+    pub fn swap(shell: &mut Shell) -> Result<(), ShellErrorKind> {
+        let old_dir = std::env::current_dir().map_err(|err| ShellErrorKind::Io(None, err))?;
+        let top = Self::top(shell)?;
+        Self::change_dir(shell, &top)?;
+        shell.dir_history.stack.pop();
+        shell.dir_history.stack.push(old_dir);
+        Ok(())
+    }
+
+    /// Remove the top of the stack and change to it
+    pub fn pop(shell: &mut Shell) -> Result<(), ShellErrorKind> {
+        let top = Self::top(shell)?;
+        Self::change_dir(shell, &top)?;
+        shell.dir_history.stack.pop();
+        Ok(())
+    }
+
+    fn top(shell: &Shell) -> Result<PathBuf, ShellErrorKind> {
+        shell.dir_history.stack.last().cloned().ok_or_else(|| {
+            ShellErrorKind::Basic(
+                "Directory Error",
+                String::from("The directory stack is empty"),
+            )
+        })
+    }
+
+    fn changed(shell: &mut Shell, old_dir: PathBuf) -> Result<(), ShellErrorKind> {
+        let new_dir = std::env::current_dir().map_err(|err| ShellErrorKind::Io(None, err))?;
+        // scripts don't count as visits, a failed save does not fail the cd
+        if shell.interactive {
+            let _ = shell.dir_history.frecency.borrow_mut().visit(&new_dir);
+        }
+        shell.run_hooks(
+            HookEvent::OnCd,
+            &[
+                Value::from(old_dir.to_string_lossy().to_string()),
+                Value::from(new_dir.to_string_lossy().to_string()),
+            ],
+        );
+        Ok(())
     }
 }
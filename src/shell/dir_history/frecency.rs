@@ -0,0 +1,271 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, BufRead, BufReader, BufWriter, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use fd_lock::RwLock;
+use serde::{Deserialize, Serialize};
+
+// ranks are scaled down when their sum grows past this so old directories are forgotten
+const MAX_TOTAL_RANK: f64 = 10000.0;
+
+const HOUR: i64 = 60 * 60;
+const DAY: i64 = 24 * HOUR;
+const WEEK: i64 = 7 * DAY;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirEntry {
+    pub path: String,
+    /// Grows by one on every visit
+    pub rank: f64,
+    /// Seconds since the unix epoch of the last visit
+    pub last_visit: i64,
+}
+
+impl DirEntry {
+    /// The rank weighted by how long ago the directory was visited
+    pub fn score(&self, now: i64) -> f64 {
+        let age = now - self.last_visit;
+        if age < HOUR {
+            self.rank * 4.0
+        } else if age < DAY {
+            self.rank * 2.0
+        } else if age < WEEK {
+            self.rank / 2.0
+        } else {
+            self.rank / 4.0
+        }
+    }
+}
+
+/// Visited directories ranked by how often and how recently they were visited, saved as json
+/// lines so that every shell adds to the same database
+pub struct Frecency {
+    path: PathBuf,
+    // loaded the first time it is used
+    entries: Option<Vec<DirEntry>>,
+}
+
+impl Frecency {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            entries: None,
+        }
+    }
+
+    pub fn entries(&mut self) -> &[DirEntry] {
+        let path = &self.path;
+        self.entries.get_or_insert_with(|| {
+            File::open(path)
+                .and_then(|file| {
+                    let lock = RwLock::new(file);
+                    let guard = lock.read()?;
+                    load_from(&guard)
+                })
+                .unwrap_or_default()
+        })
+    }
+
+    /// Count a visit to `dir`, the database is reloaded so visits from other shells are kept
+    pub fn visit(&mut self, dir: &Path) -> io::Result<()> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&self.path)?;
+        let mut lock = RwLock::new(file);
+        let mut guard = lock.write()?;
+        let mut entries = load_from(&guard)?;
+        add_visit(&mut entries, &dir.to_string_lossy(), now());
+
+        guard.seek(SeekFrom::Start(0))?;
+        guard.set_len(0)?;
+        let mut writer = BufWriter::new(&*guard);
+        for entry in &entries {
+            serde_json::to_writer(&mut writer, entry)?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()?;
+        drop(writer);
+        self.entries = Some(entries);
+        Ok(())
+    }
+
+    /// Score of a directory, 0 if it has never been visited
+    pub fn score(&mut self, dir: &Path) -> f64 {
+        let now = now();
+        let dir = dir.to_string_lossy();
+        self.entries()
+            .iter()
+            .find(|entry| entry.path == dir)
+            .map_or(0.0, |entry| entry.score(now))
+    }
+
+    /// Directories that match all fragments, best first. The current directory and directories
+    /// that no longer exist are left out.
+    pub fn query(&mut self, fragments: &[&str]) -> Vec<PathBuf> {
+        let now = now();
+        let current = std::env::current_dir().ok();
+        let mut matches: Vec<_> = self
+            .entries()
+            .iter()
+            .filter(|entry| matches(&entry.path, fragments))
+            .map(|entry| (entry.score(now), PathBuf::from(&entry.path)))
+            .filter(|(_, path)| Some(path) != current.as_ref() && path.is_dir())
+            .collect();
+        matches.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+        matches.into_iter().map(|(_, path)| path).collect()
+    }
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs() as i64)
+}
+
+// lines that can not be parsed are skipped so one corrupt line does not lose every directory
+fn load_from(file: &File) -> io::Result<Vec<DirEntry>> {
+    let mut entries = Vec::new();
+    for line in BufReader::new(file).split(b'\n') {
+        if let Ok(entry) = serde_json::from_slice(&line?) {
+            entries.push(entry);
+        }
+    }
+    Ok(entries)
+}
+
+fn add_visit(entries: &mut Vec<DirEntry>, dir: &str, now: i64) {
+    match entries.iter_mut().find(|entry| entry.path == dir) {
+        Some(entry) => {
+            entry.rank += 1.0;
+            entry.last_visit = now;
+        }
+        None => entries.push(DirEntry {
+            path: dir.to_string(),
+            rank: 1.0,
+            last_visit: now,
+        }),
+    }
+
+    let total: f64 = entries.iter().map(|entry| entry.rank).sum();
+    if total > MAX_TOTAL_RANK {
+        let factor = 0.9 * MAX_TOTAL_RANK / total;
+        for entry in entries.iter_mut() {
+            entry.rank *= factor;
+        }
+        entries.retain(|entry| entry.rank >= 1.0);
+    }
+}
+
+// the fragments have to be found in order ignoring case and the last one has to be in the last
+// component, so `z foo` goes to `foo` and not to a directory inside of it. The last one is
+// searched from the end because a path like `/home/foo/foo` also has it in an earlier component.
+fn matches(path: &str, fragments: &[&str]) -> bool {
+    let path = path.to_lowercase();
+    let Some((last, fragments)) = fragments.split_last() else {
+        return true;
+    };
+    let mut rest = path.as_str();
+    for fragment in fragments {
+        let fragment = fragment.to_lowercase();
+        match rest.find(&fragment) {
+            Some(index) => rest = &rest[index + fragment.len()..],
+            None => return false,
+        }
+    }
+    let last = last.to_lowercase();
+    match rest.rfind(&last) {
+        Some(index) => !rest[index + last.len()..].contains(std::path::MAIN_SEPARATOR),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_test() {
+        assert!(matches("/home/user/projects/crust", &["crust"]));
+        assert!(matches("/home/user/projects/crust", &["PRO", "cr"]));
+        assert!(matches("/home/user/projects/crust", &[]));
+        assert!(!matches("/home/user/projects/crust", &["crust", "pro"]));
+        assert!(!matches("/home/user/projects/crust", &["projects"]));
+        assert!(matches("/home/crust/projects/crust", &["crust"]));
+        assert!(matches("/home/u/src/app/src", &["src"]));
+        assert!(matches("/home/u/src/app/src", &["src", "src"]));
+        assert!(!matches("/home/u/src/app", &["src"]));
+    }
+
+    #[test]
+    fn add_visit_test() {
+        let mut entries = Vec::new();
+        add_visit(&mut entries, "/a", 10);
+        add_visit(&mut entries, "/b", 20);
+        add_visit(&mut entries, "/a", 30);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].rank, 2.0);
+        assert_eq!(entries[0].last_visit, 30);
+
+        // recent visits count more than old ones
+        assert!(entries[1].score(20 + HOUR - 1) > entries[1].score(20 + WEEK));
+
+        entries[0].rank = MAX_TOTAL_RANK;
+        add_visit(&mut entries, "/a", 40);
+        assert!(entries.iter().map(|entry| entry.rank).sum::<f64>() <= MAX_TOTAL_RANK);
+        assert!(entries.iter().all(|entry| entry.path == "/a"));
+    }
+
+    #[test]
+    fn query_test() {
+        let root = std::env::temp_dir().join(format!("crust-frecency-{}", std::process::id()));
+        let (often, once) = (root.join("often"), root.join("once"));
+        std::fs::create_dir_all(&often).unwrap();
+        std::fs::create_dir_all(&once).unwrap();
+
+        let db = root.join("dirs.jsonl");
+        let mut frecency = Frecency::new(db.clone());
+        frecency.visit(&often).unwrap();
+        frecency.visit(&once).unwrap();
+        frecency.visit(&often).unwrap();
+        frecency.visit(&root.join("gone")).unwrap();
+
+        let mut loaded = Frecency::new(db);
+        assert_eq!(loaded.query(&["crust-frecency"]), Vec::<PathBuf>::new());
+        assert_eq!(loaded.query(&[]), vec![often.clone(), once]);
+        assert_eq!(loaded.query(&["oft"]), vec![often.clone()]);
+        assert!(loaded.score(&often) > 0.0);
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn corrupt_line_test() {
+        let root = std::env::temp_dir().join(format!("crust-frecency-bad-{}", std::process::id()));
+        let (kept, added) = (root.join("kept"), root.join("added"));
+        std::fs::create_dir_all(&kept).unwrap();
+        std::fs::create_dir_all(&added).unwrap();
+
+        let db = root.join("dirs.jsonl");
+        let entry = DirEntry {
+            path: kept.to_string_lossy().to_string(),
+            rank: 5.0,
+            last_visit: now(),
+        };
+        let mut data = b"{\"path\": \"/cut off\n\xff\n".to_vec();
+        data.extend(serde_json::to_vec(&entry).unwrap());
+        std::fs::write(&db, data).unwrap();
+
+        let mut frecency = Frecency::new(db.clone());
+        assert_eq!(frecency.query(&[]), vec![kept.clone()]);
+        frecency.visit(&added).unwrap();
+
+        let mut loaded = Frecency::new(db);
+        assert_eq!(loaded.query(&[]), vec![kept, added]);
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
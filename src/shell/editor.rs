@@ -0,0 +1,439 @@
+use std::{mem, rc::Rc, sync::Arc};
+
+use indexmap::IndexMap;
+use rustyline::{
+    config::Configurer, Anchor, At, Cmd, EditMode, Editor, EventHandler, KeyCode, KeyEvent,
+    Modifiers, Movement, Word,
+};
+
+use super::{
+    frame::Frame,
+    helper::EditorHelper,
+    history::JsonHistory,
+    parser::{
+        ast::expr::closure::Closure,
+        shell_error::{ShellError, ShellErrorKind},
+    },
+    report_error,
+    stream::{OutputStream, ValueStream},
+    value::Value,
+    Shell,
+};
+
+/// Options of the line editor that are set with `$editor`
+#[derive(Debug, Clone, Copy)]
+pub struct EditorOptions {
+    pub edit_mode: EditMode,
+    /// Lines that start with a space are not saved in the history
+    pub history_ignore_space: bool,
+    /// A line that is the same as the one before it is not saved in the history
+    pub history_ignore_dups: bool,
+}
+
+impl Default for EditorOptions {
+    fn default() -> Self {
+        Self {
+            edit_mode: EditMode::Emacs,
+            history_ignore_space: false,
+            history_ignore_dups: true,
+        }
+    }
+}
+
+impl EditorOptions {
+    pub fn to_value(self) -> Value {
+        let edit_mode = match self.edit_mode {
+            EditMode::Vi => "vi",
+            _ => "emacs",
+        };
+        let duplicates = match self.history_ignore_dups {
+            true => "ignore_consecutive",
+            false => "always",
+        };
+        Value::from(IndexMap::from([
+            (Rc::from("edit_mode"), Value::from(edit_mode)),
+            (
+                Rc::from("history_ignore_space"),
+                Value::Bool(self.history_ignore_space),
+            ),
+            (Rc::from("history_duplicates"), Value::from(duplicates)),
+        ]))
+    }
+
+    /// Change the options that are in `map`, the others keep their value
+    pub fn update(&mut self, map: &IndexMap<Rc<str>, Value>) -> Result<(), ShellErrorKind> {
+        let error = |message: String| ShellErrorKind::Basic("Editor Error", message);
+        for (key, value) in map {
+            match (&**key, value) {
+                ("edit_mode", Value::String(mode)) => {
+                    self.edit_mode = match mode.as_str() {
+                        "emacs" => EditMode::Emacs,
+                        "vi" => EditMode::Vi,
+                        _ => return Err(error(String::from("edit_mode must be emacs or vi"))),
+                    }
+                }
+                ("history_ignore_space", Value::Bool(ignore)) => {
+                    self.history_ignore_space = *ignore
+                }
+                ("history_duplicates", Value::String(duplicates)) => {
+                    self.history_ignore_dups = match duplicates.as_str() {
+                        "always" => false,
+                        "ignore_consecutive" => true,
+                        _ => {
+                            return Err(error(String::from(
+                                "history_duplicates must be always or ignore_consecutive",
+                            )))
+                        }
+                    }
+                }
+                ("edit_mode" | "history_duplicates", value) => {
+                    return Err(error(format!(
+                        "{key} must be a string but is a {}",
+                        value.to_type()
+                    )))
+                }
+                ("history_ignore_space", value) => {
+                    return Err(error(format!(
+                        "{key} must be a bool but is a {}",
+                        value.to_type()
+                    )))
+                }
+                _ => {
+                    return Err(error(format!(
+                        "Unknown option `{key}`, expected edit_mode, history_ignore_space or history_duplicates"
+                    )))
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// What a key bound with `keybind add` does
+#[derive(Debug, Clone)]
+pub enum KeyAction {
+    /// One of [ACTIONS]
+    Action(&'static str),
+    Insert(String),
+    /// Run a line, the line that is being edited is kept
+    Run(String),
+    /// Gets the line that is being edited and returns the new line, or nothing to keep it
+    Closure(Rc<(Rc<Closure>, Frame)>),
+}
+
+impl KeyAction {
+    pub fn to_value(&self) -> Value {
+        match self {
+            Self::Action(name) => Value::from(*name),
+            Self::Insert(text) => Value::from(format!("insert {text}")),
+            Self::Run(src) => Value::from(format!("run {src}")),
+            Self::Closure(closure) => Value::Closure(closure.clone()),
+        }
+    }
+}
+
+/// A key, the name it was bound with and its action
+pub type KeyBinding = (KeyEvent, String, KeyAction);
+
+/// Names of the editor actions a key can be bound to
+pub const ACTIONS: [&str; 28] = [
+    "accept-hint",
+    "accept-hint-word",
+    "accept-line",
+    "backward-char",
+    "backward-kill-line",
+    "backward-kill-word",
+    "backward-word",
+    "beginning-of-line",
+    "capitalize-word",
+    "clear-screen",
+    "complete",
+    "downcase-word",
+    "end-of-line",
+    "forward-char",
+    "forward-word",
+    "history-search-backward",
+    "history-search-forward",
+    "insert-newline",
+    "kill-line",
+    "kill-whole-line",
+    "kill-word",
+    "next-history",
+    "noop",
+    "previous-history",
+    "search-history",
+    "transpose-chars",
+    "undo",
+    "yank",
+];
+
+// actions that need more than a rustyline command are handled by the helper
+fn action_cmd(name: &str) -> Option<Cmd> {
+    let cmd = match name {
+        "accept-hint" => Cmd::CompleteHint,
+        "accept-line" => Cmd::AcceptLine,
+        "backward-char" => Cmd::Move(Movement::BackwardChar(1)),
+        "backward-kill-line" => Cmd::Kill(Movement::BeginningOfLine),
+        "backward-kill-word" => Cmd::Kill(Movement::BackwardWord(1, Word::Emacs)),
+        "backward-word" => Cmd::Move(Movement::BackwardWord(1, Word::Emacs)),
+        "beginning-of-line" => Cmd::Move(Movement::BeginningOfLine),
+        "capitalize-word" => Cmd::CapitalizeWord,
+        "clear-screen" => Cmd::ClearScreen,
+        "complete" => Cmd::Complete,
+        "downcase-word" => Cmd::DowncaseWord,
+        "end-of-line" => Cmd::Move(Movement::EndOfLine),
+        "forward-char" => Cmd::Move(Movement::ForwardChar(1)),
+        "forward-word" => Cmd::Move(Movement::ForwardWord(1, At::AfterEnd, Word::Emacs)),
+        "history-search-backward" => Cmd::HistorySearchBackward,
+        "history-search-forward" => Cmd::HistorySearchForward,
+        "insert-newline" => Cmd::Newline,
+        "kill-line" => Cmd::Kill(Movement::EndOfLine),
+        "kill-whole-line" => Cmd::Kill(Movement::WholeLine),
+        "kill-word" => Cmd::Kill(Movement::ForwardWord(1, At::AfterEnd, Word::Emacs)),
+        "next-history" => Cmd::NextHistory,
+        "noop" => Cmd::Noop,
+        "previous-history" => Cmd::PreviousHistory,
+        "transpose-chars" => Cmd::TransposeChars,
+        "undo" => Cmd::Undo(1),
+        "yank" => Cmd::Yank(1, Anchor::Before),
+        _ => return None,
+    };
+    Some(cmd)
+}
+
+/// Parse a key like `ctrl-e`, `alt-enter`, `shift-tab`, `f5` or `x`
+pub fn parse_key(name: &str) -> Option<KeyEvent> {
+    let mut mods = Modifiers::NONE;
+    let mut rest = name;
+    loop {
+        let lower = rest.to_lowercase();
+        let (modifier, len) = if lower.starts_with("ctrl-") {
+            (Modifiers::CTRL, 5)
+        } else if lower.starts_with("alt-") {
+            (Modifiers::ALT, 4)
+        } else if lower.starts_with("shift-") {
+            (Modifiers::SHIFT, 6)
+        } else {
+            break;
+        };
+        mods |= modifier;
+        rest = &rest[len..];
+    }
+
+    let mut chars = rest.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(c), None) => KeyCode::Char(c),
+        _ => match rest.to_lowercase().as_str() {
+            "backspace" => KeyCode::Backspace,
+            "delete" => KeyCode::Delete,
+            "down" => KeyCode::Down,
+            "end" => KeyCode::End,
+            "enter" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "home" => KeyCode::Home,
+            "insert" => KeyCode::Insert,
+            "left" => KeyCode::Left,
+            "pagedown" => KeyCode::PageDown,
+            "pageup" => KeyCode::PageUp,
+            "right" => KeyCode::Right,
+            "space" => KeyCode::Char(' '),
+            "tab" => KeyCode::Tab,
+            "up" => KeyCode::Up,
+            f => match f.strip_prefix('f').and_then(|n| n.parse().ok()) {
+                Some(n @ 1..=24) => KeyCode::F(n),
+                _ => return None,
+            },
+        },
+    };
+    Some(KeyEvent::normalize(KeyEvent(code, mods)))
+}
+
+impl Shell {
+    /// Bind a key, replacing what it was bound to before
+    pub fn bind_key(&mut self, key: KeyEvent, name: String, action: KeyAction) {
+        self.key_bindings.retain(|(bound, ..)| *bound != key);
+        self.key_bindings.push((key, name, action));
+    }
+
+    /// Remove the binding of a key, returns false if it was not bound
+    pub fn unbind_key(&mut self, key: KeyEvent) -> bool {
+        let len = self.key_bindings.len();
+        self.key_bindings.retain(|(bound, ..)| *bound != key);
+        len != self.key_bindings.len()
+    }
+
+    pub fn key_bindings(&self) -> &[KeyBinding] {
+        &self.key_bindings
+    }
+
+    /// Apply the options and key bindings to the editor before a line is read. Keys that are no
+    /// longer bound go back to what they do by default.
+    pub(super) fn configure_editor(&mut self, editor: &mut Editor<EditorHelper, JsonHistory>) {
+        let options = self.editor_options;
+        editor.set_edit_mode(options.edit_mode);
+        editor.set_history_ignore_space(options.history_ignore_space);
+        let _ = editor.set_history_ignore_dups(options.history_ignore_dups);
+
+        for key in mem::take(&mut self.bound_keys) {
+            editor.unbind_sequence(key);
+        }
+        let helper = editor.helper().unwrap();
+        let abbreviations = Arc::new(self.editor_abbreviations());
+        let mut handlers = vec![
+            (
+                KeyEvent(KeyCode::Enter, Modifiers::NONE),
+                EventHandler::Conditional(Box::new(
+                    helper.abbr_handler(abbreviations.clone(), true),
+                )),
+            ),
+            (
+                KeyEvent::from(' '),
+                EventHandler::Conditional(Box::new(helper.abbr_handler(abbreviations, false))),
+            ),
+            (
+                KeyEvent::ctrl('R'),
+                EventHandler::Conditional(Box::new(helper.search_handler())),
+            ),
+        ];
+        for (key, _, action) in &self.key_bindings {
+            let handler = match action {
+                KeyAction::Action("accept-hint-word") => {
+                    EventHandler::Conditional(Box::new(helper.hint_word_handler()))
+                }
+                KeyAction::Action("search-history") => {
+                    EventHandler::Conditional(Box::new(helper.search_handler()))
+                }
+                // safe because only names from `ACTIONS` are bound
+                KeyAction::Action(name) => EventHandler::Simple(action_cmd(name).unwrap()),
+                KeyAction::Insert(text) => EventHandler::Simple(Cmd::Insert(1, text.clone())),
+                KeyAction::Run(_) | KeyAction::Closure(_) => {
+                    EventHandler::Conditional(Box::new(helper.binding_handler(*key)))
+                }
+            };
+            handlers.push((*key, handler));
+            self.bound_keys.push(*key);
+        }
+        for (key, handler) in handlers {
+            editor.bind_sequence(key, handler);
+        }
+    }
+
+    /// Run a key bound to a line or a closure after it left the editor, returns the line that is
+    /// edited next split at the cursor
+    pub(super) fn run_key_binding(
+        &mut self,
+        key: KeyEvent,
+        line: String,
+        pos: usize,
+    ) -> (String, String) {
+        let unchanged = |line: String| {
+            let right = line[pos..].to_string();
+            let mut left = line;
+            left.truncate(pos);
+            (left, right)
+        };
+        let Some((.., action)) = self.key_bindings.iter().find(|(bound, ..)| *bound == key) else {
+            return unchanged(line);
+        };
+
+        match action.clone() {
+            KeyAction::Run(src) => {
+                let mut output = OutputStream::new_output();
+                self.run_src(
+                    String::from("keybind"),
+                    src,
+                    &mut output,
+                    ValueStream::new(),
+                );
+                output.end();
+                unchanged(line)
+            }
+            KeyAction::Closure(closure) => {
+                let args = match closure.0.parameters.len() {
+                    0 => Vec::new(),
+                    _ => vec![Value::from(line.clone())],
+                };
+                let Ok(output) = self.eval_closure(&closure, args) else {
+                    return unchanged(line);
+                };
+                match output.unpack() {
+                    Value::Null => unchanged(line),
+                    Value::String(new_line) => (new_line.to_string(), String::new()),
+                    value => {
+                        let message = format!(
+                            "Key binding closure must return a string or nothing, got a {}",
+                            value.to_type()
+                        );
+                        report_error(ShellError::new(
+                            ShellErrorKind::Basic("Keybind Error", message),
+                            closure.0.src.clone(),
+                        ));
+                        unchanged(line)
+                    }
+                }
+            }
+            KeyAction::Action(_) | KeyAction::Insert(_) => unchanged(line),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_key_test() {
+        assert_eq!(parse_key("ctrl-e"), Some(KeyEvent::ctrl('E')));
+        assert_eq!(
+            parse_key("Ctrl-Alt-x"),
+            Some(KeyEvent(KeyCode::Char('X'), Modifiers::CTRL_ALT))
+        );
+        assert_eq!(
+            parse_key("alt-enter"),
+            Some(KeyEvent(KeyCode::Enter, Modifiers::ALT))
+        );
+        assert_eq!(
+            parse_key("shift-tab"),
+            Some(KeyEvent(KeyCode::BackTab, Modifiers::NONE))
+        );
+        assert_eq!(
+            parse_key("f5"),
+            Some(KeyEvent(KeyCode::F(5), Modifiers::NONE))
+        );
+        assert_eq!(parse_key("-"), Some(KeyEvent::from('-')));
+        assert_eq!(parse_key("alt--"), Some(KeyEvent::alt('-')));
+        assert_eq!(parse_key("ctrl-"), None);
+        assert_eq!(parse_key("hyper-x"), None);
+    }
+
+    #[test]
+    fn actions_test() {
+        for name in ACTIONS {
+            let special = matches!(name, "accept-hint-word" | "search-history");
+            assert_eq!(action_cmd(name).is_some(), !special, "{name}");
+        }
+    }
+
+    #[test]
+    fn editor_options_test() {
+        let mut options = EditorOptions::default();
+        let Value::Map(map) = options.to_value() else {
+            unreachable!()
+        };
+        assert_eq!(map["edit_mode"], Value::from("emacs"));
+
+        let update = IndexMap::from([
+            (Rc::from("edit_mode"), Value::from("vi")),
+            (Rc::from("history_duplicates"), Value::from("always")),
+        ]);
+        options.update(&update).unwrap();
+        assert_eq!(options.edit_mode, EditMode::Vi);
+        assert!(!options.history_ignore_dups);
+        assert!(!options.history_ignore_space);
+
+        let unknown = IndexMap::from([(Rc::from("bell"), Value::Bool(true))]);
+        assert!(options.update(&unknown).is_err());
+        let wrong_type = IndexMap::from([(Rc::from("edit_mode"), Value::Int(1))]);
+        assert!(options.update(&wrong_type).is_err());
+    }
+}
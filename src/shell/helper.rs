@@ -1,28 +1,107 @@
-use std::{borrow::Cow, fmt::Write, sync::Arc};
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fmt::Write,
+    io,
+    path::PathBuf,
+    ptr::NonNull,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
 
-use crossterm::style::Stylize;
+use crossterm::{
+    cursor::{MoveToColumn, MoveToPreviousLine, RestorePosition, SavePosition},
+    style::Stylize,
+    terminal::{self, Clear, ClearType},
+    Command,
+};
 use miette::NamedSource;
 use rustyline::{
     completion::{Completer, Pair},
     highlight,
     hint::Hinter,
     history::History,
-    validate::Validator,
-    Changeset, Helper,
+    validate::{ValidationContext, ValidationResult, Validator},
+    Changeset, Cmd, ConditionalEventHandler, Event, EventContext, Helper, InputMode, KeyEvent,
+    RepeatCount,
 };
+use unicode_width::UnicodeWidthStr;
 
 mod completer;
-use completer::FilenameCompleter;
+use completer::{quote_path, FilenameCompleter};
+
+mod cursor;
+use cursor::{cursor, Position};
 
 mod highlighter;
 
+mod search;
+use search::SearchHandler;
+pub use search::{pick, search_history};
+
 use self::highlighter::{ColorType, HighlightVisitor};
-use super::history::JsonHistory;
-use crate::parser::{ast::Ast, Parser};
+use super::{
+    abbreviations::EditorAbbreviations,
+    builtins::functions::get_builtin_app,
+    completions::ExternalCompletion,
+    current_dir_path,
+    dir_history::frecency::Frecency,
+    frame::Frame,
+    history::JsonHistory,
+    render_error,
+    value::{
+        cell_path::{get_path, parse_path},
+        Value,
+    },
+    Shell,
+};
+use crate::{
+    argparse::App,
+    parser::{
+        ast::Ast,
+        lexer::token::span::Span,
+        syntax_error::{SyntaxError, SyntaxErrorKind},
+        Parser,
+    },
+};
+
+/// The prompt of every line that continues an incomplete line when `$prompt_continuation` is
+/// not set
+const CONTINUATION_PROMPT: &str = "::: ";
+const INDENT_WIDTH: usize = 4;
+// rustyline expands tabs to this width
+const TAB_STOP: usize = 8;
 
 pub struct EditorHelper {
     filename_completer: FilenameCompleter,
     pub prompt: String,
+    /// Drawn at the right edge of the first line, empty when there is none
+    pub rprompt: String,
+    continuation_prompt: String,
+    /// The entered lines of an incomplete input, the line that is edited continues them
+    pending: String,
+    // terminal rows the pending lines take up
+    pending_rows: usize,
+    // columns left between the end of the line and the right prompt after the last highlight
+    rprompt_space: Cell<Option<usize>>,
+    // the hint that is shown, it is part of the line on screen when a search is opened
+    hint: RefCell<String>,
+    /// The line that was being edited when the history search was opened
+    search_request: Arc<Mutex<Option<String>>>,
+    /// A key bound to a line or closure, the line and the cursor when it was pressed
+    binding_request: Arc<Mutex<Option<(KeyEvent, String, usize)>>>,
+    /// An abbreviation that was ended by space or enter
+    abbr_request: Arc<Mutex<Option<AbbrRequest>>>,
+    /// The top level frame of the shell, used to complete variables and functions
+    pub frame: Frame,
+    pub aliases: HashMap<String, String>,
+    /// Visited directories that `cd` completes
+    pub frecency: Option<Rc<RefCell<Frecency>>>,
+    pub home_dir: PathBuf,
+    /// Set while the shell waits for a line so that completers registered by scripts can run,
+    /// only `with_shell` may use it
+    pub shell: Option<NonNull<Shell>>,
 }
 
 impl EditorHelper {
@@ -30,10 +109,323 @@ impl EditorHelper {
         Self {
             filename_completer: FilenameCompleter::new(),
             prompt: String::new(),
+            rprompt: String::new(),
+            continuation_prompt: CONTINUATION_PROMPT.dark_grey().to_string(),
+            pending: String::new(),
+            pending_rows: 0,
+            rprompt_space: Cell::new(None),
+            hint: RefCell::new(String::new()),
+            search_request: Arc::new(Mutex::new(None)),
+            binding_request: Arc::new(Mutex::new(None)),
+            abbr_request: Arc::new(Mutex::new(None)),
+            frame: Frame::default(),
+            aliases: HashMap::new(),
+            frecency: None,
+            home_dir: PathBuf::new(),
+            shell: None,
+        }
+    }
+
+    pub fn search_handler(&self) -> SearchHandler {
+        SearchHandler(self.search_request.clone())
+    }
+
+    pub fn binding_handler(&self, key: KeyEvent) -> BindingHandler {
+        BindingHandler(key, self.binding_request.clone())
+    }
+
+    pub fn hint_word_handler(&self) -> HintWordHandler {
+        HintWordHandler
+    }
+
+    /// Expands abbreviations on space, or on enter before the line is entered
+    pub fn abbr_handler(
+        &self,
+        abbreviations: Arc<EditorAbbreviations>,
+        accept: bool,
+    ) -> AbbrHandler {
+        AbbrHandler {
+            abbreviations,
+            request: self.abbr_request.clone(),
+            accept,
+        }
+    }
+
+    /// The key that left the editor, the line and cursor and the hint that was on screen
+    pub fn take_binding_request(&self) -> Option<(KeyEvent, String, usize, String)> {
+        let (key, line, pos) = self.binding_request.lock().unwrap().take()?;
+        Some((key, line, pos, self.hint.borrow().clone()))
+    }
+
+    /// The abbreviation that left the editor and the hint that was on screen
+    pub fn take_abbr_request(&self) -> Option<(AbbrRequest, String)> {
+        let request = self.abbr_request.lock().unwrap().take()?;
+        Some((request, self.hint.borrow().clone()))
+    }
+
+    /// The line and hint that were on screen when the history search was opened
+    pub fn take_search_request(&self) -> Option<(String, String)> {
+        let line = self.search_request.lock().unwrap().take()?;
+        Some((line, self.hint.borrow().clone()))
+    }
+
+    /// Use a rendered `$prompt_continuation`, or the default when it is `None`
+    pub fn set_continuation_prompt(&mut self, prompt: Option<String>) {
+        self.continuation_prompt =
+            prompt.unwrap_or_else(|| CONTINUATION_PROMPT.dark_grey().to_string());
+    }
+
+    /// The prompt of the line that is edited, the continuation prompt when it continues an
+    /// incomplete input
+    pub fn current_prompt(&self) -> &str {
+        match self.is_continued() {
+            true => &self.continuation_prompt,
+            false => &self.prompt,
+        }
+    }
+
+    /// If the line that is edited continues an incomplete input
+    pub fn is_continued(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// The whole input when `line` is entered
+    pub fn input(&self, line: &str) -> String {
+        self.pending.clone() + line
+    }
+
+    /// Keep an entered line of an incomplete input so that the next line continues it, returns
+    /// the indentation of the next line
+    pub fn continue_input(&mut self, line: &str) -> String {
+        if let Ok((columns, _)) = terminal::size() {
+            self.pending_rows += self.rows(line, columns as usize);
+        }
+        self.pending.push_str(line);
+        self.pending.push('\n');
+        " ".repeat(cursor(&self.pending).depth * INDENT_WIDTH)
+    }
+
+    /// Forget the pending lines after the input was entered or cancelled
+    pub fn reset_input(&mut self) {
+        self.pending.clear();
+        self.pending_rows = 0;
+    }
+
+    /// Highlight an entered input, lines after the first start with the continuation prompt
+    pub fn highlight_line(&self, line: &str) -> String {
+        Highlighter::new(line, 0, Some(&self.continuation_prompt)).highlight()
+    }
+
+    /// Highlight the line that is edited, it is parsed after the pending lines
+    pub fn highlight_edited(&self, line: &str) -> String {
+        let input = self.input(line);
+        Highlighter::new(&input, self.pending.len(), None).highlight()
+    }
+
+    /// Number of terminal rows the current prompt and `line` take up, this matches how
+    /// rustyline lays them out
+    pub fn rows(&self, line: &str, columns: usize) -> usize {
+        let prompt = strip_ansi_escapes::strip_str(self.current_prompt());
+        let (mut row, mut col) = (0, 0);
+        for c in prompt.chars().chain(line.chars()) {
+            if c == '\n' {
+                row += 1;
+                col = 0;
+                continue;
+            }
+            let width = match c {
+                '\t' => TAB_STOP - col % TAB_STOP,
+                c => c.to_string().width(),
+            };
+            col += width;
+            if col > columns {
+                row += 1;
+                col = width;
+            }
+        }
+        if col == columns {
+            row += 1;
+        }
+        row + 1
+    }
+
+    /// Clear the prompt and `line` after they were entered, the cursor ends up where the prompt
+    /// started
+    pub fn clear_prompt(&self, line: &str, columns: usize) {
+        clear_rows(self.rows(line, columns));
+    }
+
+    /// Clear the prompt and `line` after they were entered together with the pending lines
+    pub fn clear_input(&self, line: &str, columns: usize) {
+        clear_rows(self.pending_rows + self.rows(line, columns));
+    }
+
+    // the right prompt is drawn after the line and the cursor is moved back, it is left out when
+    // the line would run into it
+    fn write_rprompt(&self, line: &str, columns: usize, output: &mut String) {
+        self.rprompt_space.set(None);
+        if self.rprompt.is_empty() || line.contains('\n') || self.is_continued() {
+            return;
         }
+
+        let prompt = strip_ansi_escapes::strip_str(&self.prompt);
+        let prompt_width = prompt.rsplit('\n').next().unwrap_or_default().width();
+        let rprompt_width = strip_ansi_escapes::strip_str(&self.rprompt).width();
+        let used = prompt_width + line.width() + rprompt_width + 1;
+        let Some(space) = columns.checked_sub(used) else {
+            return;
+        };
+
+        let _ = SavePosition.write_ansi(output);
+        let _ = MoveToColumn((columns - rprompt_width) as u16).write_ansi(output);
+        output.push_str(&self.rprompt);
+        let _ = RestorePosition.write_ansi(output);
+        self.rprompt_space.set(Some(space + 1));
+    }
+
+    fn complete_variable(&self, start: usize, word: &str) -> (usize, Vec<Pair>) {
+        let Some((name, path)) = word.split_once('.') else {
+            let mut names = self.frame.all_variable_names();
+            names.sort();
+            names.dedup();
+            return (start, prefixed(names.iter().map(|name| &**name), word));
+        };
+
+        // everything before the last dot is the path to the map or table whose keys are completed
+        let (path, partial) = path.rsplit_once('.').unwrap_or(("", path));
+        let start = start + word.len() - partial.len();
+        let Some(value) = self
+            .frame
+            .clone()
+            .into_iter()
+            .find_map(|frame| frame.get_var(name))
+        else {
+            return (start, Vec::new());
+        };
+
+        let keys = match get_path(&value, &parse_path(path), Span::new(0, 0)) {
+            Ok(Value::Map(map)) => map.keys().cloned().collect(),
+            Ok(Value::Table(table)) => table.headers().to_vec(),
+            _ => Vec::new(),
+        };
+        (start, prefixed(keys.iter().map(|key| &**key), partial))
+    }
+
+    // aliases are expanded so that `alias r = math round` completes the flags of `math round`
+    fn expand_alias(&self, command: String, args: Vec<String>) -> (String, Vec<String>) {
+        let Some(alias) = self.aliases.get(&command) else {
+            return (command, args);
+        };
+        let mut words = alias.split_whitespace().map(String::from);
+        let command = words.next().unwrap_or(command);
+        (command, words.chain(args).collect())
+    }
+
+    fn command_app(command: &str, args: &[String]) -> Option<&'static App> {
+        let app = get_builtin_app(command)?;
+        match args.first().and_then(|arg| app.sub_command(arg)) {
+            Some(sub_command) => Some(sub_command),
+            None => Some(app),
+        }
+    }
+
+    // directories in the working directory are ordered by frecency, visited directories elsewhere
+    // that match the word come after them
+    fn complete_directory(
+        &self,
+        line: &str,
+        pos: usize,
+        word: &str,
+        word_start: usize,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let (start, candidates) = self.filename_completer.complete_path(line, pos, None)?;
+        let Some(frecency) = &self.frecency else {
+            return Ok((start, candidates));
+        };
+        let mut frecency = frecency.borrow_mut();
+        let home = &self.home_dir;
+        let current = current_dir_path();
+
+        let mut candidates: Vec<_> = candidates
+            .into_iter()
+            .map(|candidate| {
+                let path = candidate
+                    .replacement
+                    .trim_matches('\'')
+                    .trim_end_matches('/');
+                let path = match path.strip_prefix("~/") {
+                    Some(rest) => home.join(rest),
+                    None => current.join(path),
+                };
+                (frecency.score(&path), path, candidate)
+            })
+            .collect();
+        candidates.sort_by(|(a, ..), (b, ..)| b.total_cmp(a));
+
+        let mut frecent = Vec::new();
+        if start == word_start && !word.is_empty() && !word.contains('/') {
+            for dir in frecency.query(&[word]) {
+                if candidates.iter().any(|(_, path, _)| *path == dir) {
+                    continue;
+                }
+                let display = match dir.strip_prefix(home) {
+                    Ok(rest) => format!("~/{}", rest.to_string_lossy()),
+                    Err(_) => dir.to_string_lossy().to_string(),
+                };
+                frecent.push(Pair {
+                    replacement: quote_path(format!("{display}/")),
+                    display,
+                });
+            }
+        }
+
+        let candidates = candidates.into_iter().map(|(.., candidate)| candidate);
+        Ok((start, candidates.chain(frecent).collect()))
+    }
+
+    fn complete_external(
+        &self,
+        command: &str,
+        args: &[String],
+        word: &str,
+        line: &str,
+    ) -> Option<ExternalCompletion> {
+        self.with_shell(|shell| shell.complete_external(command, args, word, line))?
+    }
+
+    // completer closures are evaluated by the shell, which is waiting for the editor
+    fn with_shell<T>(&self, f: impl FnOnce(&mut Shell) -> T) -> Option<T> {
+        let shell = self.shell?;
+        // safe because the shell is only set while it waits for the editor and the editor has
+        // been taken out of it, so nothing else has a reference to the shell until the line is read
+        Some(f(unsafe { &mut *shell.as_ptr() }))
     }
 }
 
+fn prefixed<'a>(candidates: impl Iterator<Item = &'a str>, prefix: &str) -> Vec<Pair> {
+    candidates
+        .filter(|candidate| candidate.starts_with(prefix))
+        .map(|candidate| Pair {
+            display: candidate.to_string(),
+            replacement: candidate.to_string(),
+        })
+        .collect()
+}
+
+// the help text is shown next to every flag and sub command
+fn described<'a>(candidates: impl Iterator<Item = (&'a str, &'a str)>, prefix: &str) -> Vec<Pair> {
+    candidates
+        .filter(|(candidate, _)| candidate.starts_with(prefix))
+        .map(|(candidate, help)| Pair {
+            display: match help {
+                "" => candidate.to_string(),
+                help => format!("{candidate}  {help}"),
+            },
+            replacement: candidate.to_string(),
+        })
+        .collect()
+}
+
 impl Completer for EditorHelper {
     type Candidate = Pair;
 
@@ -43,7 +435,66 @@ impl Completer for EditorHelper {
         pos: usize,
         _: &rustyline::Context<'_>,
     ) -> rustyline::Result<(usize, Vec<Self::Candidate>)> {
-        self.filename_completer.complete_path(line, pos)
+        let cursor = cursor(&line[..pos]);
+        let word = &line[cursor.start..pos];
+        if let Some(variable) = word.strip_prefix('$') {
+            return Ok(self.complete_variable(cursor.start + 1, variable));
+        }
+
+        match cursor.position {
+            Position::Command => {
+                let mut commands = self.frame.all_function_names();
+                commands.extend(self.aliases.keys().map(|alias| alias.as_str().into()));
+                self.filename_completer
+                    .complete_path(line, pos, Some(&commands))
+            }
+            Position::Argument { command, args } => {
+                let (command, args) = self.expand_alias(command, args);
+                let Some(app) = Self::command_app(&command, &args) else {
+                    let completion = self.complete_external(&command, &args, word, &line[..pos]);
+                    let completion = match completion {
+                        Some(completion) if !completion.candidates.is_empty() => completion,
+                        _ => return self.filename_completer.complete_path(line, pos, None),
+                    };
+
+                    let candidates = completion.candidates.iter();
+                    let mut candidates = described(
+                        candidates.map(|(value, help)| (value.as_str(), help.as_str())),
+                        word,
+                    );
+                    if completion.files {
+                        let (start, mut files) =
+                            self.filename_completer.complete_path(line, pos, None)?;
+                        if start == cursor.start {
+                            candidates.append(&mut files);
+                        }
+                    }
+                    return Ok((cursor.start, candidates));
+                };
+
+                if word.starts_with('-') {
+                    let switches = app.switches();
+                    let switches = switches
+                        .iter()
+                        .map(|(switch, help)| (switch.as_str(), *help));
+                    return Ok((cursor.start, described(switches, word)));
+                }
+
+                if matches!(command.as_str(), "cd" | "pushd") {
+                    return self.complete_directory(line, pos, word, cursor.start);
+                }
+
+                let (start, mut candidates) =
+                    self.filename_completer.complete_path(line, pos, None)?;
+                if args.is_empty() && start == cursor.start {
+                    let mut sub_commands = described(app.sub_commands(), word);
+                    sub_commands.append(&mut candidates);
+                    candidates = sub_commands;
+                }
+                Ok((start, candidates))
+            }
+            Position::Value => self.filename_completer.complete_path(line, pos, None),
+        }
     }
 
     fn update(
@@ -60,20 +511,29 @@ impl Completer for EditorHelper {
 
 impl highlight::Highlighter for EditorHelper {
     fn highlight_hint<'h>(&self, hint: &'h str) -> Cow<'h, str> {
-        Cow::Owned(hint.dim().to_string())
+        *self.hint.borrow_mut() = hint.to_string();
+        let mut highlighted = hint.dim().to_string();
+        // a hint that reaches the right prompt hides it
+        if self
+            .rprompt_space
+            .get()
+            .is_some_and(|space| hint.width() >= space)
+        {
+            let _ = Clear(ClearType::UntilNewLine).write_ansi(&mut highlighted);
+        }
+        Cow::Owned(highlighted)
     }
 
     fn highlight_prompt<'b, 's: 'b, 'p: 'b>(&'s self, _: &'p str, _: bool) -> Cow<'b, str> {
-        Cow::Borrowed(&self.prompt)
+        Cow::Borrowed(self.current_prompt())
     }
 
     fn highlight<'l>(&self, line: &'l str, _: usize) -> Cow<'l, str> {
-        if line.is_empty() {
-            Cow::Borrowed(line)
-        } else {
-            let highlighter = Highlighter::new(line);
-            Cow::Owned(highlighter.highlight())
-        }
+        self.hint.borrow_mut().clear();
+        let mut highlighted = self.highlight_edited(line);
+        let columns = terminal::size().map_or(0, |(columns, _)| columns as usize);
+        self.write_rprompt(line, columns, &mut highlighted);
+        Cow::Owned(highlighted)
     }
 
     fn highlight_char(&self, _: &str, _: usize, _: bool) -> bool {
@@ -85,11 +545,14 @@ pub struct Highlighter<'a> {
     ast: Ast,
     index: usize,
     line: &'a str,
+    // the line is only written from here on
+    start: usize,
     output: String,
+    continuation_prompt: Option<&'a str>,
 }
 
 impl<'a> Highlighter<'a> {
-    fn new(line: &'a str) -> Self {
+    fn new(line: &'a str, start: usize, continuation_prompt: Option<&'a str>) -> Self {
         let ast = Parser::new(String::new(), line.to_string())
             .parse()
             .0
@@ -103,7 +566,9 @@ impl<'a> Highlighter<'a> {
             ast,
             index: 0,
             line,
+            start,
             output: String::new(),
+            continuation_prompt,
         }
     }
 
@@ -111,30 +576,45 @@ impl<'a> Highlighter<'a> {
         let mut visitor = HighlightVisitor::default();
         visitor.visit_ast(&self.ast);
         for span in visitor.spans {
-            let _ = write!(
-                self.output,
-                "{}",
-                &self.line[self.index..span.span.start()].with(ColorType::Base.to_color()),
-            );
-            let _ = write!(
-                self.output,
-                "{}",
-                &self.line[span.span.start()..span.span.end()].with(span.inner.to_color())
-            );
-            self.index = span.span.end();
-        }
-        let end = &self.line[self.index..];
-        let _ = write!(&mut self.output, "{}", end.with(ColorType::Base.to_color()));
+            self.write_segment(span.span.start(), ColorType::Base);
+            self.write_segment(span.span.end(), span.inner);
+        }
+        self.write_segment(self.line.len(), ColorType::Base);
 
         self.output
     }
+
+    // writes the line up to `end`, lines after a newline start with the continuation prompt
+    // when there is one
+    fn write_segment(&mut self, end: usize, color: ColorType) {
+        while self.index < end {
+            let next = match self.line[self.index..end].find('\n') {
+                Some(newline) => self.index + newline + 1,
+                None => end,
+            };
+            // the start is always at the beginning of a line
+            if self.index >= self.start {
+                let _ = write!(
+                    self.output,
+                    "{}",
+                    self.line[self.index..next].with(color.to_color())
+                );
+                if let (Some(prompt), b'\n') =
+                    (self.continuation_prompt, self.line.as_bytes()[next - 1])
+                {
+                    self.output.push_str(prompt);
+                }
+            }
+            self.index = next;
+        }
+    }
 }
 
 impl Hinter for EditorHelper {
     type Hint = String;
 
     fn hint(&self, line: &str, _pos: usize, ctx: &rustyline::Context<'_>) -> Option<Self::Hint> {
-        if ctx.history().is_empty() {
+        if ctx.history().is_empty() || self.is_continued() {
             return None;
         }
 
@@ -150,19 +630,273 @@ impl Hinter for EditorHelper {
 }
 
 impl Validator for EditorHelper {
-    /*fn validate(
-        &self,
-        ctx: &mut rustyline::validate::ValidationContext,
-    ) -> rustyline::Result<rustyline::validate::ValidationResult> {
-        let mut parser = Parser::new(ctx.input().to_string());
-        match parser.parse() {
-            Ok(_) => Ok(ValidationResult::Valid(None)),
-            Err(error) => match error.error {
-                SyntaxErrorKind::ExpectedToken => Ok(ValidationResult::Incomplete),
-                _ => Ok(ValidationResult::Invalid(None)),
-            },
+    fn validate(&self, ctx: &mut ValidationContext) -> rustyline::Result<ValidationResult> {
+        let (_, errors) = Parser::new(String::from("shell"), self.input(ctx.input())).parse();
+        // the shell continues the input on the next line
+        if is_incomplete(&errors) {
+            return Ok(ValidationResult::Valid(None));
         }
-    }*/
+
+        // the line is kept in the editor so the error can be fixed
+        match errors.into_iter().next() {
+            Some(error) => Ok(ValidationResult::Invalid(Some(format!(
+                "\n{}",
+                render_error(error).trim_end()
+            )))),
+            None => Ok(ValidationResult::Valid(None)),
+        }
+    }
+}
+
+// moves the cursor up by `rows` and clears everything below it
+fn clear_rows(rows: usize) {
+    let _ = crossterm::execute!(
+        io::stdout(),
+        MoveToPreviousLine(rows as u16),
+        Clear(ClearType::FromCursorDown),
+    );
+}
+
+// the parser only runs out of tokens when something like a brace, a quote or a pipe is unfinished
+fn is_incomplete(errors: &[SyntaxError]) -> bool {
+    errors
+        .iter()
+        .any(|error| matches!(error.error, SyntaxErrorKind::ExpectedToken))
+}
+
+/// If enter continues `line` on a new line instead of entering it
+pub fn is_incomplete_line(line: &str) -> bool {
+    let (_, errors) = Parser::new(String::new(), line.to_string()).parse();
+    is_incomplete(&errors)
+}
+
+/// Ends the line that is being edited so that the shell can run what a key is bound to
+pub struct BindingHandler(KeyEvent, Arc<Mutex<Option<(KeyEvent, String, usize)>>>);
+
+impl ConditionalEventHandler for BindingHandler {
+    fn handle(&self, _: &Event, _: RepeatCount, _: bool, ctx: &EventContext) -> Option<Cmd> {
+        *self.1.lock().unwrap() = Some((self.0, ctx.line().to_string(), ctx.pos()));
+        Some(Cmd::Interrupt)
+    }
+}
+
+/// An abbreviation in `line` from `start` to the cursor at `pos`, `accept` is set when it was
+/// ended by enter
+pub struct AbbrRequest {
+    pub line: String,
+    pub start: usize,
+    pub pos: usize,
+    pub accept: bool,
+}
+
+/// Leaves the editor when space or enter is pressed after an abbreviation so that the shell can
+/// expand it
+pub struct AbbrHandler {
+    abbreviations: Arc<EditorAbbreviations>,
+    request: Arc<Mutex<Option<AbbrRequest>>>,
+    accept: bool,
+}
+
+impl ConditionalEventHandler for AbbrHandler {
+    fn handle(&self, _: &Event, _: RepeatCount, _: bool, ctx: &EventContext) -> Option<Cmd> {
+        let start = match ctx.input_mode() {
+            InputMode::Command => None,
+            _ => abbreviation_at(&self.abbreviations, ctx.line(), ctx.pos()),
+        }?;
+
+        *self.request.lock().unwrap() = Some(AbbrRequest {
+            line: ctx.line().to_string(),
+            start,
+            pos: ctx.pos(),
+            accept: self.accept,
+        });
+        Some(Cmd::Interrupt)
+    }
+}
+
+// the start of an abbreviation that ends at the cursor
+fn abbreviation_at(abbreviations: &EditorAbbreviations, line: &str, pos: usize) -> Option<usize> {
+    // a word is only finished when the cursor is at its end
+    if line[pos..]
+        .chars()
+        .next()
+        .is_some_and(|c| !c.is_whitespace())
+    {
+        return None;
+    }
+    let cursor = cursor(&line[..pos]);
+    let anywhere = abbreviations.get(&line[cursor.start..pos])?;
+    match cursor.position {
+        Position::Command => Some(cursor.start),
+        _ if *anywhere => Some(cursor.start),
+        _ => None,
+    }
+}
+
+/// Accepts the hint up to the end of its next word, the key does what it does by default when
+/// there is no hint
+pub struct HintWordHandler;
+
+impl ConditionalEventHandler for HintWordHandler {
+    fn handle(&self, _: &Event, _: RepeatCount, _: bool, ctx: &EventContext) -> Option<Cmd> {
+        let hint = ctx.hint_text().filter(|hint| !hint.is_empty())?;
+        Some(Cmd::Insert(1, hint_word(hint).to_string()))
+    }
+}
+
+// leading whitespace and the word after it
+fn hint_word(hint: &str) -> &str {
+    let start = hint.len() - hint.trim_start().len();
+    let end = hint[start..]
+        .find(char::is_whitespace)
+        .map_or(hint.len(), |end| start + end);
+    &hint[..end]
 }
 
 impl Helper for EditorHelper {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn abbreviation_at_test() {
+        let abbreviations =
+            EditorAbbreviations::from([(String::from("gco"), false), (String::from("L"), true)]);
+        let at = |line: &str, pos| abbreviation_at(&abbreviations, line, pos);
+        assert_eq!(at("gco", 3), Some(0));
+        assert_eq!(at("ls | gco", 8), Some(5));
+        assert_eq!(at("echo gco", 8), None);
+        assert_eq!(at("gcox", 3), None);
+        assert_eq!(at("gco x", 3), Some(0));
+        assert_eq!(at("ls L", 4), Some(3));
+        assert_eq!(at("ls", 2), None);
+        assert_eq!(at("", 0), None);
+    }
+
+    #[test]
+    fn hint_word_test() {
+        assert_eq!(hint_word("it commit -m"), "it");
+        assert_eq!(hint_word("  --all"), "  --all");
+        assert_eq!(hint_word(" x "), " x");
+    }
+
+    #[test]
+    fn rows_test() {
+        let mut helper = EditorHelper::new();
+        helper.prompt = "> ".red().to_string();
+        assert_eq!(helper.rows("", 10), 1);
+        assert_eq!(helper.rows("echo abc", 10), 2);
+        assert_eq!(helper.rows("echo a", 10), 1);
+        assert_eq!(helper.rows("echo abcdefghijklmn", 10), 3);
+        assert_eq!(helper.rows("if $x {\n::: echo\n::: }", 10), 3);
+
+        helper.prompt = String::from("dir\n> ");
+        assert_eq!(helper.rows("ls", 10), 2);
+
+        helper.prompt = String::from("> ");
+        // the cursor wraps when the line fills the last column
+        assert_eq!(helper.rows("abcdefgh", 10), 2);
+        assert_eq!(helper.rows("\tx", 10), 1);
+        assert_eq!(helper.rows("\tabc", 10), 2);
+        assert_eq!(helper.rows("日本語日本", 10), 2);
+        assert_eq!(helper.rows("日本語日", 10), 2);
+
+        // continued lines are counted after the continuation prompt
+        helper.continue_input("if $x {");
+        assert_eq!(helper.rows("abcde", 10), 1);
+        assert_eq!(helper.rows("abcdefg", 10), 2);
+    }
+
+    #[test]
+    fn rprompt_test() {
+        let rprompt = |helper: &EditorHelper, line: &str, columns: usize| {
+            let mut output = String::new();
+            helper.write_rprompt(line, columns, &mut output);
+            (
+                strip_ansi_escapes::strip_str(output),
+                helper.rprompt_space.get(),
+            )
+        };
+        let mut helper = EditorHelper::new();
+        helper.prompt = String::from("> ");
+        assert_eq!(rprompt(&helper, "ls", 20), (String::new(), None));
+
+        helper.rprompt = "[R]".red().to_string();
+        assert_eq!(rprompt(&helper, "ls", 20), (String::from("[R]"), Some(13)));
+        // one column is kept free between the line and the right prompt
+        assert_eq!(
+            rprompt(&helper, "abcdefghijklmn", 20),
+            (String::from("[R]"), Some(1))
+        );
+        assert_eq!(
+            rprompt(&helper, "abcdefghijklmno", 20),
+            (String::new(), None)
+        );
+        assert_eq!(rprompt(&helper, "ls", 0), (String::new(), None));
+        assert_eq!(rprompt(&helper, "if $x {\n}", 20), (String::new(), None));
+
+        helper.continue_input("if $x {");
+        assert_eq!(rprompt(&helper, "ls", 20), (String::new(), None));
+    }
+
+    #[test]
+    fn hint_hides_rprompt_test() {
+        let mut helper = EditorHelper::new();
+        helper.rprompt = String::from("[R]");
+        helper.rprompt_space.set(Some(4));
+        let clear = Clear(ClearType::UntilNewLine).to_string();
+        assert!(!highlight::Highlighter::highlight_hint(&helper, "abc").contains(&clear));
+        assert!(highlight::Highlighter::highlight_hint(&helper, "abcd").contains(&clear));
+    }
+
+    #[test]
+    fn is_incomplete_line_test() {
+        assert!(is_incomplete_line("if $x {"));
+        assert!(is_incomplete_line("echo ["));
+        assert!(is_incomplete_line("ls |"));
+        assert!(is_incomplete_line("echo \"abc"));
+        assert!(is_incomplete_line("if $x {\n    echo ("));
+        assert!(!is_incomplete_line("if $x {\n    echo\n}"));
+        assert!(!is_incomplete_line("echo )"));
+        assert!(!is_incomplete_line(""));
+    }
+
+    #[test]
+    fn continue_input_test() {
+        let mut helper = EditorHelper::new();
+        helper.prompt = String::from("> ");
+        assert!(!helper.is_continued());
+        assert_eq!(helper.current_prompt(), "> ");
+
+        assert_eq!(helper.continue_input("if $x {"), "    ");
+        assert!(helper.is_continued());
+        assert_eq!(
+            strip_ansi_escapes::strip_str(helper.current_prompt()),
+            CONTINUATION_PROMPT
+        );
+        assert_eq!(helper.continue_input("    for y in $z {"), "        ");
+        assert_eq!(helper.input("}"), "if $x {\n    for y in $z {\n}");
+        // only the edited line is drawn, the pending lines are already on screen
+        let highlighted = strip_ansi_escapes::strip_str(helper.highlight_edited("echo {"));
+        assert_eq!(highlighted, "echo {");
+
+        helper.reset_input();
+        assert!(!helper.is_continued());
+        assert_eq!(helper.input("ls"), "ls");
+    }
+
+    #[test]
+    fn continuation_prompt_test() {
+        let mut helper = EditorHelper::new();
+        helper.set_continuation_prompt(Some(String::from(".. ")));
+        let highlighted = strip_ansi_escapes::strip_str(helper.highlight_line("if $x {\n}"));
+        assert_eq!(highlighted, "if $x {\n.. }");
+        helper.continue_input("if $x {");
+        assert_eq!(helper.current_prompt(), ".. ");
+
+        helper.set_continuation_prompt(None);
+        let highlighted = strip_ansi_escapes::strip_str(helper.highlight_line("[\n  1]"));
+        assert_eq!(highlighted, format!("[\n{CONTINUATION_PROMPT}  1]"));
+    }
+}
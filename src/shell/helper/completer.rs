@@ -76,7 +76,14 @@ impl FilenameCompleter {
         Self
     }
 
-    pub fn complete_path(&self, line: &str, pos: usize) -> Result<(usize, Vec<Pair>)> {
+    /// Complete a file name, `commands` are user defined names that are completed along with
+    /// executables and builtins when the cursor is at the position of a command
+    pub fn complete_path(
+        &self,
+        line: &str,
+        pos: usize,
+        commands: Option<&[Rc<str>]>,
+    ) -> Result<(usize, Vec<Pair>)> {
         //let (line, pos) = replace_escapes(line, pos);
         let (start, path, esc_char, break_chars, quote): (_, _, _, fn(_: char) -> bool, _) =
             if let Some((idx, quote)) = find_unclosed_quote(&line[..pos]) {
@@ -110,8 +117,8 @@ impl FilenameCompleter {
             };
 
         let mut matches = Vec::new();
-        if start == 0 && !path.contains('/') {
-            matches.extend(command_complete(&path));
+        if let (Some(commands), false) = (commands, path.contains('/')) {
+            matches.extend(command_complete(&path, commands));
         }
         matches.extend(filename_complete(&path, esc_char, break_chars, quote));
         matches.par_sort_by(|a, b| {
@@ -140,18 +147,18 @@ impl Completer for FilenameCompleter {
     type Candidate = Pair;
 
     fn complete(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> Result<(usize, Vec<Pair>)> {
-        self.complete_path(line, pos)
+        self.complete_path(line, pos, None)
     }
 }
 
-fn command_complete(start: &str) -> Vec<Pair> {
+fn command_complete(start: &str, user_commands: &[Rc<str>]) -> Vec<Pair> {
     let executables = Rc::new(executable_finder::executables().unwrap());
     let mut commands: Vec<_> = executables
         .iter()
         .map(|exe| (exe.name.to_string(), Some(exe.path.clone())))
         .collect();
-    // TODO add user defined functions
     commands.extend(get_builtins().map(|s| (s.to_string(), None)));
+    commands.extend(user_commands.iter().map(|s| (s.to_string(), None)));
     commands.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
     let commands = Rc::new(commands);
 
@@ -246,13 +253,7 @@ fn filename_complete(
                         let path = match quote {
                             Quote::Double => escape(path, esc_char, is_break_char, Quote::Double),
                             Quote::Single => path,
-                            Quote::None => {
-                                if path.as_bytes().iter().any(|c| is_break_char(*c as char)) {
-                                    format!("'{path}'")
-                                } else {
-                                    path
-                                }
-                            }
+                            Quote::None => quote_path(path),
                         };
 
                         entries.push(Pair {
@@ -267,6 +268,15 @@ fn filename_complete(
     entries
 }
 
+/// Put a path in single quotes if it contains a character that would end the word
+pub fn quote_path(path: String) -> String {
+    if path.chars().any(is_default_break_char) {
+        format!("'{path}'")
+    } else {
+        path
+    }
+}
+
 #[cfg(any(windows, target_os = "macos"))]
 fn normalize(s: &str) -> Cow<str> {
     // case insensitive
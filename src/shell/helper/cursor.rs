@@ -0,0 +1,177 @@
+use std::sync::Arc;
+
+use miette::NamedSource;
+
+use crate::parser::lexer::{token::TokenType, Lexer};
+
+/// What kind of word the cursor is in, this decides what is completed
+#[derive(Debug, PartialEq, Eq)]
+pub enum Position {
+    /// The first word of a pipeline part
+    Command,
+    /// A word after a command, `args` are the finished words between the command and the cursor
+    Argument { command: String, args: Vec<String> },
+    /// Somewhere no command is run, like in a list or after `let x =`
+    Value,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Cursor {
+    pub position: Position,
+    /// Start of the word the cursor is in
+    pub start: usize,
+    /// Number of parens, braces and brackets that are still open
+    pub depth: usize,
+}
+
+// every paren, brace and bracket starts a new part that ends when it is closed
+struct Part {
+    words: Vec<String>,
+    command: bool,
+}
+
+impl Part {
+    fn new(command: bool) -> Self {
+        Self {
+            words: Vec::new(),
+            command,
+        }
+    }
+}
+
+/// Find the word that ends at the end of `line` and what position it is in
+pub fn cursor(line: &str) -> Cursor {
+    let lexer = Lexer::new(Arc::new(NamedSource::new(String::new(), line.to_string())));
+    let mut parts = vec![Part::new(true)];
+    let mut word: Option<(usize, usize)> = None;
+    let mut quote = None;
+    let mut after_at = false;
+
+    let finish_word = |parts: &mut Vec<Part>, word: &mut Option<(usize, usize)>| {
+        if let Some((start, end)) = word.take() {
+            // safe because there is always at least one part
+            let part = parts.last_mut().unwrap();
+            part.words.push(line[start..end].to_string());
+        }
+    };
+
+    for token in lexer {
+        let (start, end) = (token.span.start(), token.span.end());
+        let at = token.token_type == TokenType::At;
+        if let Some(quote_type) = &quote {
+            if token.token_type == *quote_type {
+                quote = None;
+            }
+            word = word.map(|(start, _)| (start, end));
+            continue;
+        }
+
+        match token.token_type {
+            TokenType::Quote | TokenType::DoubleQuote => {
+                word = Some((word.map_or(start, |(start, _)| start), end));
+                quote = Some(token.token_type);
+            }
+            TokenType::Space => finish_word(&mut parts, &mut word),
+            TokenType::Pipe
+            | TokenType::SemiColon
+            | TokenType::NewLine
+            | TokenType::And
+            | TokenType::Or
+            | TokenType::Exec => {
+                word = None;
+                let part = parts.last_mut().unwrap();
+                part.words.clear();
+                part.command = true;
+            }
+            TokenType::Let | TokenType::Export | TokenType::Assignment => {
+                word = None;
+                parts.last_mut().unwrap().command = false;
+            }
+            TokenType::LeftParen | TokenType::LeftBrace | TokenType::LeftBracket => {
+                finish_word(&mut parts, &mut word);
+                let command = match token.token_type {
+                    TokenType::LeftBrace => !after_at,
+                    TokenType::LeftParen => true,
+                    _ => false,
+                };
+                parts.push(Part::new(command));
+            }
+            TokenType::RightParen | TokenType::RightBrace | TokenType::RightBracket => {
+                word = None;
+                if parts.len() > 1 {
+                    parts.pop();
+                }
+                // the closed group is a word of its own in the outer part
+                parts.last_mut().unwrap().words.push(String::new());
+            }
+            _ => word = Some((word.map_or(start, |(start, _)| start), end)),
+        }
+        after_at = at;
+    }
+
+    let start = match word {
+        Some((start, end)) if end == line.len() => start,
+        _ => line.len(),
+    };
+    let depth = parts.len() - 1;
+    // safe because the first part is never popped
+    let part = parts.pop().unwrap();
+    let position = match part.words.split_first() {
+        _ if !part.command => Position::Value,
+        None => Position::Command,
+        Some((command, args)) => Position::Argument {
+            command: command.clone(),
+            args: args.to_vec(),
+        },
+    };
+
+    Cursor {
+        position,
+        start,
+        depth,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argument(command: &str, args: &[&str]) -> Position {
+        Position::Argument {
+            command: command.to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn cursor_test() {
+        assert_eq!(
+            cursor("ec"),
+            Cursor {
+                position: Position::Command,
+                start: 0,
+                depth: 0,
+            }
+        );
+        assert_eq!(cursor("ls | ma").position, Position::Command);
+        assert_eq!(cursor("ls | ma").start, 5);
+        assert_eq!(
+            cursor("math round --d").position,
+            argument("math", &["round"])
+        );
+        assert_eq!(cursor("math round --d").start, 11);
+        assert_eq!(cursor("echo (ls -").position, argument("ls", &[]));
+        assert_eq!(cursor("echo (ls) -").position, argument("echo", &[""]));
+        assert_eq!(
+            cursor("echo \"a b\" ").position,
+            argument("echo", &["\"a b\""])
+        );
+        assert_eq!(cursor("let x = $cf").position, Position::Value);
+        assert_eq!(cursor("let x = $cf").start, 8);
+        assert_eq!(cursor("print $cfg.servers.0.").start, 6);
+        assert_eq!(cursor("[1 2 ").position, Position::Value);
+        assert_eq!(cursor("@{a: ").position, Position::Value);
+        assert_eq!(cursor("ls | each {|x| pr").position, Position::Command);
+        assert_eq!(cursor("if $x {\n    for y in [1 (2").depth, 3);
+    }
+}
@@ -203,6 +203,12 @@ impl HighlightVisitor {
             LiteralKind::Int(_) => self
                 .spans
                 .push(Spanned::new(ColorType::Literal, literal.span)),
+            LiteralKind::Duration(_) => self
+                .spans
+                .push(Spanned::new(ColorType::Literal, literal.span)),
+            LiteralKind::Filesize(_) => self
+                .spans
+                .push(Spanned::new(ColorType::Literal, literal.span)),
             LiteralKind::Bool(_) => self
                 .spans
                 .push(Spanned::new(ColorType::Literal, literal.span)),
@@ -287,6 +293,22 @@ impl HighlightVisitor {
                 ));
                 self.visit_expr(expr);
             }
+            StatementKind::AssignPath(target, expr) => {
+                self.visit_expr(target);
+                self.spans.push(Spanned::new(
+                    ColorType::Keyword,
+                    Span::new(target.span.end(), expr.span.start()),
+                ));
+                self.visit_expr(expr);
+            }
+            StatementKind::AssignOpPath(target, op, expr) => {
+                self.visit_expr(target);
+                self.spans.push(Spanned::new(
+                    ColorType::Keyword,
+                    Span::new(op.span.start(), op.span.end()),
+                ));
+                self.visit_expr(expr);
+            }
             StatementKind::If(expr, block, next) => {
                 self.spans.push(Spanned::new(
                     ColorType::Keyword,
@@ -0,0 +1,505 @@
+use std::{
+    collections::HashSet,
+    io::{self, Write},
+    sync::{Arc, Mutex},
+};
+
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
+    execute, queue,
+    style::{Print, StyledContent, Stylize},
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use rustyline::{Cmd, ConditionalEventHandler, EventContext, RepeatCount};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::shell::history::{HistoryEntry, JsonHistory};
+
+/// Ends the line that is being edited so that the shell can open the history search, the line is
+/// used as the query
+pub struct SearchHandler(pub(super) Arc<Mutex<Option<String>>>);
+
+impl ConditionalEventHandler for SearchHandler {
+    fn handle(
+        &self,
+        _: &rustyline::Event,
+        _: RepeatCount,
+        _: bool,
+        ctx: &EventContext,
+    ) -> Option<Cmd> {
+        *self.0.lock().unwrap() = Some(ctx.line().to_string());
+        Some(Cmd::Interrupt)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StatusFilter {
+    #[default]
+    All,
+    Succeeded,
+    Failed,
+}
+
+#[derive(Debug, Default)]
+pub struct SearchFilter {
+    /// Only lines entered in this directory
+    pub directory: Option<String>,
+    pub status: StatusFilter,
+    /// Only lines entered in this session
+    pub session: Option<i64>,
+}
+
+impl SearchFilter {
+    fn matches(&self, entry: &HistoryEntry) -> bool {
+        let status = match self.status {
+            StatusFilter::All => true,
+            StatusFilter::Succeeded => entry.status == Some(0),
+            StatusFilter::Failed => entry.status.is_some_and(|status| status != 0),
+        };
+        status
+            && self
+                .directory
+                .as_ref()
+                .is_none_or(|dir| *dir == entry.working_dir)
+            && self.session.is_none_or(|id| entry.session == Some(id))
+    }
+}
+
+#[derive(Debug)]
+pub struct SearchMatch<'a> {
+    pub entry: &'a HistoryEntry,
+    /// Char indices of the entry that matched the query
+    pub indices: Vec<usize>,
+}
+
+/// Lines that match the filter and contain the query as a subsequence, the best match comes
+/// first and equal matches are ordered newest first. Repeated lines are only kept once.
+pub fn search<'a>(
+    entries: impl DoubleEndedIterator<Item = &'a HistoryEntry>,
+    query: &str,
+    filter: &SearchFilter,
+) -> Vec<SearchMatch<'a>> {
+    let mut seen = HashSet::new();
+    let mut matches: Vec<_> = entries
+        .rev()
+        .filter(|entry| filter.matches(entry) && seen.insert(entry.entry.as_str()))
+        .filter_map(|entry| {
+            let (score, indices) = fuzzy_match(query, &entry.entry)?;
+            Some((score, SearchMatch { entry, indices }))
+        })
+        .collect();
+    matches.sort_by_key(|(score, _)| -score);
+    matches.into_iter().map(|(_, m)| m).collect()
+}
+
+// characters that follow each other or start a word score higher
+fn fuzzy_match(query: &str, text: &str) -> Option<(i64, Vec<usize>)> {
+    let mut query = query.chars().flat_map(char::to_lowercase).peekable();
+    let chars: Vec<char> = text.chars().collect();
+    let mut indices: Vec<usize> = Vec::new();
+    let mut score = 0;
+    for (i, c) in chars.iter().enumerate() {
+        let Some(&wanted) = query.peek() else {
+            break;
+        };
+        if !c.to_lowercase().eq(std::iter::once(wanted)) {
+            continue;
+        }
+        score += 1;
+        if indices.last().is_some_and(|&last| last + 1 == i) {
+            score += 4;
+        }
+        if i == 0 || !chars[i - 1].is_alphanumeric() {
+            score += 2;
+        }
+        indices.push(i);
+        query.next();
+    }
+    query.peek().is_none().then_some((score, indices))
+}
+
+/// What a picker searches through
+trait PickerSource {
+    type Match<'a>
+    where
+        Self: 'a;
+    type Picked;
+
+    /// The matches for the query, best first
+    fn matches(&self, query: &str) -> Vec<Self::Match<'_>>;
+    /// The text of a match, the char indices that matched and what is shown before it
+    fn row<'a>(
+        &'a self,
+        m: &'a Self::Match<'_>,
+    ) -> (&'a str, &'a [usize], StyledContent<&'static str>);
+    fn picked(&self, m: &Self::Match<'_>) -> Self::Picked;
+    /// The line between the matches and the query
+    fn info(&self, matches: usize) -> String;
+    /// Keys that the picker does not handle itself
+    fn key(&mut self, _code: KeyCode, _ctrl: bool) {}
+}
+
+struct Picker<S> {
+    source: S,
+    query: String,
+    selected: usize,
+}
+
+impl<S: PickerSource> Picker<S> {
+    fn new(source: S, query: &str) -> Self {
+        Self {
+            source,
+            query: query.to_string(),
+            selected: 0,
+        }
+    }
+
+    // runs on the alternate screen in raw mode
+    fn run(&mut self) -> io::Result<Option<S::Picked>> {
+        let mut stdout = io::stdout();
+        terminal::enable_raw_mode()?;
+        execute!(stdout, EnterAlternateScreen)?;
+        let res = self.read_keys(&mut stdout);
+        execute!(stdout, LeaveAlternateScreen)?;
+        terminal::disable_raw_mode()?;
+        res
+    }
+
+    fn read_keys(&mut self, stdout: &mut impl Write) -> io::Result<Option<S::Picked>> {
+        loop {
+            let matches = self.source.matches(&self.query);
+            self.selected = self.selected.min(matches.len().saturating_sub(1));
+            self.draw(stdout, &matches)?;
+
+            let Event::Key(KeyEvent {
+                code, modifiers, ..
+            }) = event::read()?
+            else {
+                continue;
+            };
+            let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+            match code {
+                KeyCode::Esc => return Ok(None),
+                KeyCode::Char('c' | 'g') if ctrl => return Ok(None),
+                KeyCode::Enter => {
+                    return Ok(matches.get(self.selected).map(|m| self.source.picked(m)))
+                }
+                KeyCode::Up => self.selected += 1,
+                KeyCode::Char('p' | 'r') if ctrl => self.selected += 1,
+                KeyCode::Down => self.selected = self.selected.saturating_sub(1),
+                KeyCode::Char('n') if ctrl => self.selected = self.selected.saturating_sub(1),
+                KeyCode::Char('u') if ctrl => self.query.clear(),
+                KeyCode::Char(c) if !ctrl => {
+                    self.query.push(c);
+                    self.selected = 0;
+                }
+                KeyCode::Backspace => {
+                    self.query.pop();
+                    self.selected = 0;
+                }
+                code => {
+                    drop(matches);
+                    self.source.key(code, ctrl)
+                }
+            }
+        }
+    }
+
+    // the query is on the last row with the info above it, matches grow upwards from there
+    fn draw(&self, stdout: &mut impl Write, matches: &[S::Match<'_>]) -> io::Result<()> {
+        let (columns, rows) = terminal::size()?;
+        let width = columns as usize;
+        queue!(stdout, Hide, Clear(ClearType::All))?;
+
+        let list_rows = rows.saturating_sub(2) as usize;
+        // scrolls so the selected line stays visible
+        let offset = self.selected.saturating_sub(list_rows.saturating_sub(1));
+        for (i, m) in matches.iter().enumerate().skip(offset).take(list_rows) {
+            let row = (list_rows - 1 - (i - offset)) as u16;
+            queue!(stdout, MoveTo(0, row))?;
+            let selected = i == self.selected;
+            let marker = if selected { "> ".red() } else { "  ".reset() };
+            let (text, indices, prefix) = self.source.row(m);
+            let used = 2 + prefix.content().width();
+            queue!(stdout, Print(marker), Print(prefix))?;
+            write_entry(stdout, text, indices, width.saturating_sub(used), selected)?;
+        }
+
+        let info: String = self
+            .source
+            .info(matches.len())
+            .chars()
+            .take(width)
+            .collect();
+        queue!(
+            stdout,
+            MoveTo(0, rows.saturating_sub(2)),
+            Print(info.dark_grey()),
+            MoveTo(0, rows.saturating_sub(1)),
+            Print("> ".green()),
+            Print(&self.query),
+            Show,
+        )?;
+        stdout.flush()
+    }
+}
+
+struct HistorySource<'a> {
+    history: &'a JsonHistory,
+    filter: SearchFilter,
+    directory: String,
+}
+
+impl PickerSource for HistorySource<'_> {
+    type Match<'a>
+        = SearchMatch<'a>
+    where
+        Self: 'a;
+    type Picked = String;
+
+    fn matches(&self, query: &str) -> Vec<SearchMatch<'_>> {
+        search(self.history.iter(), query, &self.filter)
+    }
+
+    fn row<'a>(
+        &'a self,
+        m: &'a SearchMatch<'_>,
+    ) -> (&'a str, &'a [usize], StyledContent<&'static str>) {
+        let status = match m.entry.status {
+            Some(0) => "✓ ".green(),
+            Some(_) => "✗ ".red(),
+            None => "  ".reset(),
+        };
+        (&m.entry.entry, &m.indices, status)
+    }
+
+    fn picked(&self, m: &SearchMatch<'_>) -> String {
+        m.entry.entry.clone()
+    }
+
+    fn info(&self, matches: usize) -> String {
+        let status = match self.filter.status {
+            StatusFilter::All => "any status",
+            StatusFilter::Succeeded => "succeeded",
+            StatusFilter::Failed => "failed",
+        };
+        format!(
+            "  {}/{}  ^D {}  ^F {}  ^S {}",
+            matches,
+            self.history.iter().count(),
+            if self.filter.directory.is_some() {
+                "this directory"
+            } else {
+                "all directories"
+            },
+            status,
+            if self.filter.session.is_some() {
+                "this session"
+            } else {
+                "all sessions"
+            },
+        )
+    }
+
+    fn key(&mut self, code: KeyCode, ctrl: bool) {
+        match code {
+            KeyCode::Char('d') if ctrl => {
+                self.filter.directory = match self.filter.directory {
+                    Some(_) => None,
+                    None => Some(self.directory.clone()),
+                }
+            }
+            KeyCode::Char('f') if ctrl => {
+                self.filter.status = match self.filter.status {
+                    StatusFilter::All => StatusFilter::Succeeded,
+                    StatusFilter::Succeeded => StatusFilter::Failed,
+                    StatusFilter::Failed => StatusFilter::All,
+                }
+            }
+            KeyCode::Char('s') if ctrl => {
+                self.filter.session = match self.filter.session {
+                    Some(_) => None,
+                    None => Some(self.history.session()),
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Full screen fuzzy search through the history, returns the picked line or `None` when the
+/// search was cancelled
+pub fn search_history(history: &JsonHistory, query: &str) -> io::Result<Option<String>> {
+    let source = HistorySource {
+        history,
+        filter: SearchFilter::default(),
+        directory: std::env::current_dir()
+            .map(|dir| dir.to_string_lossy().to_string())
+            .unwrap_or_default(),
+    };
+    Picker::new(source, query).run()
+}
+
+struct ListSource<'a>(&'a [String]);
+
+impl PickerSource for ListSource<'_> {
+    /// The index of the item and the char indices that matched
+    type Match<'a>
+        = (usize, Vec<usize>)
+    where
+        Self: 'a;
+    type Picked = usize;
+
+    // the sort is stable so equal matches keep the order of the list
+    fn matches(&self, query: &str) -> Vec<(usize, Vec<usize>)> {
+        let mut matches: Vec<_> = self
+            .0
+            .iter()
+            .enumerate()
+            .filter_map(|(i, item)| Some((i, fuzzy_match(query, item)?)))
+            .collect();
+        matches.sort_by_key(|(_, (score, _))| -score);
+        matches
+            .into_iter()
+            .map(|(i, (_, indices))| (i, indices))
+            .collect()
+    }
+
+    fn row<'a>(
+        &'a self,
+        (item, indices): &'a (usize, Vec<usize>),
+    ) -> (&'a str, &'a [usize], StyledContent<&'static str>) {
+        (&self.0[*item], indices, "".reset())
+    }
+
+    fn picked(&self, (item, _): &(usize, Vec<usize>)) -> usize {
+        *item
+    }
+
+    fn info(&self, matches: usize) -> String {
+        format!("  {}/{}", matches, self.0.len())
+    }
+}
+
+/// Full screen fuzzy search through a list, equal matches keep the order of the list. Returns
+/// the index of the picked item or `None` when the search was cancelled.
+pub fn pick(items: &[String]) -> io::Result<Option<usize>> {
+    Picker::new(ListSource(items), "").run()
+}
+
+// only the first line of an entry is shown, matched characters are highlighted
+fn write_entry(
+    stdout: &mut impl Write,
+    text: &str,
+    indices: &[usize],
+    width: usize,
+    selected: bool,
+) -> io::Result<()> {
+    let mut used = 0;
+    let mut indices = indices.iter().peekable();
+    for (i, c) in text.chars().enumerate() {
+        if c == '\n' {
+            queue!(stdout, Print(" …".dark_grey()))?;
+            break;
+        }
+        used += c.width().unwrap_or(0);
+        if used > width {
+            break;
+        }
+        let matched = indices.next_if_eq(&&i).is_some();
+        let styled = match (matched, selected) {
+            (true, _) => c.yellow().bold(),
+            (false, true) => c.bold(),
+            (false, false) => c.reset(),
+        };
+        queue!(stdout, Print(styled))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(line: &str, dir: &str, status: Option<i64>, session: i64) -> HistoryEntry {
+        HistoryEntry {
+            entry: line.to_string(),
+            working_dir: dir.to_string(),
+            timestamp: None,
+            status,
+            duration: None,
+            session: Some(session),
+        }
+    }
+
+    fn lines<'a>(matches: Vec<SearchMatch<'a>>) -> Vec<&'a str> {
+        matches.iter().map(|m| m.entry.entry.as_str()).collect()
+    }
+
+    #[test]
+    fn fuzzy_match_test() {
+        assert_eq!(fuzzy_match("gco", "git checkout").unwrap().1, vec![0, 4, 9]);
+        assert_eq!(fuzzy_match("", "ls").unwrap(), (0, Vec::new()));
+        assert!(fuzzy_match("xyz", "git checkout").is_none());
+        assert!(fuzzy_match("LS", "ls -a").is_some());
+        // consecutive characters beat scattered ones
+        assert!(fuzzy_match("ls", "ls -a").unwrap().0 > fuzzy_match("ls", "lines x").unwrap().0);
+    }
+
+    #[test]
+    fn search_test() {
+        let entries = [
+            entry("cargo build", "/a", Some(0), 1),
+            entry("cargo test", "/a", Some(101), 1),
+            entry("cd /tmp", "/b", Some(0), 2),
+            entry("cargo build", "/b", Some(0), 2),
+        ];
+        let all = SearchFilter::default();
+        assert_eq!(
+            lines(search(entries.iter(), "", &all)),
+            vec!["cargo build", "cd /tmp", "cargo test"]
+        );
+        assert_eq!(
+            lines(search(entries.iter(), "ct", &all)),
+            vec!["cd /tmp", "cargo test"]
+        );
+
+        let failed = SearchFilter {
+            status: StatusFilter::Failed,
+            ..Default::default()
+        };
+        assert_eq!(
+            lines(search(entries.iter(), "", &failed)),
+            vec!["cargo test"]
+        );
+
+        let here = SearchFilter {
+            directory: Some(String::from("/b")),
+            session: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(
+            lines(search(entries.iter(), "", &here)),
+            vec!["cargo build", "cd /tmp"]
+        );
+    }
+
+    #[test]
+    fn list_matches_test() {
+        let items = [
+            String::from("/home/src"),
+            String::from("/tmp"),
+            String::from("/srv"),
+        ];
+        let source = ListSource(&items);
+        let picked: Vec<_> = source
+            .matches("")
+            .iter()
+            .map(|m| source.picked(m))
+            .collect();
+        assert_eq!(picked, vec![0, 1, 2]);
+        assert_eq!(source.matches("sr"), vec![(0, vec![6, 7]), (2, vec![1, 2])]);
+        assert_eq!(source.info(1), "  1/3");
+    }
+}
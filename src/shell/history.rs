@@ -1,6 +1,6 @@
 use std::{
     borrow::Cow,
-    collections::{vec_deque, VecDeque},
+    collections::{vec_deque, HashSet, VecDeque},
     fs::{File, OpenOptions},
     io::SeekFrom,
     ops::Index,
@@ -9,16 +9,31 @@ use std::{
 };
 
 use fd_lock::RwLock;
+use rand::Rng;
 use rustyline::{
     history::{History, SearchDirection, SearchResult},
     Config, HistoryDuplicates, Result,
 };
 use serde::{Deserialize, Serialize};
 
+pub mod import;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HistoryEntry {
     pub entry: String,
     pub working_dir: String,
+    /// Milliseconds since the unix epoch when the line was entered
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<i64>,
+    /// Exit status of the line, `None` for lines that have not finished
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<i64>,
+    /// Nanoseconds it took to run the line
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration: Option<i64>,
+    /// Random id of the shell the line was entered in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session: Option<i64>,
 }
 
 #[derive(Default)]
@@ -161,6 +176,82 @@ impl JsonHistory {
         }
     }
 
+    pub fn session(&self) -> i64 {
+        self.mem.session
+    }
+
+    /// Record how the last added line finished, the history is saved after this
+    pub fn finish_last(&mut self, status: i64, duration: i64) {
+        if let Some(entry) = self.mem.entries.back_mut() {
+            entry.status = Some(status);
+            entry.duration = Some(duration);
+        }
+    }
+
+    /// Merge lines imported from another shell into the history file and this history. Lines
+    /// that are already in the history are skipped and the result is ordered by time, lines
+    /// without a time come first. Returns the number of lines that were added.
+    pub fn import(&mut self, path: &Path, entries: Vec<HistoryEntry>) -> Result<usize> {
+        use std::io::Seek;
+
+        let old_umask = umask();
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path);
+        restore_umask(old_umask);
+        let mut lock = RwLock::new(file?);
+        let mut lock_guard = lock.write()?;
+
+        let mut other = self.empty_copy();
+        other.load_from(&lock_guard)?;
+        let first_new_entry = self.mem.len().saturating_sub(self.new_entries);
+        for entry in self.mem.entries.iter().skip(first_new_entry) {
+            other.mem.add_entry(entry.clone());
+        }
+
+        let mut known: HashSet<String> = other.iter().map(|entry| entry.entry.clone()).collect();
+        // the newest of repeated lines is kept
+        let mut imported: Vec<_> = entries
+            .into_iter()
+            .rev()
+            .filter(|entry| known.insert(entry.entry.clone()))
+            .collect();
+        imported.reverse();
+        let count = imported.len();
+
+        let mut merged: Vec<_> = other.mem.entries.drain(..).chain(imported).collect();
+        merged.sort_by_key(|entry| entry.timestamp.unwrap_or(i64::MIN));
+        for entry in merged {
+            other.mem.add_entry(entry);
+        }
+
+        lock_guard.seek(SeekFrom::Start(0))?;
+        lock_guard.set_len(0)?;
+        other.save_to(&lock_guard, false)?;
+        self.mem.entries = other.mem.entries;
+        self.new_entries = 0;
+        self.update_path(path, &lock_guard, self.len())?;
+        Ok(count)
+    }
+
+    // a history with the same settings and no entries
+    fn empty_copy(&self) -> Self {
+        Self {
+            mem: MemHistory {
+                entries: VecDeque::new(),
+                max_len: self.mem.max_len,
+                ignore_space: self.mem.ignore_space,
+                ignore_dups: self.mem.ignore_dups,
+                session: self.mem.session,
+            },
+            new_entries: 0,
+            path_info: None,
+        }
+    }
+
     /// Return a forward iterator.
     #[must_use]
     pub fn iter(&self) -> impl DoubleEndedIterator<Item = &HistoryEntry> + '_ {
@@ -253,16 +344,7 @@ impl History for JsonHistory {
             return self.update_path(path, &lock_guard, size);
         }
         // we may need to truncate file before appending new entries
-        let mut other = Self {
-            mem: MemHistory {
-                entries: VecDeque::new(),
-                max_len: self.mem.max_len,
-                ignore_space: self.mem.ignore_space,
-                ignore_dups: self.mem.ignore_dups,
-            },
-            new_entries: 0,
-            path_info: None,
-        };
+        let mut other = self.empty_copy();
         other.load_from(&lock_guard)?;
         let first_new_entry = self.mem.len().saturating_sub(self.new_entries);
         for entry in self.mem.entries.iter().skip(first_new_entry) {
@@ -366,6 +448,7 @@ struct MemHistory {
     max_len: usize,
     ignore_space: bool,
     ignore_dups: bool,
+    session: i64,
 }
 
 impl MemHistory {
@@ -381,6 +464,7 @@ impl MemHistory {
             max_len: config.max_history_size(),
             ignore_space: config.history_ignore_space(),
             ignore_dups: config.history_duplicates() == HistoryDuplicates::IgnoreConsecutive,
+            session: rand::thread_rng().gen_range(0..i64::MAX),
         }
     }
 
@@ -460,6 +544,13 @@ impl MemHistory {
             working_dir: std::env::current_dir()
                 .map(|dir| dir.to_string_lossy().to_string())
                 .unwrap_or_else(|_| String::new()),
+            timestamp: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .ok()
+                .and_then(|since| since.as_millis().try_into().ok()),
+            status: None,
+            duration: None,
+            session: Some(self.session),
         };
 
         self.entries.push_back(entry);
@@ -0,0 +1,245 @@
+use std::path::{Path, PathBuf};
+
+use super::HistoryEntry;
+
+/// Shells whose history can be imported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryFormat {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl HistoryFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "bash" => Some(Self::Bash),
+            "zsh" => Some(Self::Zsh),
+            "fish" => Some(Self::Fish),
+            _ => None,
+        }
+    }
+
+    /// Where the shell keeps its history when it is not configured otherwise
+    pub fn default_path(self, home: &Path) -> PathBuf {
+        match self {
+            Self::Bash => home.join(".bash_history"),
+            Self::Zsh => match std::env::var_os("HISTFILE") {
+                Some(path) => PathBuf::from(path),
+                None => home.join(".zsh_history"),
+            },
+            Self::Fish => match std::env::var_os("XDG_DATA_HOME") {
+                Some(data) => Path::new(&data).join("fish/fish_history"),
+                None => home.join(".local/share/fish/fish_history"),
+            },
+        }
+    }
+
+    /// Parse a history file into entries in the order they are in the file
+    pub fn parse(self, bytes: &[u8]) -> Vec<HistoryEntry> {
+        match self {
+            Self::Bash => parse_bash(&String::from_utf8_lossy(bytes)),
+            Self::Zsh => parse_zsh(&String::from_utf8_lossy(&unmetafy(bytes))),
+            Self::Fish => parse_fish(&String::from_utf8_lossy(bytes)),
+        }
+    }
+}
+
+// times that do not fit once they are converted are left out
+fn entry(line: String, timestamp: Option<i64>, duration: Option<i64>) -> HistoryEntry {
+    HistoryEntry {
+        entry: line,
+        working_dir: String::new(),
+        timestamp: timestamp.and_then(|seconds| seconds.checked_mul(1000)),
+        status: None,
+        duration: duration.and_then(|seconds| seconds.checked_mul(1_000_000_000)),
+        session: None,
+    }
+}
+
+// with `HISTTIMEFORMAT` set every line is preceded by a `#` comment holding its timestamp
+fn parse_bash(src: &str) -> Vec<HistoryEntry> {
+    let mut entries = Vec::new();
+    let mut timestamp = None;
+    for line in src.lines() {
+        if let Some(seconds) = line.strip_prefix('#').and_then(|s| s.parse().ok()) {
+            timestamp = Some(seconds);
+        } else if !line.trim().is_empty() {
+            entries.push(entry(line.to_string(), timestamp.take(), None));
+        }
+    }
+    entries
+}
+
+// extended history lines look like `: <start>:<duration>;<command>`, lines of a multi-line command
+// end with a backslash
+fn parse_zsh(src: &str) -> Vec<HistoryEntry> {
+    let mut entries = Vec::new();
+    let mut lines = src.lines();
+    while let Some(line) = lines.next() {
+        let (timestamp, duration, mut command) = match parse_zsh_extended(line) {
+            Some((start, duration, command)) => (Some(start), Some(duration), command.to_string()),
+            None => (None, None, line.to_string()),
+        };
+        while command.ends_with('\\') {
+            command.pop();
+            command.push('\n');
+            match lines.next() {
+                Some(next) => command.push_str(next),
+                None => break,
+            }
+        }
+        if !command.trim().is_empty() {
+            entries.push(entry(command, timestamp, duration));
+        }
+    }
+    entries
+}
+
+fn parse_zsh_extended(line: &str) -> Option<(i64, i64, &str)> {
+    let (header, command) = line.strip_prefix(": ")?.split_once(';')?;
+    let (start, duration) = header.split_once(':')?;
+    Some((start.trim().parse().ok()?, duration.parse().ok()?, command))
+}
+
+// zsh stores some bytes as a 0x83 marker followed by the byte xor 32
+fn unmetafy(bytes: &[u8]) -> Vec<u8> {
+    const META: u8 = 0x83;
+    let mut output = Vec::with_capacity(bytes.len());
+    let mut iter = bytes.iter();
+    while let Some(&byte) = iter.next() {
+        match byte {
+            META => output.extend(iter.next().map(|next| next ^ 32)),
+            byte => output.push(byte),
+        }
+    }
+    output
+}
+
+// a list of `- cmd: <command>` items with a `when: <timestamp>` field, other fields are ignored
+fn parse_fish(src: &str) -> Vec<HistoryEntry> {
+    let mut entries: Vec<HistoryEntry> = Vec::new();
+    for line in src.lines() {
+        if let Some(command) = line.strip_prefix("- cmd: ") {
+            entries.push(entry(unescape_fish(command), None, None));
+        } else if let Some(when) = line.trim_start().strip_prefix("when: ") {
+            if let (Some(last), Ok(seconds)) = (entries.last_mut(), when.parse::<i64>()) {
+                last.timestamp = seconds.checked_mul(1000);
+            }
+        }
+    }
+    entries
+}
+
+fn unescape_fish(command: &str) -> String {
+    let mut output = String::with_capacity(command.len());
+    let mut chars = command.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => output.push('\n'),
+            Some('\\') => output.push('\\'),
+            Some(c) => {
+                output.push('\\');
+                output.push(c);
+            }
+            None => output.push('\\'),
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use rustyline::history::History;
+
+    use super::*;
+    use crate::shell::history::JsonHistory;
+
+    fn lines(entries: &[HistoryEntry]) -> Vec<(&str, Option<i64>)> {
+        entries
+            .iter()
+            .map(|entry| (entry.entry.as_str(), entry.timestamp))
+            .collect()
+    }
+
+    #[test]
+    fn parse_bash_test() {
+        let entries = HistoryFormat::Bash.parse(b"ls -a\n#1700000000\ncd /tmp\n\ngit status\n");
+        assert_eq!(
+            lines(&entries),
+            vec![
+                ("ls -a", None),
+                ("cd /tmp", Some(1_700_000_000_000)),
+                ("git status", None)
+            ]
+        );
+
+        let entries = HistoryFormat::Bash.parse(b"#9223372036854775807\nls\n");
+        assert_eq!(lines(&entries), vec![("ls", None)]);
+    }
+
+    #[test]
+    fn parse_zsh_test() {
+        let src = b": 1700000000:3;cargo build\n: 1700000010:0;for x in a b; do\\\n  echo $x\\\ndone\nls\n";
+        let entries = HistoryFormat::Zsh.parse(src);
+        assert_eq!(
+            lines(&entries),
+            vec![
+                ("cargo build", Some(1_700_000_000_000)),
+                ("for x in a b; do\n  echo $x\ndone", Some(1_700_000_010_000)),
+                ("ls", None)
+            ]
+        );
+        assert_eq!(entries[0].duration, Some(3_000_000_000));
+
+        // `→` is 0xe2 0x86 0x92, zsh writes the last two bytes as the meta marker and byte ^ 32
+        let entries = HistoryFormat::Zsh.parse(b"echo \xe2\x83\xa6\x83\xb2\n");
+        assert_eq!(entries[0].entry, "echo →");
+
+        let entries = HistoryFormat::Zsh.parse(b": 9223372036854776:9223372037;ls\n");
+        assert_eq!(lines(&entries), vec![("ls", None)]);
+        assert_eq!(entries[0].duration, None);
+    }
+
+    #[test]
+    fn parse_fish_test() {
+        let src = "- cmd: echo hi\n  when: 1700000000\n- cmd: printf 'a\\nb' \\\\\n  when: 1700000005\n  paths:\n    - a\n";
+        let entries = HistoryFormat::Fish.parse(src.as_bytes());
+        assert_eq!(
+            lines(&entries),
+            vec![
+                ("echo hi", Some(1_700_000_000_000)),
+                ("printf 'a\nb' \\", Some(1_700_000_005_000))
+            ]
+        );
+
+        let entries = HistoryFormat::Fish.parse(b"- cmd: ls\n  when: 9223372036854775807\n");
+        assert_eq!(lines(&entries), vec![("ls", None)]);
+    }
+
+    #[test]
+    fn import_test() {
+        let path = std::env::temp_dir().join(format!("crust-import-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut history = JsonHistory::new();
+        history.add("ls").unwrap();
+        history.save(&path).unwrap();
+
+        let entries = HistoryFormat::Bash.parse(b"#1\necho a\n#3\nls\n#2\necho b\n#4\necho a\n");
+        assert_eq!(history.import(&path, entries.clone()).unwrap(), 2);
+        // lines that are already in the history are not added again
+        assert_eq!(history.import(&path, entries).unwrap(), 0);
+
+        let mut loaded = JsonHistory::new();
+        loaded.load(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        for history in [&history, &loaded] {
+            let lines: Vec<_> = history.iter().map(|entry| entry.entry.as_str()).collect();
+            assert_eq!(lines, vec!["echo b", "echo a", "ls"]);
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use std::rc::Rc;
+
+use super::{
+    frame::Frame, parser::ast::expr::closure::Closure, stream::OutputStream, value::Value, Shell,
+};
+
+/// A closure and the scope it was defined in, added to an event
+pub type Hook = (HookEvent, Rc<(Rc<Closure>, Frame)>);
+
+/// Points in the life of the interactive shell where closures added with `hook add` run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+    /// Before the prompt is printed
+    PrePrompt,
+    /// Before a line is run, gets the line
+    PreExec,
+    /// After a line has run, gets the exit status and how long it took
+    PostExec,
+    /// After the working directory changed, gets the old and the new directory
+    OnCd,
+}
+
+impl HookEvent {
+    pub const ALL: [HookEvent; 4] = [Self::PrePrompt, Self::PreExec, Self::PostExec, Self::OnCd];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|event| event.name() == name)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::PrePrompt => "pre_prompt",
+            Self::PreExec => "pre_exec",
+            Self::PostExec => "post_exec",
+            Self::OnCd => "on_cd",
+        }
+    }
+}
+
+impl Shell {
+    pub fn add_hook(&mut self, event: HookEvent, closure: Rc<(Rc<Closure>, Frame)>) {
+        self.hooks.push((event, closure));
+    }
+
+    pub fn hooks(&self) -> &[Hook] {
+        &self.hooks
+    }
+
+    /// Remove the hooks of an event, or all of them
+    pub fn clear_hooks(&mut self, event: Option<HookEvent>) {
+        self.hooks
+            .retain(|(hook_event, _)| event.is_some_and(|event| event != *hook_event));
+    }
+
+    /// Run the hooks of an event in the order they were added. A failing hook is reported and
+    /// does not stop the others, hooks never change the exit status. While the hooks of an event
+    /// run the event does not run them again, so an `on_cd` hook can change the directory.
+    pub fn run_hooks(&mut self, event: HookEvent, arguments: &[Value]) {
+        if self.running_hooks.contains(&event) {
+            return;
+        }
+
+        let hooks: Vec<_> = self
+            .hooks
+            .iter()
+            .filter(|(hook_event, _)| *hook_event == event)
+            .map(|(_, closure)| closure.clone())
+            .collect();
+
+        self.running_hooks.push(event);
+        for hook in hooks {
+            let mut output = OutputStream::new_output();
+            let _ = self.run_closure(&hook, arguments.to_vec(), &mut output);
+            output.end();
+        }
+        self.running_hooks.retain(|running| *running != event);
+    }
+}
@@ -107,29 +107,25 @@ impl Parser {
     #[inline(always)]
     #[instrument(level = "trace")]
     fn peek(&mut self) -> Result<&Token> {
-        let mut i = 1;
-        loop {
-            let token = self.tokens.front();
-            match token {
-                Some(Token {
-                    token_type: TokenType::Symbol(ref symbol),
-                    ..
-                }) if symbol == "#" => loop {
-                    let token = self.tokens.get(i);
-                    i += 1;
-                    match token {
-                        Some(token) => {
-                            if token.token_type == TokenType::NewLine {
-                                break;
-                            }
-                        }
-                        None => return Err(SyntaxErrorKind::ExpectedToken),
-                    }
-                },
-                Some(token) => return Ok(token),
-                None => return Err(SyntaxErrorKind::ExpectedToken),
+        // comments are dropped so that the token is also the next one that is eaten, the new line
+        // is kept because it ends the statement before the comment
+        while let Some(Token {
+            token_type: TokenType::Symbol(ref symbol),
+            ..
+        }) = self.tokens.front()
+        {
+            if symbol != "#" {
+                break;
+            }
+            while self
+                .tokens
+                .front()
+                .is_some_and(|token| token.token_type != TokenType::NewLine)
+            {
+                self.tokens.pop_front();
             }
         }
+        self.tokens.front().ok_or(SyntaxErrorKind::ExpectedToken)
     }
 
     #[inline(always)]
@@ -384,18 +380,17 @@ impl Parser {
                 let var: Variable = self.parse_variable(true)?;
                 let var_span = var.span;
                 if let Ok(token) = self.peek() {
-                    match token.token_type {
-                        TokenType::Dot => {
-                            return Ok(self
-                                .parse_column(ExprKind::Variable(var).spanned(var_span))?
-                                .into())
-                        }
-                        TokenType::LeftBracket => {
-                            return Ok(self
-                                .parse_index(ExprKind::Variable(var).spanned(var_span))?
-                                .into())
-                        }
-                        _ => (),
+                    let target = match token.token_type {
+                        TokenType::Dot => Some(
+                            self.parse_column(ExprKind::Variable(var.clone()).spanned(var_span))?,
+                        ),
+                        TokenType::LeftBracket => Some(
+                            self.parse_index(ExprKind::Variable(var.clone()).spanned(var_span))?,
+                        ),
+                        _ => None,
+                    };
+                    if let Some(target) = target {
+                        return self.parse_path_assign(target);
                     }
                 }
 
@@ -599,6 +594,8 @@ impl Parser {
             | TokenType::At
             | TokenType::Int(_, _)
             | TokenType::Float(_, _)
+            | TokenType::Duration(_, _)
+            | TokenType::Filesize(_, _)
             | TokenType::Quote
             | TokenType::DoubleQuote
             | TokenType::Sub
@@ -1003,7 +1000,10 @@ impl Parser {
                 } = self.parse_string()?;
                 ExprKind::Literal(LiteralKind::String(Rc::new(string)).spanned(span)).spanned(span)
             }
-            TokenType::Int(_, _) | TokenType::Float(_, _) => {
+            TokenType::Int(_, _)
+            | TokenType::Float(_, _)
+            | TokenType::Duration(_, _)
+            | TokenType::Filesize(_, _) => {
                 let literal: Literal = self.eat()?.try_into()?;
                 let span = literal.span;
                 ExprKind::Literal(literal).spanned(span)
@@ -1047,6 +1047,43 @@ impl Parser {
         }
     }
 
+    // a cell path like `$cfg.servers.0.port` followed by `=` or an assign op updates the variable,
+    // otherwise it starts an expression or a pipeline like a variable does
+    #[instrument(level = "trace")]
+    fn parse_path_assign(&mut self, target: Expr) -> Result<Compound> {
+        let next = self.tokens.iter().find(|token| !token.is_space());
+        let is_assign = next.is_some_and(|token| token.token_type == TokenType::Assignment);
+        let is_assign_op = next.is_some_and(|token| token.token_type.is_assign_op());
+        if !is_assign && !is_assign_op {
+            let next = next.map(|token| token.token_type.clone());
+            return match next {
+                Some(TokenType::Pipe) => {
+                    self.skip_optional_space();
+                    Ok(self.parse_pipe(Some(target))?.into())
+                }
+                Some(token_type) if token_type.is_binop() => {
+                    self.skip_optional_space();
+                    Ok(self.parse_expr_part(Some(target), 0)?.into())
+                }
+                _ => Ok(target.into()),
+            };
+        }
+
+        self.skip_optional_space();
+        let token = self.eat()?;
+        self.skip_optional_space();
+        let expr = self.parse_expr(None, false)?;
+        let span = target.span + expr.span;
+        if is_assign {
+            Ok(StatementKind::AssignPath(target, expr).spanned(span).into())
+        } else {
+            let op = token.to_assign_op();
+            Ok(StatementKind::AssignOpPath(target, op, expr)
+                .spanned(span)
+                .into())
+        }
+    }
+
     #[instrument(level = "trace")]
     fn parse_column(&mut self, expr: Expr) -> Result<Expr> {
         let start = self.eat()?.expect(TokenType::Dot)?.span;
@@ -1054,9 +1091,15 @@ impl Parser {
         let column = match token.token_type {
             TokenType::Symbol(column) => column,
             TokenType::Int(column, _) => column.to_string(),
+            // `.0.port` and `.0.1` are lexed as a single symbol or float
+            TokenType::Float(_, column) => column,
             _ => return Err(SyntaxErrorKind::UnexpectedToken(token)),
         };
-        let expr = ExprKind::Column(P::new(expr), column).spanned(start + token.span);
+        let span = start + token.span;
+        let mut expr = expr;
+        for column in column.split('.') {
+            expr = ExprKind::Column(P::new(expr), column.to_string()).spanned(span);
+        }
 
         match self.peek() {
             Ok(token) => {
@@ -1418,7 +1461,9 @@ impl Parser {
                     _ => {
                         let token = self.eat()?;
                         match token.token_type {
-                            TokenType::Symbol(string) => match parts.last_mut() {
+                            TokenType::Symbol(string)
+                            | TokenType::Duration(_, string)
+                            | TokenType::Filesize(_, string) => match parts.last_mut() {
                                 Some(ArgumentPart {
                                     kind: ArgumentPartKind::Bare(text),
                                     span,
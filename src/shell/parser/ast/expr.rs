@@ -8,6 +8,7 @@ use std::{
     thread,
 };
 
+use bigdecimal::{num_bigint::BigInt, ToPrimitive};
 use subprocess::{CommunicateError, Exec, ExitStatus, Popen, PopenError, Redirection};
 
 use crate::{
@@ -20,7 +21,11 @@ use crate::{
         builtins::{self, functions::BulitinFn},
         frame::Frame,
         stream::{OutputStream, ValueStream},
-        value::{save::save_value, SpannedValue, Type, Value},
+        value::{
+            cell_path::{get_member, PathMember},
+            save::save_value,
+            SpannedValue, Type, Value,
+        },
     },
     P,
 };
@@ -57,6 +62,8 @@ macro_rules! compare_impl {
                     Value::Int(rhs) => Ok(Value::Bool(number $op rhs).spanned(span)),
                     Value::Float(rhs) => Ok(Value::Bool((*number as f64) $op *rhs).spanned(span)),
                     Value::Bool(rhs) => Ok(Value::Bool(*number $op *rhs as i64).spanned(span)),
+                    Value::Filesize(rhs) => Ok(Value::Bool(number $op rhs).spanned(span)),
+                    Value::BigInt(rhs) => Ok(Value::Bool(BigInt::from(*number) $op **rhs).spanned(span)),
                     _ => Err(ShellErrorKind::InvalidBinaryOperand(
                         binop,
                         lhs.to_type(),
@@ -69,6 +76,8 @@ macro_rules! compare_impl {
                     Value::Int(rhs) => Ok(Value::Bool(*number $op *rhs as f64).spanned(span)),
                     Value::Float(rhs) => Ok(Value::Bool(number $op rhs).spanned(span)),
                     Value::Bool(rhs) => Ok(Value::Bool(*number $op *rhs as u8 as f64).spanned(span)),
+                    Value::Filesize(rhs) => Ok(Value::Bool(*number $op *rhs as f64).spanned(span)),
+                    Value::BigInt(rhs) => Ok(Value::Bool(Some(*number) $op rhs.to_f64()).spanned(span)),
                     _ => Err(ShellErrorKind::InvalidBinaryOperand(
                         binop,
                         lhs.to_type(),
@@ -99,6 +108,51 @@ macro_rules! compare_impl {
                         rhs_span,
                     )),
                 },
+                Value::Date(date) => match &rhs {
+                    Value::Date(rhs) => Ok(Value::Bool(date $op rhs).spanned(span)),
+                    _ => Err(ShellErrorKind::InvalidBinaryOperand(
+                        binop,
+                        lhs.to_type(),
+                        rhs.to_type(),
+                        lhs_span,
+                        rhs_span,
+                    )),
+                },
+                Value::Duration(duration) => match &rhs {
+                    Value::Duration(rhs) => Ok(Value::Bool(duration $op rhs).spanned(span)),
+                    _ => Err(ShellErrorKind::InvalidBinaryOperand(
+                        binop,
+                        lhs.to_type(),
+                        rhs.to_type(),
+                        lhs_span,
+                        rhs_span,
+                    )),
+                },
+                Value::BigInt(int) => match &rhs {
+                    Value::BigInt(rhs) => Ok(Value::Bool(int $op rhs).spanned(span)),
+                    Value::Int(rhs) => Ok(Value::Bool(**int $op BigInt::from(*rhs)).spanned(span)),
+                    Value::Float(rhs) => Ok(Value::Bool(int.to_f64() $op Some(*rhs)).spanned(span)),
+                    _ => Err(ShellErrorKind::InvalidBinaryOperand(
+                        binop,
+                        lhs.to_type(),
+                        rhs.to_type(),
+                        lhs_span,
+                        rhs_span,
+                    )),
+                },
+                Value::Filesize(size) => match &rhs {
+                    Value::Filesize(rhs) => Ok(Value::Bool(size $op rhs).spanned(span)),
+                    // plain numbers are a count of bytes so the size column of ls can be filtered
+                    Value::Int(rhs) => Ok(Value::Bool(size $op rhs).spanned(span)),
+                    Value::Float(rhs) => Ok(Value::Bool((*size as f64) $op *rhs).spanned(span)),
+                    _ => Err(ShellErrorKind::InvalidBinaryOperand(
+                        binop,
+                        lhs.to_type(),
+                        rhs.to_type(),
+                        lhs_span,
+                        rhs_span,
+                    )),
+                },
                 _ => Err(ShellErrorKind::InvalidBinaryOperand(
                     binop,
                         lhs.to_type(),
@@ -188,14 +242,8 @@ impl Expr {
             },
             ExprKind::Column(expr, col) => {
                 let (value, span) = expr.eval(ctx)?.into();
-                match value {
-                    Value::Map(map) => match map.get(col.as_str()) {
-                        Some(value) => Ok(value.clone().spanned(span)),
-                        None => Err(ShellErrorKind::ColumnNotFound(col.to_string())),
-                    },
-                    Value::Table(table) => Ok(Value::from(table.column(col)?).spanned(span)),
-                    _ => Err(ShellErrorKind::NoColumns(value.to_type())),
-                }
+                let member = PathMember::parse(col);
+                Ok(get_member(&value, &member, span)?.spanned(span))
             }
             ExprKind::Index { expr, index } => {
                 let (value, span) = expr.eval(ctx)?.into();
@@ -224,9 +272,20 @@ impl Expr {
                 let span = unop.span + span;
                 match unop.kind {
                     UnOpKind::Neg => match &value {
-                        Value::Int(int) => Ok(Value::Int(-*int).spanned(span)),
+                        Value::Int(int) => Ok(ctx.shell.overflow.neg(*int, span)?.spanned(span)),
                         Value::Float(float) => Ok(Value::Float(-*float).spanned(span)),
                         Value::Bool(boolean) => Ok(Value::Int(-(*boolean as i64)).spanned(span)),
+                        Value::Duration(duration) => Ok(ctx
+                            .shell
+                            .overflow
+                            .unit(duration.overflowing_neg(), Value::Duration, "-", span)?
+                            .spanned(span)),
+                        Value::Filesize(size) => Ok(ctx
+                            .shell
+                            .overflow
+                            .unit(size.overflowing_neg(), Value::Filesize, "-", span)?
+                            .spanned(span)),
+                        Value::BigInt(int) => Ok(Value::from(-(**int).clone()).spanned(span)),
                         _ => Err(ShellErrorKind::InvalidUnaryOperand(
                             *unop,
                             value.to_type(),
@@ -241,12 +300,24 @@ impl Expr {
                     let lhs = lhs.eval(ctx)?;
                     let rhs = rhs.eval(ctx)?;
                     let span = lhs.span + rhs.span;
+                    ctx.shell.last_match = match (&lhs.value, &rhs.value) {
+                        (Value::String(string), Value::Regex(regex)) => {
+                            Value::from_captures(&regex.0, string)
+                        }
+                        _ => Value::Null,
+                    };
                     Ok(Value::Bool(lhs.try_match(rhs, binop.span)?).spanned(span))
                 }
                 BinOpKind::NotMatch => {
                     let lhs = lhs.eval(ctx)?;
                     let rhs = rhs.eval(ctx)?;
                     let span = lhs.span + rhs.span;
+                    ctx.shell.last_match = match (&lhs.value, &rhs.value) {
+                        (Value::String(string), Value::Regex(regex)) => {
+                            Value::from_captures(&regex.0, string)
+                        }
+                        _ => Value::Null,
+                    };
                     Ok(Value::Bool(!lhs.try_match(rhs, binop.span)?).spanned(span))
                 }
                 BinOpKind::Range => {
@@ -280,27 +351,27 @@ impl Expr {
                 BinOpKind::Add => {
                     let lhs = lhs.eval(ctx)?;
                     let rhs = rhs.eval(ctx)?;
-                    lhs.try_add(rhs, binop.span)
+                    lhs.try_add(rhs, binop.span, ctx.shell.overflow)
                 }
                 BinOpKind::Sub => {
                     let lhs = lhs.eval(ctx)?;
                     let rhs = rhs.eval(ctx)?;
-                    lhs.try_sub(rhs, binop.span)
+                    lhs.try_sub(rhs, binop.span, ctx.shell.overflow)
                 }
                 BinOpKind::Mul => {
                     let lhs = lhs.eval(ctx)?;
                     let rhs = rhs.eval(ctx)?;
-                    lhs.try_mul(rhs, binop.span)
+                    lhs.try_mul(rhs, binop.span, ctx.shell.overflow)
                 }
                 BinOpKind::Div => {
                     let lhs = lhs.eval(ctx)?;
                     let rhs = rhs.eval(ctx)?;
-                    lhs.try_div(rhs, binop.span)
+                    lhs.try_div(rhs, binop.span, ctx.shell.overflow)
                 }
                 BinOpKind::Expo => {
                     let lhs = lhs.eval(ctx)?;
                     let rhs = rhs.eval(ctx)?;
-                    lhs.try_expo(rhs, binop.span)
+                    lhs.try_expo(rhs, binop.span, ctx.shell.overflow)
                 }
                 BinOpKind::Mod => {
                     let lhs = lhs.eval(ctx)?;
@@ -452,6 +523,7 @@ impl Expr {
                                         new.into_value_stream(),
                                         redirect.append,
                                         false,
+                                        false,
                                     )?;
                                 }
                             }
@@ -537,6 +609,7 @@ impl Expr {
                                         new.into_value_stream(),
                                         redirect.append,
                                         false,
+                                        false,
                                     )?;
                                 }
                             }
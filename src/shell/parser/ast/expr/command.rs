@@ -46,6 +46,8 @@ impl TryFrom<Token> for CommandPart {
             TokenType::Symbol(text) => Ok(CommandPartKind::String(text).spanned(span)),
             TokenType::Int(_, text) => Ok(CommandPartKind::String(text).spanned(span)),
             TokenType::Float(_, text) => Ok(CommandPartKind::String(text).spanned(span)),
+            TokenType::Duration(_, text) => Ok(CommandPartKind::String(text).spanned(span)),
+            TokenType::Filesize(_, text) => Ok(CommandPartKind::String(text).spanned(span)),
             _ => Err(SyntaxErrorKind::UnexpectedToken(token)),
         }
     }
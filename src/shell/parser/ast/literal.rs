@@ -1,6 +1,9 @@
 use std::{convert::TryFrom, rc::Rc};
 
-use bigdecimal::{num_bigint::BigUint, BigDecimal};
+use bigdecimal::{
+    num_bigint::{BigInt, BigUint},
+    BigDecimal,
+};
 use indexmap::IndexMap;
 use num_traits::cast::ToPrimitive;
 use regex::Regex;
@@ -14,7 +17,7 @@ use crate::{
         syntax_error::SyntaxErrorKind,
         Token, TokenType,
     },
-    shell::value::{table::Table, SpannedValue, Value},
+    shell::value::{overflow::Overflow, table::Table, SpannedValue, Value},
 };
 
 #[derive(Debug, Clone)]
@@ -25,6 +28,8 @@ pub enum LiteralKind {
     Map(Vec<(Expr, Expr)>),
     Float(BigDecimal),
     Int(BigUint),
+    Duration(BigDecimal),
+    Filesize(BigDecimal),
     Bool(bool),
     Regex(Rc<(Regex, String)>),
 }
@@ -47,6 +52,8 @@ impl TryFrom<Token> for Literal {
         match token.token_type {
             TokenType::Float(number, _) => Ok(LiteralKind::Float(number).spanned(token.span)),
             TokenType::Int(number, _) => Ok(LiteralKind::Int(number).spanned(token.span)),
+            TokenType::Duration(nanos, _) => Ok(LiteralKind::Duration(nanos).spanned(token.span)),
+            TokenType::Filesize(bytes, _) => Ok(LiteralKind::Filesize(bytes).spanned(token.span)),
             TokenType::True => Ok(LiteralKind::Bool(true).spanned(token.span)),
             TokenType::False => Ok(LiteralKind::Bool(false).spanned(token.span)),
             _ => Err(SyntaxErrorKind::UnexpectedToken(token)),
@@ -94,6 +101,17 @@ impl Literal {
             LiteralKind::Float(number) => Ok(Value::Float(number.to_f64().unwrap()).spanned(span)),
             LiteralKind::Int(number) => match number.to_i64() {
                 Some(number) => Ok(Value::Int(number).spanned(span)),
+                None if ctx.shell.overflow == Overflow::Promote => {
+                    Ok(Value::from(BigInt::from(number.clone())).spanned(span))
+                }
+                None => Err(ShellErrorKind::IntegerOverFlow),
+            },
+            LiteralKind::Duration(nanos) => match nanos.round(0).to_i64() {
+                Some(nanos) => Ok(Value::Duration(nanos).spanned(span)),
+                None => Err(ShellErrorKind::IntegerOverFlow),
+            },
+            LiteralKind::Filesize(bytes) => match bytes.round(0).to_i64() {
+                Some(bytes) => Ok(Value::Filesize(bytes).spanned(span)),
                 None => Err(ShellErrorKind::IntegerOverFlow),
             },
             LiteralKind::Bool(boolean) => Ok(Value::Bool(*boolean).spanned(span)),
@@ -2,13 +2,20 @@ use std::{collections::HashMap, rc::Rc, sync::atomic::Ordering};
 
 use crate::{
     parser::{
-        ast::{expr::Expr, statement::assign_op::AssignOpKind, Block, Variable},
+        ast::{
+            expr::{Expr, ExprKind},
+            statement::assign_op::AssignOpKind,
+            Block, Variable,
+        },
         lexer::token::span::Span,
         shell_error::ShellErrorKind,
     },
     shell::{
         builtins::variables::{is_builtin, set_var, SetResult},
-        value::Value,
+        value::{
+            cell_path::{get_path, set_path, PathMember},
+            SpannedValue, Value,
+        },
     },
     P,
 };
@@ -27,6 +34,8 @@ pub enum StatementKind {
     Declaration(Variable, Expr),
     Assign(Variable, Expr),
     AssignOp(Variable, AssignOp, Expr),
+    AssignPath(Expr, Expr),
+    AssignOpPath(Expr, AssignOp, Expr),
     If(Expr, Block, Option<P<Statement>>),
     Fn(Rc<str>, Rc<Function>),
     Return(Option<Expr>),
@@ -69,18 +78,26 @@ impl Statement {
             }
             StatementKind::AssignOp(var, op, expr) => {
                 let current = var.eval(ctx)?;
-                let res = match op.kind {
-                    AssignOpKind::Expo => current.try_expo(expr.eval(ctx)?, op.span),
-                    AssignOpKind::Add => current.try_add(expr.eval(ctx)?, op.span),
-                    AssignOpKind::Sub => current.try_sub(expr.eval(ctx)?, op.span),
-                    AssignOpKind::Mul => current.try_mul(expr.eval(ctx)?, op.span),
-                    AssignOpKind::Div => current.try_div(expr.eval(ctx)?, op.span),
-                    AssignOpKind::Mod => current.try_mod(expr.eval(ctx)?, op.span),
-                }?;
-
+                let res = apply_assign_op(ctx, current, op, expr)?;
                 ctx.frame.update_var(&var.name, res.value)?;
                 Ok(())
             }
+            StatementKind::AssignPath(target, expr) => {
+                let (var, path) = eval_cell_path(ctx, target)?;
+                let value = expr.eval(ctx)?.value;
+                update_cell_path(ctx, var, |current| {
+                    set_path(current, &path, value, true, target.span)
+                })
+            }
+            StatementKind::AssignOpPath(target, op, expr) => {
+                let (var, path) = eval_cell_path(ctx, target)?;
+                // the indexes were already evaluated so the target is not evaluated again
+                let current = get_path(&var.eval(ctx)?.value, &path, target.span)?;
+                let res = apply_assign_op(ctx, current.spanned(target.span), op, expr)?;
+                update_cell_path(ctx, var, |current| {
+                    set_path(current, &path, res.value, false, target.span)
+                })
+            }
             StatementKind::Declaration(var, expr) => {
                 if is_builtin(&var.name) {
                     return Err(ShellErrorKind::OverrideBuiltin(
@@ -234,3 +251,60 @@ impl Statement {
         }
     }
 }
+
+fn apply_assign_op(
+    ctx: &mut Context,
+    current: SpannedValue,
+    op: &AssignOp,
+    expr: &Expr,
+) -> Result<SpannedValue, ShellErrorKind> {
+    let rhs = expr.eval(ctx)?;
+    match op.kind {
+        AssignOpKind::Expo => current.try_expo(rhs, op.span, ctx.shell.overflow),
+        AssignOpKind::Add => current.try_add(rhs, op.span, ctx.shell.overflow),
+        AssignOpKind::Sub => current.try_sub(rhs, op.span, ctx.shell.overflow),
+        AssignOpKind::Mul => current.try_mul(rhs, op.span, ctx.shell.overflow),
+        AssignOpKind::Div => current.try_div(rhs, op.span, ctx.shell.overflow),
+        AssignOpKind::Mod => current.try_mod(rhs, op.span),
+    }
+}
+
+// the parser only creates path assignments for columns and indexes of a variable
+fn eval_cell_path<'a>(
+    ctx: &mut Context,
+    target: &'a Expr,
+) -> Result<(&'a Variable, Vec<PathMember>), ShellErrorKind> {
+    match &target.kind {
+        ExprKind::Variable(var) => Ok((var, Vec::new())),
+        ExprKind::Column(expr, column) => {
+            let (var, mut path) = eval_cell_path(ctx, expr)?;
+            path.push(PathMember::parse(column));
+            Ok((var, path))
+        }
+        ExprKind::Index { expr, index } => {
+            let (var, mut path) = eval_cell_path(ctx, expr)?;
+            path.push(PathMember::from_value(index.eval(ctx)?)?);
+            Ok((var, path))
+        }
+        _ => unreachable!("cell path assignment to a {:?}", target.kind),
+    }
+}
+
+fn update_cell_path(
+    ctx: &mut Context,
+    var: &Variable,
+    update: impl FnOnce(&mut Value) -> Result<(), ShellErrorKind>,
+) -> Result<(), ShellErrorKind> {
+    if is_builtin(&var.name) {
+        return Err(ShellErrorKind::ReadOnlyVar(var.name.to_string(), var.span));
+    }
+
+    let mut value = var.eval(ctx)?.value;
+    // the variable lets go of its reference while updating so only the changed parts are copied
+    if matches!(value, Value::Map(_) | Value::List(_) | Value::Table(_)) {
+        ctx.frame.update_var(&var.name, Value::Null)?;
+    }
+    let result = update(&mut value);
+    ctx.frame.update_var(&var.name, value)?;
+    result
+}
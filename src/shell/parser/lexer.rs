@@ -8,7 +8,10 @@ use miette::NamedSource;
 use token::{span::Span, Token, TokenType};
 use tracing::instrument;
 
-use crate::str_ext::StrExt;
+use crate::{
+    shell::value::{date::DURATION_UNITS, filesize::FILESIZE_UNITS},
+    str_ext::StrExt,
+};
 
 const LINE_ENDINGS: [&str; 8] = [
     "\u{000D}\u{000A}", // CarriageReturn followed by LineFeed
@@ -256,11 +259,9 @@ impl Lexer {
                     }
                 }
                 None => {
-                    return {
-                        Token {
-                            token_type: TokenType::Symbol(string),
-                            span: Span::new(start, end),
-                        }
+                    return Token {
+                        token_type: parse_unit(&value).unwrap_or(TokenType::Symbol(string)),
+                        span: Span::new(start, end),
                     }
                 }
             }
@@ -278,13 +279,33 @@ impl Lexer {
             }
         } else {
             Token {
-                token_type: TokenType::Symbol(string),
+                token_type: parse_unit(&value).unwrap_or(TokenType::Symbol(string)),
                 span: Span::new(start, end),
             }
         }
     }
 }
 
+// number literals followed by a unit like `500ms` or `1.5GiB`
+// the number is converted to nanoseconds or bytes
+fn parse_unit(value: &str) -> Option<TokenType> {
+    let unit_start = value.find(|c: char| c.is_ascii_alphabetic())?;
+    let (number, unit) = value.split_at(unit_start);
+    let number = number.parse::<BigDecimal>().ok()?;
+
+    if let Some((_, size)) = DURATION_UNITS.iter().find(|(name, _)| *name == unit) {
+        return Some(TokenType::Duration(
+            number * BigDecimal::from(*size),
+            value.to_string(),
+        ));
+    }
+
+    FILESIZE_UNITS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(unit))
+        .map(|(_, size)| TokenType::Filesize(number * BigDecimal::from(*size), value.to_string()))
+}
+
 impl Iterator for Lexer {
     type Item = Token;
     #[inline]
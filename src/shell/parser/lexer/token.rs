@@ -30,6 +30,10 @@ pub enum TokenType {
     Symbol(String),
     Float(BigDecimal, String),
     Int(BigUint, String),
+    /// A duration literal like `500ms`, in nanoseconds
+    Duration(BigDecimal, String),
+    /// A filesize literal like `10kb`, in bytes
+    Filesize(BigDecimal, String),
     Control,
     DoubleQuote,
     Quote,
@@ -200,6 +204,8 @@ impl TokenType {
                 | Symbol(_)
                 | Float(_, _)
                 | Int(_, _)
+                | Duration(_, _)
+                | Filesize(_, _)
                 | True
                 | False
                 | Let
@@ -244,7 +250,10 @@ impl Token {
     pub fn try_into_argpart(self) -> Result<ArgumentPart> {
         use TokenType::*;
         match self.token_type {
-            Symbol(text) => Ok(ArgumentPartKind::Bare(text).spanned(self.span)),
+            // unit literals are kept as text so that `sleep 1s` still works
+            Symbol(text) | Duration(_, text) | Filesize(_, text) => {
+                Ok(ArgumentPartKind::Bare(text).spanned(self.span))
+            }
             Int(number, _) => Ok(ArgumentPartKind::Int(number.into()).spanned(self.span)),
             Float(number, _) => Ok(ArgumentPartKind::Float(number).spanned(self.span)),
             True => Ok(ArgumentPartKind::Expr(
@@ -107,12 +107,14 @@ pub enum ShellErrorKind {
         src: Arc<NamedSource<String>>,
     },
     IntegerOverFlow,
+    ArithmeticOverflow(String, Option<Span>),
     InvalidPipelineInput {
         expected: Type,
         recived: Type,
     },
     AssertionFailed(Span),
     UnknownFileType(String),
+    UnknownFormat(String),
     ExternalExitCode(subprocess::ExitStatus),
     ArgParse(#[from] ParseError),
     Io(Option<PathBuf>, io::Error),
@@ -127,6 +129,8 @@ pub enum ShellErrorKind {
     Json(#[from] serde_json::Error),
     TomlDe(#[from] toml::de::Error),
     TomlSer(#[from] toml::ser::Error),
+    Yaml(#[from] serde_yaml::Error),
+    Ini(#[from] ini::ParseError),
     Readline(#[from] rustyline::error::ReadlineError),
 }
 
@@ -168,6 +172,7 @@ impl fmt::Display for ShellErrorKind {
             NoMatch(pattern, ..) => write!(f, "No match found for pattern `{pattern}`"),
             VariableNotFound(name, ..) => write!(f, "Variable with name `{name}` not found"),
             IntegerOverFlow => write!(f, "Integer literal too large"),
+            ArithmeticOverflow(op, ..) => write!(f, "Integer overflow in `{op}`"),
             Interrupt => write!(f, "^C"),
             InvalidPipelineInput { expected, recived } => {
                 write!(f, "Pipeline expected {expected} recived {recived}")
@@ -213,6 +218,7 @@ impl fmt::Display for ShellErrorKind {
             NegativeIndex { index } => write!(f, "Index `{index}` is not positive"),
             ColumnNotFound(column) => write!(f, "Column `{column}` not found"),
             UnknownFileType(ext) => write!(f, "Unkown file type `.{ext}`"),
+            UnknownFormat(format) => write!(f, "Unknown format `{format}`"),
             ExternalExitCode(exit_status) => write!(
                 f,
                 "Subprocess exit with code: `{}`",
@@ -232,6 +238,8 @@ impl fmt::Display for ShellErrorKind {
             Json(error) => error.fmt(f),
             TomlDe(error) => error.fmt(f),
             TomlSer(error) => error.fmt(f),
+            Yaml(error) => error.fmt(f),
+            Ini(error) => error.fmt(f),
             Open(error) => error.fmt(f),
             Readline(error) => error.fmt(f),
             // these are not real errors and should always be handled and should therefore never be displayed
@@ -266,6 +274,13 @@ impl Diagnostic for ShellError {
                 )]
                 .into_iter(),
             )),
+            ShellErrorKind::ArithmeticOverflow(_, span) => Some(P::new(
+                [LabeledSpan::new_with_span(
+                    Some(String::from("Result does not fit in a 64-bit integer")),
+                    span?,
+                )]
+                .into_iter(),
+            )),
             ShellErrorKind::ReadOnlyVar(ref name, span) => Some(P::new(
                 [LabeledSpan::new_with_span(
                     Some(format!("`{name}` is a read only variable")),
@@ -323,6 +338,7 @@ impl Diagnostic for ShellError {
         Some(match self.error {
             Basic(n, _) => P::new(n),
             DivisionByZero => P::new("Division by Zero Error"),
+            ArithmeticOverflow(..) => P::new("Overflow Error"),
             InvalidBinaryOperand(..)
             | InvalidUnaryOperand(..)
             | InvalidIterator(..)
@@ -343,7 +359,8 @@ impl Diagnostic for ShellError {
             MaxRecursion(..) => P::new("Recursion Error"),
             CommandNotFound(..) | CommandPermissionDenied(..) => P::new("Command Error"),
             FileNotFound(..) | FilePermissionDenied(..) => P::new("File Error"),
-            UnknownFileType(..) | TomlDe(..) | Json(..) => P::new("Deserialization Error"),
+            UnknownFileType(..) | UnknownFormat(..) | TomlDe(..) | Json(..) | Yaml(..)
+            | Ini(..) => P::new("Deserialization Error"),
             TomlSer(..) => P::new("Serialization Error"),
             _ => P::new("Shell Error"),
         })
@@ -5,6 +5,8 @@ use std::{
     rc::Rc,
 };
 
+use bigdecimal::{num_bigint::BigInt, ToPrimitive};
+use chrono::{DateTime, FixedOffset};
 use crossterm::style::{StyledContent, Stylize};
 use indexmap::IndexMap;
 use regex::Regex;
@@ -15,18 +17,26 @@ use crate::parser::{
     shell_error::ShellErrorKind,
 };
 
+pub mod cell_path;
+pub mod date;
+pub mod filesize;
 mod format;
+pub mod overflow;
 pub mod table;
+use overflow::{big_pow, Overflow};
 use table::Table;
 mod types;
 pub use types::Type;
+mod ini;
+pub mod load;
 pub mod save;
+mod tagged;
 
 use self::hashable::HashableValue;
 use super::frame::Frame;
 
 mod de;
-mod hashable;
+pub mod hashable;
 mod ser;
 
 #[derive(Debug, Clone)]
@@ -69,13 +79,18 @@ impl SpannedValue {
     }
 
     // this function converts a value to a string if it can be done so losslessly
+    // units are the exception, they are written the same way they are displayed
     pub fn try_into_string(self) -> Result<String, ShellErrorKind> {
         let (value, span) = self.into();
         match value {
             Value::Int(number) => Ok(number.to_string()),
+            Value::BigInt(int) => Ok(int.to_string()),
             Value::Float(number) => Ok(number.to_string()),
             Value::String(string) => Ok(string.to_string()),
             Value::Bool(boolean) => Ok(boolean.to_string()),
+            Value::Date(date) => Ok(date::format_date(&date)),
+            Value::Duration(duration) => Ok(date::format_duration(duration)),
+            Value::Filesize(size) => Ok(filesize::format_filesize(size)),
             _ => Err(ShellErrorKind::InvalidConversion {
                 from: value.to_type(),
                 to: Type::STRING,
@@ -89,9 +104,13 @@ impl SpannedValue {
         let (value, span) = self.into();
         match value {
             Value::Int(number) => output.push(number.to_string()),
+            Value::BigInt(int) => output.push(int.to_string()),
             Value::Float(number) => output.push(number.to_string()),
             Value::String(string) => output.push(string.to_string()),
             Value::Bool(boolean) => output.push(boolean.to_string()),
+            Value::Date(date) => output.push(date::format_date(&date)),
+            Value::Duration(duration) => output.push(date::format_duration(duration)),
+            Value::Filesize(size) => output.push(filesize::format_filesize(size)),
             Value::Range(range) => {
                 for i in (*range).clone() {
                     output.push(i.to_string());
@@ -114,10 +133,18 @@ impl SpannedValue {
         Ok(())
     }
 
-    pub fn try_add(self, rhs: SpannedValue, binop: Span) -> Result<SpannedValue, ShellErrorKind> {
+    pub fn try_add(
+        self,
+        rhs: SpannedValue,
+        binop: Span,
+        overflow: Overflow,
+    ) -> Result<SpannedValue, ShellErrorKind> {
         let (lhs, lhs_span) = self.into();
         let (rhs, rhs_span) = rhs.into();
         let span = lhs_span + rhs_span;
+        if let Some(value) = big_int_op(&lhs, &rhs, |lhs, rhs| lhs + rhs) {
+            return Ok(value.spanned(span));
+        }
         match lhs {
             Value::Int(number) => match rhs {
                 Value::List(mut list) => {
@@ -125,8 +152,11 @@ impl SpannedValue {
                     Ok(Value::List(list).spanned(span))
                 }
                 Value::Float(rhs) => Ok(Value::Float(number as f64 + rhs).spanned(span)),
+                Value::Filesize(rhs) => Ok(overflow
+                    .unit(number.overflowing_add(rhs), Value::Filesize, "+", binop)?
+                    .spanned(span)),
                 _ => match rhs.try_as_int() {
-                    Some(rhs) => Ok(Value::Int(number.wrapping_add(rhs)).spanned(span)),
+                    Some(rhs) => Ok(overflow.add(number, rhs, binop)?.spanned(span)),
                     None => Err(ShellErrorKind::InvalidBinaryOperand(
                         BinOpKind::Add.spanned(binop),
                         lhs.to_type(),
@@ -141,6 +171,9 @@ impl SpannedValue {
                     Rc::make_mut(&mut list).push(lhs);
                     Ok(Value::List(list).spanned(span))
                 }
+                Value::Filesize(rhs) => Ok(overflow
+                    .float_unit(number + rhs as f64, Value::Filesize, "+", binop)?
+                    .spanned(span)),
                 _ => match rhs.try_as_float() {
                     Some(rhs) => Ok(Value::Float(number + rhs).spanned(span)),
                     None => Err(ShellErrorKind::InvalidBinaryOperand(
@@ -159,7 +192,7 @@ impl SpannedValue {
                 }
                 Value::Float(rhs) => Ok(Value::Float(boolean as u8 as f64 + rhs).spanned(span)),
                 _ => match rhs.try_as_int() {
-                    Some(rhs) => Ok(Value::Int((boolean as i64).wrapping_add(rhs)).spanned(span)),
+                    Some(rhs) => Ok(overflow.add(boolean as i64, rhs, binop)?.spanned(span)),
                     None => Err(ShellErrorKind::InvalidBinaryOperand(
                         BinOpKind::Add.spanned(binop),
                         lhs.to_type(),
@@ -200,6 +233,62 @@ impl SpannedValue {
                 }
                 Ok(Value::List(list).spanned(span))
             }
+            Value::Date(ref date) => match rhs {
+                Value::Duration(rhs) => {
+                    Ok(Value::from(date::add_duration(date, rhs)?).spanned(span))
+                }
+                _ => Err(ShellErrorKind::InvalidBinaryOperand(
+                    BinOpKind::Add.spanned(binop),
+                    lhs.to_type(),
+                    rhs.to_type(),
+                    lhs_span,
+                    rhs_span,
+                )),
+            },
+            Value::Duration(duration) => match rhs {
+                Value::Date(ref rhs) => {
+                    Ok(Value::from(date::add_duration(rhs, duration)?).spanned(span))
+                }
+                Value::Duration(rhs) => Ok(overflow
+                    .unit(duration.overflowing_add(rhs), Value::Duration, "+", binop)?
+                    .spanned(span)),
+                _ => Err(ShellErrorKind::InvalidBinaryOperand(
+                    BinOpKind::Add.spanned(binop),
+                    lhs.to_type(),
+                    rhs.to_type(),
+                    lhs_span,
+                    rhs_span,
+                )),
+            },
+            Value::Filesize(size) => match rhs {
+                Value::Filesize(rhs) => Ok(overflow
+                    .unit(size.overflowing_add(rhs), Value::Filesize, "+", binop)?
+                    .spanned(span)),
+                // plain numbers are a count of bytes, the same as when comparing
+                Value::Int(rhs) => Ok(overflow
+                    .unit(size.overflowing_add(rhs), Value::Filesize, "+", binop)?
+                    .spanned(span)),
+                Value::Float(rhs) => Ok(overflow
+                    .float_unit(size as f64 + rhs, Value::Filesize, "+", binop)?
+                    .spanned(span)),
+                _ => Err(ShellErrorKind::InvalidBinaryOperand(
+                    BinOpKind::Add.spanned(binop),
+                    lhs.to_type(),
+                    rhs.to_type(),
+                    lhs_span,
+                    rhs_span,
+                )),
+            },
+            Value::BigInt(ref int) => match rhs {
+                Value::Float(rhs) => Ok(Value::Float(big_to_float(int) + rhs).spanned(span)),
+                _ => Err(ShellErrorKind::InvalidBinaryOperand(
+                    BinOpKind::Add.spanned(binop),
+                    lhs.to_type(),
+                    rhs.to_type(),
+                    lhs_span,
+                    rhs_span,
+                )),
+            },
             _ => Err(ShellErrorKind::InvalidBinaryOperand(
                 BinOpKind::Add.spanned(binop),
                 lhs.to_type(),
@@ -210,14 +299,25 @@ impl SpannedValue {
         }
     }
 
-    pub fn try_sub(self, rhs: SpannedValue, binop: Span) -> Result<SpannedValue, ShellErrorKind> {
+    pub fn try_sub(
+        self,
+        rhs: SpannedValue,
+        binop: Span,
+        overflow: Overflow,
+    ) -> Result<SpannedValue, ShellErrorKind> {
         let (lhs, lhs_span) = self.into();
         let (rhs, rhs_span) = rhs.into();
         let span = lhs_span + rhs_span;
+        if let Some(value) = big_int_op(&lhs, &rhs, |lhs, rhs| lhs - rhs) {
+            return Ok(value.spanned(span));
+        }
         match lhs {
             Value::Int(number) => match rhs {
-                Value::Int(rhs) => Ok(Value::Int(number.wrapping_sub(rhs)).spanned(span)),
+                Value::Int(rhs) => Ok(overflow.sub(number, rhs, binop)?.spanned(span)),
                 Value::Float(rhs) => Ok(Value::Float(number as f64 - rhs).spanned(span)),
+                Value::Filesize(rhs) => Ok(overflow
+                    .unit(number.overflowing_sub(rhs), Value::Filesize, "-", binop)?
+                    .spanned(span)),
                 _ => Err(ShellErrorKind::InvalidBinaryOperand(
                     BinOpKind::Sub.spanned(binop),
                     lhs.to_type(),
@@ -226,9 +326,25 @@ impl SpannedValue {
                     rhs_span,
                 )),
             },
-            Value::Float(number) => match rhs.try_as_float() {
-                Some(rhs) => Ok(Value::Float(number - rhs).spanned(span)),
-                None => Err(ShellErrorKind::InvalidBinaryOperand(
+            Value::Float(number) => match rhs {
+                Value::Filesize(rhs) => Ok(overflow
+                    .float_unit(number - rhs as f64, Value::Filesize, "-", binop)?
+                    .spanned(span)),
+                _ => match rhs.try_as_float() {
+                    Some(rhs) => Ok(Value::Float(number - rhs).spanned(span)),
+                    None => Err(ShellErrorKind::InvalidBinaryOperand(
+                        BinOpKind::Sub.spanned(binop),
+                        lhs.to_type(),
+                        rhs.to_type(),
+                        lhs_span,
+                        rhs_span,
+                    )),
+                },
+            },
+            Value::Bool(boolean) => match rhs {
+                Value::Int(rhs) => Ok(overflow.sub(boolean as i64, rhs, binop)?.spanned(span)),
+                Value::Float(rhs) => Ok(Value::Float(boolean as u8 as f64 - rhs).spanned(span)),
+                _ => Err(ShellErrorKind::InvalidBinaryOperand(
                     BinOpKind::Sub.spanned(binop),
                     lhs.to_type(),
                     rhs.to_type(),
@@ -236,9 +352,53 @@ impl SpannedValue {
                     rhs_span,
                 )),
             },
-            Value::Bool(boolean) => match rhs {
-                Value::Int(rhs) => Ok(Value::Int((boolean as i64).wrapping_sub(rhs)).spanned(span)),
-                Value::Float(rhs) => Ok(Value::Float(boolean as u8 as f64 - rhs).spanned(span)),
+            Value::Date(ref date) => match rhs {
+                Value::Date(ref rhs) => {
+                    Ok(Value::Duration(date::difference(date, rhs)?).spanned(span))
+                }
+                Value::Duration(rhs) => {
+                    Ok(Value::from(date::sub_duration(date, rhs)?).spanned(span))
+                }
+                _ => Err(ShellErrorKind::InvalidBinaryOperand(
+                    BinOpKind::Sub.spanned(binop),
+                    lhs.to_type(),
+                    rhs.to_type(),
+                    lhs_span,
+                    rhs_span,
+                )),
+            },
+            Value::Duration(duration) => match rhs {
+                Value::Duration(rhs) => Ok(overflow
+                    .unit(duration.overflowing_sub(rhs), Value::Duration, "-", binop)?
+                    .spanned(span)),
+                _ => Err(ShellErrorKind::InvalidBinaryOperand(
+                    BinOpKind::Sub.spanned(binop),
+                    lhs.to_type(),
+                    rhs.to_type(),
+                    lhs_span,
+                    rhs_span,
+                )),
+            },
+            Value::Filesize(size) => match rhs {
+                Value::Filesize(rhs) => Ok(overflow
+                    .unit(size.overflowing_sub(rhs), Value::Filesize, "-", binop)?
+                    .spanned(span)),
+                Value::Int(rhs) => Ok(overflow
+                    .unit(size.overflowing_sub(rhs), Value::Filesize, "-", binop)?
+                    .spanned(span)),
+                Value::Float(rhs) => Ok(overflow
+                    .float_unit(size as f64 - rhs, Value::Filesize, "-", binop)?
+                    .spanned(span)),
+                _ => Err(ShellErrorKind::InvalidBinaryOperand(
+                    BinOpKind::Sub.spanned(binop),
+                    lhs.to_type(),
+                    rhs.to_type(),
+                    lhs_span,
+                    rhs_span,
+                )),
+            },
+            Value::BigInt(ref int) => match rhs {
+                Value::Float(rhs) => Ok(Value::Float(big_to_float(int) - rhs).spanned(span)),
                 _ => Err(ShellErrorKind::InvalidBinaryOperand(
                     BinOpKind::Sub.spanned(binop),
                     lhs.to_type(),
@@ -257,15 +417,29 @@ impl SpannedValue {
         }
     }
 
-    pub fn try_mul(self, rhs: SpannedValue, binop: Span) -> Result<SpannedValue, ShellErrorKind> {
+    pub fn try_mul(
+        self,
+        rhs: SpannedValue,
+        binop: Span,
+        overflow: Overflow,
+    ) -> Result<SpannedValue, ShellErrorKind> {
         let (lhs, lhs_span) = self.into();
         let (rhs, rhs_span) = rhs.into();
         let ty_lhs = lhs.to_type();
         let span = lhs_span + rhs_span;
+        if let Some(value) = big_int_op(&lhs, &rhs, |lhs, rhs| lhs * rhs) {
+            return Ok(value.spanned(span));
+        }
         match lhs {
             Value::Int(number) => match rhs {
-                Value::Int(rhs) => Ok(Value::Int(number.wrapping_mul(rhs)).spanned(span)),
+                Value::Int(rhs) => Ok(overflow.mul(number, rhs, binop)?.spanned(span)),
                 Value::Float(rhs) => Ok(Value::Float(number as f64 * rhs).spanned(span)),
+                Value::Duration(rhs) => Ok(overflow
+                    .unit(number.overflowing_mul(rhs), Value::Duration, "*", binop)?
+                    .spanned(span)),
+                Value::Filesize(rhs) => Ok(overflow
+                    .unit(number.overflowing_mul(rhs), Value::Filesize, "*", binop)?
+                    .spanned(span)),
                 Value::String(string) => {
                     if string.is_empty() {
                         return Ok(Value::String(string).spanned(span));
@@ -296,18 +470,26 @@ impl SpannedValue {
                     rhs_span,
                 )),
             },
-            Value::Float(number) => match rhs.try_as_float() {
-                Some(rhs) => Ok(Value::Float(number * rhs).spanned(span)),
-                None => Err(ShellErrorKind::InvalidBinaryOperand(
-                    BinOpKind::Mul.spanned(binop),
-                    lhs.to_type(),
-                    rhs.to_type(),
-                    lhs_span,
-                    rhs_span,
-                )),
+            Value::Float(number) => match rhs {
+                Value::Duration(rhs) => Ok(overflow
+                    .float_unit(number * rhs as f64, Value::Duration, "*", binop)?
+                    .spanned(span)),
+                Value::Filesize(rhs) => Ok(overflow
+                    .float_unit(number * rhs as f64, Value::Filesize, "*", binop)?
+                    .spanned(span)),
+                _ => match rhs.try_as_float() {
+                    Some(rhs) => Ok(Value::Float(number * rhs).spanned(span)),
+                    None => Err(ShellErrorKind::InvalidBinaryOperand(
+                        BinOpKind::Mul.spanned(binop),
+                        lhs.to_type(),
+                        rhs.to_type(),
+                        lhs_span,
+                        rhs_span,
+                    )),
+                },
             },
             Value::Bool(boolean) => match rhs {
-                Value::Int(rhs) => Ok(Value::Int((boolean as i64).wrapping_mul(rhs)).spanned(span)),
+                Value::Int(rhs) => Ok(overflow.mul(boolean as i64, rhs, binop)?.spanned(span)),
                 Value::Float(rhs) => Ok(Value::Float(boolean as u8 as f64 * rhs).spanned(span)),
                 Value::String(string) => {
                     let mut new = String::new();
@@ -375,6 +557,46 @@ impl SpannedValue {
                 }
                 Ok(Value::from(new).spanned(span))
             }
+            Value::Duration(duration) => match rhs {
+                Value::Int(rhs) => Ok(overflow
+                    .unit(duration.overflowing_mul(rhs), Value::Duration, "*", binop)?
+                    .spanned(span)),
+                Value::Float(rhs) => Ok(overflow
+                    .float_unit(duration as f64 * rhs, Value::Duration, "*", binop)?
+                    .spanned(span)),
+                _ => Err(ShellErrorKind::InvalidBinaryOperand(
+                    BinOpKind::Mul.spanned(binop),
+                    lhs.to_type(),
+                    rhs.to_type(),
+                    lhs_span,
+                    rhs_span,
+                )),
+            },
+            Value::Filesize(size) => match rhs {
+                Value::Int(rhs) => Ok(overflow
+                    .unit(size.overflowing_mul(rhs), Value::Filesize, "*", binop)?
+                    .spanned(span)),
+                Value::Float(rhs) => Ok(overflow
+                    .float_unit(size as f64 * rhs, Value::Filesize, "*", binop)?
+                    .spanned(span)),
+                _ => Err(ShellErrorKind::InvalidBinaryOperand(
+                    BinOpKind::Mul.spanned(binop),
+                    lhs.to_type(),
+                    rhs.to_type(),
+                    lhs_span,
+                    rhs_span,
+                )),
+            },
+            Value::BigInt(ref int) => match rhs {
+                Value::Float(rhs) => Ok(Value::Float(big_to_float(int) * rhs).spanned(span)),
+                _ => Err(ShellErrorKind::InvalidBinaryOperand(
+                    BinOpKind::Mul.spanned(binop),
+                    lhs.to_type(),
+                    rhs.to_type(),
+                    lhs_span,
+                    rhs_span,
+                )),
+            },
             _ => Err(ShellErrorKind::InvalidBinaryOperand(
                 BinOpKind::Mul.spanned(binop),
                 lhs.to_type(),
@@ -385,7 +607,12 @@ impl SpannedValue {
         }
     }
 
-    pub fn try_div(self, rhs: SpannedValue, binop: Span) -> Result<SpannedValue, ShellErrorKind> {
+    pub fn try_div(
+        self,
+        rhs: SpannedValue,
+        binop: Span,
+        overflow: Overflow,
+    ) -> Result<SpannedValue, ShellErrorKind> {
         let (lhs, lhs_span) = self.into();
         let (rhs, rhs_span) = rhs.into();
         let span = lhs_span + rhs_span;
@@ -425,6 +652,50 @@ impl SpannedValue {
                     rhs_span,
                 )),
             },
+            Value::Duration(duration) => match rhs {
+                Value::Duration(rhs) => {
+                    Ok(Value::Float(duration as f64 / rhs as f64).spanned(span))
+                }
+                Value::Int(rhs) => Ok(overflow
+                    .unit(duration.overflowing_div(rhs), Value::Duration, "/", binop)?
+                    .spanned(span)),
+                Value::Float(rhs) => Ok(overflow
+                    .float_unit(duration as f64 / rhs, Value::Duration, "/", binop)?
+                    .spanned(span)),
+                _ => Err(ShellErrorKind::InvalidBinaryOperand(
+                    BinOpKind::Div.spanned(binop),
+                    lhs.to_type(),
+                    rhs.to_type(),
+                    lhs_span,
+                    rhs_span,
+                )),
+            },
+            Value::Filesize(size) => match rhs {
+                Value::Filesize(rhs) => Ok(Value::Float(size as f64 / rhs as f64).spanned(span)),
+                Value::Int(rhs) => Ok(overflow
+                    .unit(size.overflowing_div(rhs), Value::Filesize, "/", binop)?
+                    .spanned(span)),
+                Value::Float(rhs) => Ok(overflow
+                    .float_unit(size as f64 / rhs, Value::Filesize, "/", binop)?
+                    .spanned(span)),
+                _ => Err(ShellErrorKind::InvalidBinaryOperand(
+                    BinOpKind::Div.spanned(binop),
+                    lhs.to_type(),
+                    rhs.to_type(),
+                    lhs_span,
+                    rhs_span,
+                )),
+            },
+            Value::BigInt(ref int) => match rhs.try_as_float() {
+                Some(rhs) => Ok(Value::Float(big_to_float(int) / rhs).spanned(span)),
+                None => Err(ShellErrorKind::InvalidBinaryOperand(
+                    BinOpKind::Div.spanned(binop),
+                    lhs.to_type(),
+                    rhs.to_type(),
+                    lhs_span,
+                    rhs_span,
+                )),
+            },
             _ => Err(ShellErrorKind::InvalidBinaryOperand(
                 BinOpKind::Div.spanned(binop),
                 lhs.to_type(),
@@ -435,21 +706,32 @@ impl SpannedValue {
         }
     }
 
-    pub fn try_expo(self, rhs: SpannedValue, binop: Span) -> Result<SpannedValue, ShellErrorKind> {
+    pub fn try_expo(
+        self,
+        rhs: SpannedValue,
+        binop: Span,
+        overflow: Overflow,
+    ) -> Result<SpannedValue, ShellErrorKind> {
         let (lhs, lhs_span) = self.into();
         let (rhs, rhs_span) = rhs.into();
         let span = lhs_span + rhs_span;
 
         match lhs {
-            Value::Int(number) => match rhs.try_as_float() {
-                Some(rhs) => Ok(Value::Float((number as f64).powf(rhs)).spanned(span)),
-                None => Err(ShellErrorKind::InvalidBinaryOperand(
-                    BinOpKind::Expo.spanned(binop),
-                    lhs.to_type(),
-                    rhs.to_type(),
-                    lhs_span,
-                    rhs_span,
-                )),
+            // ints to a positive int power stay ints, every other power is a float
+            Value::Int(number) => match rhs {
+                Value::Int(exp) if exp >= 0 => {
+                    Ok(overflow.pow(number, exp as u64, binop)?.spanned(span))
+                }
+                _ => match rhs.try_as_float() {
+                    Some(rhs) => Ok(Value::Float((number as f64).powf(rhs)).spanned(span)),
+                    None => Err(ShellErrorKind::InvalidBinaryOperand(
+                        BinOpKind::Expo.spanned(binop),
+                        lhs.to_type(),
+                        rhs.to_type(),
+                        lhs_span,
+                        rhs_span,
+                    )),
+                },
             },
             Value::Float(number) => match rhs.try_as_float() {
                 Some(rhs) => Ok(Value::Float((number).powf(rhs)).spanned(span)),
@@ -471,6 +753,19 @@ impl SpannedValue {
                     rhs_span,
                 )),
             },
+            Value::BigInt(ref int) => match rhs {
+                Value::Int(exp) if exp >= 0 => Ok(big_pow(int, exp as u64, binop)?.spanned(span)),
+                _ => match rhs.try_as_float() {
+                    Some(rhs) => Ok(Value::Float(big_to_float(int).powf(rhs)).spanned(span)),
+                    None => Err(ShellErrorKind::InvalidBinaryOperand(
+                        BinOpKind::Expo.spanned(binop),
+                        lhs.to_type(),
+                        rhs.to_type(),
+                        lhs_span,
+                        rhs_span,
+                    )),
+                },
+            },
             _ => Err(ShellErrorKind::InvalidBinaryOperand(
                 BinOpKind::Expo.spanned(binop),
                 lhs.to_type(),
@@ -490,9 +785,13 @@ impl SpannedValue {
             return Err(ShellErrorKind::DivisionByZero);
         }
 
+        if let Some(value) = big_int_op(&lhs, &rhs, |lhs, rhs| lhs % rhs) {
+            return Ok(value.spanned(span));
+        }
+
         match lhs {
             Value::Int(number) => match rhs {
-                Value::Int(rhs) => Ok(Value::Int(number % rhs).spanned(span)),
+                Value::Int(rhs) => Ok(Value::Int(number.wrapping_rem(rhs)).spanned(span)),
                 Value::Float(rhs) => Ok(Value::Float(number as f64 % rhs).spanned(span)),
                 _ => Err(ShellErrorKind::InvalidBinaryOperand(
                     BinOpKind::Mod.spanned(binop),
@@ -523,6 +822,16 @@ impl SpannedValue {
                     rhs_span,
                 )),
             },
+            Value::BigInt(ref int) => match rhs {
+                Value::Float(rhs) => Ok(Value::Float(big_to_float(int) % rhs).spanned(span)),
+                _ => Err(ShellErrorKind::InvalidBinaryOperand(
+                    BinOpKind::Mod.spanned(binop),
+                    lhs.to_type(),
+                    rhs.to_type(),
+                    lhs_span,
+                    rhs_span,
+                )),
+            },
             _ => Err(ShellErrorKind::InvalidBinaryOperand(
                 BinOpKind::Mod.spanned(binop),
                 lhs.to_type(),
@@ -587,6 +896,25 @@ impl AsRef<Value> for SpannedValue {
     }
 }
 
+// arithmetic with promoted ints is exact, `None` when neither side is one or the other is not an int
+fn big_int_op(
+    lhs: &Value,
+    rhs: &Value,
+    op: impl FnOnce(BigInt, BigInt) -> BigInt,
+) -> Option<Value> {
+    if !matches!(lhs, Value::BigInt(_)) && !matches!(rhs, Value::BigInt(_)) {
+        return None;
+    }
+    Some(Value::from(op(
+        lhs.try_as_big_int()?,
+        rhs.try_as_big_int()?,
+    )))
+}
+
+fn big_to_float(int: &BigInt) -> f64 {
+    int.to_f64().unwrap_or(f64::NAN)
+}
+
 impl From<SpannedValue> for Value {
     fn from(value: SpannedValue) -> Self {
         value.value
@@ -619,6 +947,13 @@ pub enum Value {
     Regex(Rc<(Regex, String)>),
     Binary(Rc<Vec<u8>>),
     Closure(Rc<(Rc<Closure>, Frame)>),
+    Date(Rc<DateTime<FixedOffset>>),
+    // nanoseconds
+    Duration(i64),
+    // bytes
+    Filesize(i64),
+    // only ints that do not fit in an `Int`, from promoted arithmetic or large numbers in loaded data
+    BigInt(Rc<BigInt>),
 }
 
 impl Value {
@@ -629,6 +964,28 @@ impl Value {
     pub fn as_hashable(&self) -> &HashableValue {
         unsafe { mem::transmute::<_, &HashableValue>(self) }
     }
+
+    // positional groups are keyed by their index and named groups by their name
+    // groups that did not participate in the match are null
+    pub fn from_captures(regex: &Regex, string: &str) -> Value {
+        let Some(captures) = regex.captures(string) else {
+            return Value::Null;
+        };
+
+        let group_value = |group: Option<regex::Match>| match group {
+            Some(group) => Value::from(group.as_str()),
+            None => Value::Null,
+        };
+
+        let mut map = IndexMap::new();
+        for (index, group) in captures.iter().enumerate() {
+            map.insert(Rc::from(index.to_string()), group_value(group));
+        }
+        for name in regex.capture_names().flatten() {
+            map.insert(Rc::from(name), group_value(captures.name(name)));
+        }
+        Value::from(map)
+    }
 }
 
 impl fmt::Display for Value {
@@ -779,6 +1136,10 @@ impl fmt::Display for Value {
                 Ok(())
             }
             Self::Closure(_) => write!(f, "{}", self.to_compact_string()),
+            Self::Date(date) => date::format_date(date).magenta().fmt(f),
+            Self::Duration(duration) => date::format_duration(*duration).yellow().fmt(f),
+            Self::Filesize(size) => filesize::format_filesize(*size).yellow().fmt(f),
+            Self::BigInt(int) => int.to_string().yellow().fmt(f),
             Self::Null => Ok(()),
         }
     }
@@ -797,12 +1158,15 @@ impl PartialEq for Value {
                 Value::Float(rhs) => *number as f64 == *rhs,
                 Value::Int(rhs) => number == rhs,
                 Value::Bool(rhs) => *number == *rhs as i64,
+                Value::Filesize(rhs) => number == rhs,
                 _ => false,
             },
             Value::Float(number) => match other {
                 Value::Float(rhs) => *number == *rhs,
                 Value::Int(rhs) => *number == *rhs as f64,
                 Value::Bool(rhs) => *number == *rhs as u8 as f64,
+                Value::Filesize(rhs) => *number == *rhs as f64,
+                Value::BigInt(rhs) => Some(*number) == rhs.to_f64(),
                 _ => false,
             },
             Value::Bool(boolean) => match other {
@@ -818,6 +1182,10 @@ impl PartialEq for Value {
                 Value::Null => false,
                 Value::Regex(_) => false,
                 Value::Closure(_) => false,
+                Value::Date(_) => false,
+                Value::Duration(duration) => (*duration != 0) == *boolean,
+                Value::Filesize(size) => (*size != 0) == *boolean,
+                Value::BigInt(_) => *boolean,
             },
             Value::String(string) => match other {
                 Value::String(rhs) => string == rhs,
@@ -854,6 +1222,28 @@ impl PartialEq for Value {
                 _ => false,
             },
             Value::Closure(_) => false,
+            Value::Date(date) => match other {
+                Value::Date(rhs) => date == rhs,
+                _ => false,
+            },
+            Value::Duration(duration) => match other {
+                Value::Duration(rhs) => duration == rhs,
+                Value::Bool(rhs) => (*duration != 0) == *rhs,
+                _ => false,
+            },
+            Value::Filesize(size) => match other {
+                Value::Filesize(rhs) => size == rhs,
+                Value::Int(rhs) => size == rhs,
+                Value::Float(rhs) => *size as f64 == *rhs,
+                Value::Bool(rhs) => (*size != 0) == *rhs,
+                _ => false,
+            },
+            Value::BigInt(int) => match other {
+                Value::BigInt(rhs) => int == rhs,
+                Value::Float(rhs) => int.to_f64() == Some(*rhs),
+                Value::Bool(rhs) => *rhs,
+                _ => false,
+            },
         }
     }
 }
@@ -865,9 +1255,13 @@ impl Value {
     ) -> Result<(), ShellErrorKind> {
         match self {
             Value::Int(number) => output.push(number.to_string()),
+            Value::BigInt(int) => output.push(int.to_string()),
             Value::Float(number) => output.push(number.to_string()),
             Value::String(string) => output.push(string.to_string()),
             Value::Bool(boolean) => output.push(boolean.to_string()),
+            Value::Date(date) => output.push(date::format_date(&date)),
+            Value::Duration(duration) => output.push(date::format_duration(duration)),
+            Value::Filesize(size) => output.push(filesize::format_filesize(size)),
             Value::Range(range) => {
                 for i in (*range).clone() {
                     output.push(i.to_string());
@@ -910,14 +1304,24 @@ impl Value {
                 humansize::format_size(data.len(), humansize::BINARY.space_after_value(false))
             ),
             Self::Closure(closure) => format!("[closure {:p}]", Rc::as_ptr(closure)),
+            Self::Date(date) => date::format_date(date),
+            Self::Duration(duration) => date::format_duration(*duration),
+            Self::Filesize(size) => filesize::format_filesize(*size),
+            Self::BigInt(int) => int.to_string(),
         }
     }
 
     pub fn compact_string_color(&self) -> comfy_table::Color {
         use comfy_table::Color;
         match self {
-            Self::Int(_) | Self::Float(_) | Self::Bool(_) => Color::Yellow,
+            Self::Int(_)
+            | Self::Float(_)
+            | Self::Bool(_)
+            | Self::Duration(_)
+            | Self::Filesize(_)
+            | Self::BigInt(_) => Color::Yellow,
             Self::Regex(_) => Color::Blue,
+            Self::Date(_) => Color::Magenta,
             Self::List(_)
             | Self::Map(_)
             | Self::Binary(_)
@@ -942,6 +1346,10 @@ impl Value {
             Self::Regex(..) => Type::REGEX,
             Self::Binary(..) => Type::BINARY,
             Self::Closure(..) => Type::CLOSURE,
+            Self::Date(..) => Type::DATE,
+            Self::Duration(..) => Type::DURATION,
+            Self::Filesize(..) => Type::FILESIZE,
+            Self::BigInt(..) => Type::BIGINT,
         }
     }
 
@@ -960,6 +1368,16 @@ impl Value {
             Self::Int(number) => Some(*number as f64),
             Self::Float(number) => Some(*number),
             Self::Bool(boolean) => Some(*boolean as u8 as f64),
+            Self::BigInt(int) => int.to_f64(),
+            _ => None,
+        }
+    }
+
+    pub fn try_as_big_int(&self) -> Option<BigInt> {
+        match self {
+            Self::Int(number) => Some(BigInt::from(*number)),
+            Self::Bool(boolean) => Some(BigInt::from(*boolean as u8)),
+            Self::BigInt(int) => Some((**int).clone()),
             _ => None,
         }
     }
@@ -979,6 +1397,10 @@ impl Value {
             // TODO make this return an error
             Self::Regex(..) => false,
             Self::Closure(..) => false,
+            Self::Date(..) => true,
+            Self::Duration(duration) => *duration != 0,
+            Self::Filesize(size) => *size != 0,
+            Self::BigInt(_) => true,
         }
     }
 
@@ -1054,7 +1476,7 @@ impl Value {
 
     pub fn is_zero(&self) -> bool {
         match self {
-            Value::Bool(false) | Value::Int(0) => true,
+            Value::Bool(false) | Value::Int(0) | Value::Duration(0) | Value::Filesize(0) => true,
             Value::Float(number) if *number == 0.0 => true,
             _ => false,
         }
@@ -1107,6 +1529,16 @@ impl From<i64> for Value {
     }
 }
 
+// ints that fit are always turned back into an `Int`
+impl From<BigInt> for Value {
+    fn from(value: BigInt) -> Self {
+        match value.to_i64() {
+            Some(int) => Value::Int(int),
+            None => Value::BigInt(Rc::new(value)),
+        }
+    }
+}
+
 impl From<f64> for Value {
     fn from(value: f64) -> Self {
         Value::Float(value)
@@ -1125,6 +1557,12 @@ impl From<char> for Value {
     }
 }
 
+impl From<DateTime<FixedOffset>> for Value {
+    fn from(value: DateTime<FixedOffset>) -> Self {
+        Value::Date(Rc::new(value))
+    }
+}
+
 impl From<Vec<u8>> for Value {
     fn from(value: Vec<u8>) -> Self {
         Value::Binary(Rc::new(value))
@@ -0,0 +1,296 @@
+use std::{fmt, rc::Rc};
+
+use indexmap::IndexMap;
+
+use super::{table::Table, SpannedValue, Value};
+use crate::parser::{lexer::token::span::Span, shell_error::ShellErrorKind};
+
+// one step of a cell path like `servers.0.port`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathMember {
+    Key(Rc<str>),
+    Index(i64),
+}
+
+impl PathMember {
+    pub fn parse(member: &str) -> Self {
+        match member.parse::<i64>() {
+            Ok(index) => Self::Index(index),
+            Err(_) => Self::Key(Rc::from(member)),
+        }
+    }
+
+    pub fn from_value(value: SpannedValue) -> Result<Self, ShellErrorKind> {
+        match value.value {
+            Value::Int(index) => Ok(Self::Index(index)),
+            _ => Ok(Self::parse(&value.try_into_string()?)),
+        }
+    }
+
+    // maps are always accessed by key so that `$map.0` finds the key `0`
+    fn key(&self) -> Rc<str> {
+        match self {
+            Self::Key(key) => key.clone(),
+            Self::Index(index) => Rc::from(index.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for PathMember {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Key(key) => write!(f, "{key}"),
+            Self::Index(index) => write!(f, "{index}"),
+        }
+    }
+}
+
+pub fn parse_path(path: &str) -> Vec<PathMember> {
+    if path.is_empty() {
+        return Vec::new();
+    }
+    path.split('.').map(PathMember::parse).collect()
+}
+
+fn resolve_index(index: i64, len: usize, span: Span) -> Result<usize, ShellErrorKind> {
+    Value::Int(index).spanned(span).try_as_index(len)
+}
+
+pub fn get_member(value: &Value, member: &PathMember, span: Span) -> Result<Value, ShellErrorKind> {
+    match (value, member) {
+        (Value::Map(map), member) => match map.get(&*member.key()) {
+            Some(value) => Ok(value.clone()),
+            None => Err(ShellErrorKind::ColumnNotFound(member.to_string())),
+        },
+        (Value::List(list), PathMember::Index(index)) => {
+            Ok(list[resolve_index(*index, list.len(), span)?].clone())
+        }
+        (Value::Table(table), PathMember::Index(index)) => {
+            Ok(Value::from(table.row(Value::Int(*index).spanned(span))?))
+        }
+        (Value::Table(table), PathMember::Key(column)) => Ok(Value::from(table.column(column)?)),
+        (_, PathMember::Key(_)) => Err(ShellErrorKind::NoColumns(value.to_type())),
+        _ => Err(ShellErrorKind::NotIndexable(value.to_type(), span)),
+    }
+}
+
+pub fn get_path(value: &Value, path: &[PathMember], span: Span) -> Result<Value, ShellErrorKind> {
+    let mut current = value.clone();
+    for member in path {
+        current = get_member(&current, member, span)?;
+    }
+    Ok(current)
+}
+
+// with `insert` missing keys are created, lists can also be extended by setting the index after the last item
+pub fn set_path(
+    value: &mut Value,
+    path: &[PathMember],
+    new: Value,
+    insert: bool,
+    span: Span,
+) -> Result<(), ShellErrorKind> {
+    let Some((member, rest)) = path.split_first() else {
+        *value = new;
+        return Ok(());
+    };
+
+    match value {
+        Value::Map(map) => {
+            let map = Rc::make_mut(map);
+            let key = member.key();
+            match map.get_mut(&*key) {
+                Some(child) => set_path(child, rest, new, insert, span),
+                None if insert => {
+                    let mut child = Value::from(IndexMap::new());
+                    set_path(&mut child, rest, new, insert, span)?;
+                    map.insert(key, child);
+                    Ok(())
+                }
+                None => Err(ShellErrorKind::ColumnNotFound(member.to_string())),
+            }
+        }
+        Value::List(list) => {
+            let PathMember::Index(index) = member else {
+                return Err(ShellErrorKind::NoColumns(value.to_type()));
+            };
+            let list = Rc::make_mut(list);
+            if insert && *index == list.len() as i64 {
+                let mut child = Value::from(IndexMap::new());
+                set_path(&mut child, rest, new, insert, span)?;
+                list.push(child);
+                return Ok(());
+            }
+            let index = resolve_index(*index, list.len(), span)?;
+            set_path(&mut list[index], rest, new, insert, span)
+        }
+        Value::Table(table) => {
+            let mut rows = table_rows(table);
+            match member {
+                PathMember::Index(index) => {
+                    let index = resolve_index(*index, rows.len(), span)?;
+                    set_path(&mut rows[index], rest, new, insert, span)?;
+                }
+                // setting a column sets it in every row
+                PathMember::Key(_) => {
+                    if !insert && !table.has_column(&member.key()) {
+                        return Err(ShellErrorKind::ColumnNotFound(member.to_string()));
+                    }
+                    for row in &mut rows {
+                        set_path(row, path, new.clone(), insert, span)?;
+                    }
+                }
+            }
+            *value = rows_to_table(rows);
+            Ok(())
+        }
+        _ => Err(ShellErrorKind::NotIndexable(value.to_type(), span)),
+    }
+}
+
+pub fn delete_path(
+    value: &mut Value,
+    path: &[PathMember],
+    span: Span,
+) -> Result<(), ShellErrorKind> {
+    let Some((member, rest)) = path.split_first() else {
+        return Ok(());
+    };
+
+    if !rest.is_empty() {
+        return match value {
+            Value::Map(map) => match Rc::make_mut(map).get_mut(&*member.key()) {
+                Some(child) => delete_path(child, rest, span),
+                None => Err(ShellErrorKind::ColumnNotFound(member.to_string())),
+            },
+            Value::List(list) => {
+                let PathMember::Index(index) = member else {
+                    return Err(ShellErrorKind::NoColumns(value.to_type()));
+                };
+                let list = Rc::make_mut(list);
+                let index = resolve_index(*index, list.len(), span)?;
+                delete_path(&mut list[index], rest, span)
+            }
+            Value::Table(table) => {
+                let mut rows = table_rows(table);
+                match member {
+                    PathMember::Index(index) => {
+                        let index = resolve_index(*index, rows.len(), span)?;
+                        delete_path(&mut rows[index], rest, span)?;
+                    }
+                    PathMember::Key(_) => {
+                        for row in &mut rows {
+                            delete_path(row, path, span)?;
+                        }
+                    }
+                }
+                *value = rows_to_table(rows);
+                Ok(())
+            }
+            _ => Err(ShellErrorKind::NotIndexable(value.to_type(), span)),
+        };
+    }
+
+    match value {
+        Value::Map(map) => match Rc::make_mut(map).shift_remove(&*member.key()) {
+            Some(_) => Ok(()),
+            None => Err(ShellErrorKind::ColumnNotFound(member.to_string())),
+        },
+        Value::List(list) => {
+            let PathMember::Index(index) = member else {
+                return Err(ShellErrorKind::NoColumns(value.to_type()));
+            };
+            let list = Rc::make_mut(list);
+            let index = resolve_index(*index, list.len(), span)?;
+            list.remove(index);
+            Ok(())
+        }
+        Value::Table(table) => {
+            let mut rows = table_rows(table);
+            match member {
+                PathMember::Index(index) => {
+                    let index = resolve_index(*index, rows.len(), span)?;
+                    rows.remove(index);
+                }
+                PathMember::Key(column) => {
+                    if !table.has_column(column) {
+                        return Err(ShellErrorKind::ColumnNotFound(column.to_string()));
+                    }
+                    for row in &mut rows {
+                        delete_path(row, path, span)?;
+                    }
+                }
+            }
+            *value = rows_to_table(rows);
+            Ok(())
+        }
+        _ => Err(ShellErrorKind::NotIndexable(value.to_type(), span)),
+    }
+}
+
+fn table_rows(table: &Table) -> Vec<Value> {
+    table.iter().map(Value::from).collect()
+}
+
+// rows are rebuilt into a table so that new columns are added to every row
+fn rows_to_table(rows: Vec<Value>) -> Value {
+    let mut table = Table::new();
+    for row in rows {
+        table.insert_map(Rc::unwrap_or_clone(row.unwrap_map()));
+    }
+    Value::from(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> Value {
+        let server = Value::from(IndexMap::from([(Rc::from("port"), Value::Int(80))]));
+        Value::from(IndexMap::from([(
+            Rc::from("servers"),
+            Value::from(vec![server]),
+        )]))
+    }
+
+    #[test]
+    fn cell_path_test() {
+        let span = Span::new(0, 0);
+        let path = parse_path("servers.0.port");
+        assert_eq!(
+            path,
+            vec![
+                PathMember::Key(Rc::from("servers")),
+                PathMember::Index(0),
+                PathMember::Key(Rc::from("port")),
+            ]
+        );
+
+        let original = config();
+        let mut value = original.clone();
+        set_path(&mut value, &path, Value::Int(8080), false, span).unwrap();
+        assert_eq!(get_path(&value, &path, span).unwrap(), Value::Int(8080));
+        // the original value is copied on write and never changed
+        assert_eq!(get_path(&original, &path, span).unwrap(), Value::Int(80));
+
+        assert!(set_path(
+            &mut value,
+            &parse_path("servers.0.host"),
+            Value::Null,
+            false,
+            span
+        )
+        .is_err());
+        set_path(
+            &mut value,
+            &parse_path("servers.0.host"),
+            Value::Null,
+            true,
+            span,
+        )
+        .unwrap();
+
+        delete_path(&mut value, &parse_path("servers.0.port"), span).unwrap();
+        assert!(get_path(&value, &path, span).is_err());
+    }
+}
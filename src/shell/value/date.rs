@@ -0,0 +1,204 @@
+use std::fmt::Write;
+
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, TimeDelta, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use crate::parser::shell_error::ShellErrorKind;
+
+const DISPLAY_FORMAT: &str = "%Y-%m-%d %H:%M:%S %:z";
+
+// formats tried in order when parsing a date without an explicit format
+// dates without an offset are assumed to be in local time
+const NAIVE_DATE_TIME_FORMATS: [&str; 4] = [
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M",
+    "%Y-%m-%dT%H:%M",
+];
+
+pub const DURATION_UNITS: [(&str, u64); 7] = [
+    ("d", 86_400_000_000_000),
+    ("h", 3_600_000_000_000),
+    ("min", 60_000_000_000),
+    ("s", 1_000_000_000),
+    ("ms", 1_000_000),
+    ("us", 1_000),
+    ("ns", 1),
+];
+
+pub fn format_date(date: &DateTime<FixedOffset>) -> String {
+    date.format(DISPLAY_FORMAT).to_string()
+}
+
+pub fn format_date_with(
+    date: &DateTime<FixedOffset>,
+    format: &str,
+) -> Result<String, ShellErrorKind> {
+    // chrono only reports invalid format strings when the date is written
+    let mut output = String::new();
+    write!(output, "{}", date.format(format)).map_err(|_| {
+        ShellErrorKind::Basic("Date Error", format!("Invalid date format `{format}`"))
+    })?;
+    Ok(output)
+}
+
+// durations are displayed with every non zero unit, for example `1h 2min 30s`
+pub fn format_duration(nanos: i64) -> String {
+    if nanos == 0 {
+        return String::from("0ns");
+    }
+
+    let mut parts = Vec::new();
+    let mut rest = nanos.unsigned_abs();
+    for (unit, size) in DURATION_UNITS {
+        let count = rest / size;
+        if count > 0 {
+            parts.push(format!("{count}{unit}"));
+            rest %= size;
+        }
+    }
+
+    let sign = if nanos < 0 { "-" } else { "" };
+    format!("{sign}{}", parts.join(" "))
+}
+
+pub fn add_duration(
+    date: &DateTime<FixedOffset>,
+    nanos: i64,
+) -> Result<DateTime<FixedOffset>, ShellErrorKind> {
+    date.checked_add_signed(TimeDelta::nanoseconds(nanos))
+        .ok_or_else(|| ShellErrorKind::Basic("Date Error", String::from("Date out of range")))
+}
+
+// subtracts directly because the negation of the smallest duration does not fit
+pub fn sub_duration(
+    date: &DateTime<FixedOffset>,
+    nanos: i64,
+) -> Result<DateTime<FixedOffset>, ShellErrorKind> {
+    date.checked_sub_signed(TimeDelta::nanoseconds(nanos))
+        .ok_or_else(|| ShellErrorKind::Basic("Date Error", String::from("Date out of range")))
+}
+
+pub fn difference(
+    lhs: &DateTime<FixedOffset>,
+    rhs: &DateTime<FixedOffset>,
+) -> Result<i64, ShellErrorKind> {
+    lhs.signed_duration_since(rhs)
+        .num_nanoseconds()
+        .ok_or_else(|| ShellErrorKind::Basic("Date Error", String::from("Duration out of range")))
+}
+
+pub fn now(utc: bool) -> DateTime<FixedOffset> {
+    if utc {
+        Utc::now().fixed_offset()
+    } else {
+        Local::now().fixed_offset()
+    }
+}
+
+pub fn parse_date(
+    string: &str,
+    format: Option<&str>,
+) -> Result<DateTime<FixedOffset>, ShellErrorKind> {
+    let string = string.trim();
+    let parsed = match format {
+        Some(format) => DateTime::parse_from_str(string, format).ok().or_else(|| {
+            NaiveDateTime::parse_from_str(string, format)
+                .ok()
+                .or_else(|| {
+                    NaiveDate::parse_from_str(string, format)
+                        .ok()
+                        .and_then(|date| date.and_hms_opt(0, 0, 0))
+                })
+                .and_then(from_local)
+        }),
+        None => DateTime::parse_from_rfc3339(string)
+            .or_else(|_| DateTime::parse_from_rfc2822(string))
+            .ok()
+            .or_else(|| {
+                NAIVE_DATE_TIME_FORMATS
+                    .iter()
+                    .find_map(|format| NaiveDateTime::parse_from_str(string, format).ok())
+                    .or_else(|| {
+                        NaiveDate::parse_from_str(string, "%Y-%m-%d")
+                            .ok()
+                            .and_then(|date| date.and_hms_opt(0, 0, 0))
+                    })
+                    .and_then(from_local)
+            }),
+    };
+
+    parsed.ok_or_else(|| {
+        ShellErrorKind::Basic("Date Error", format!("Cannot parse `{string}` as a date"))
+    })
+}
+
+fn from_local(naive: NaiveDateTime) -> Option<DateTime<FixedOffset>> {
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|date| date.fixed_offset())
+}
+
+// accepts `utc`, `local`, a fixed offset like `+02:00` or a IANA name like `Europe/Stockholm`
+pub fn to_timezone(
+    date: &DateTime<FixedOffset>,
+    zone: &str,
+) -> Result<DateTime<FixedOffset>, ShellErrorKind> {
+    if zone.eq_ignore_ascii_case("utc") || zone.eq_ignore_ascii_case("z") {
+        return Ok(date.with_timezone(&Utc).fixed_offset());
+    }
+
+    if zone.eq_ignore_ascii_case("local") {
+        return Ok(date.with_timezone(&Local).fixed_offset());
+    }
+
+    if let Ok(offset) = zone.parse::<FixedOffset>() {
+        return Ok(date.with_timezone(&offset));
+    }
+
+    match zone.parse::<Tz>() {
+        Ok(tz) => Ok(date.with_timezone(&tz).fixed_offset()),
+        Err(_) => Err(ShellErrorKind::Basic(
+            "Date Error",
+            format!("Unknown timezone `{zone}`"),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duration_format_test() {
+        assert_eq!(format_duration(0), "0ns");
+        assert_eq!(format_duration(1_500_000_000), "1s 500ms");
+        assert_eq!(format_duration(-3_723_000_000_000), "-1h 2min 3s");
+    }
+
+    #[test]
+    fn parse_test() {
+        let date = parse_date("2024-02-29T12:30:00+01:00", None).unwrap();
+        assert_eq!(format_date(&date), "2024-02-29 12:30:00 +01:00");
+        let utc = to_timezone(&date, "utc").unwrap();
+        assert_eq!(format_date(&utc), "2024-02-29 11:30:00 +00:00");
+        let custom = parse_date("29/02/2024 12:30 +0100", Some("%d/%m/%Y %H:%M %z")).unwrap();
+        assert_eq!(custom, date);
+        assert!(parse_date("not a date", None).is_err());
+    }
+
+    #[test]
+    fn sub_duration_test() {
+        let date = parse_date("2024-02-29T12:30:00+01:00", None).unwrap();
+        let later = add_duration(&date, i64::MAX).unwrap();
+        assert_eq!(
+            sub_duration(&date, i64::MIN).unwrap(),
+            add_duration(&later, 1).unwrap()
+        );
+        assert_eq!(
+            sub_duration(&date, 1_000_000_000).unwrap(),
+            add_duration(&date, -1_000_000_000).unwrap()
+        );
+    }
+}
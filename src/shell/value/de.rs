@@ -1,12 +1,14 @@
-use std::fmt;
+use std::{fmt, rc::Rc};
 
+use bigdecimal::num_bigint::BigInt;
 use indexmap::IndexMap;
+use regex::Regex;
 use serde::{
-    de::{MapAccess, SeqAccess, Visitor},
+    de::{DeserializeSeed, Error, MapAccess, SeqAccess, Visitor},
     Deserialize,
 };
 
-use super::Value;
+use super::{table::Table, Value};
 
 impl<'de> Deserialize<'de> for Value {
     #[inline]
@@ -14,166 +16,248 @@ impl<'de> Deserialize<'de> for Value {
     where
         D: serde::Deserializer<'de>,
     {
-        struct ValueVisitor;
+        deserializer.deserialize_any(ValueVisitor { raw: false })
+    }
+}
 
-        impl<'de> Visitor<'de> for ValueVisitor {
-            type Value = Value;
+/// A value deserialized exactly as it is stored.
+/// Arrays are never converted to tables and tagged objects are left as maps.
+pub struct RawValue(pub Value);
 
-            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                formatter.write_str("data that can be represented as a value")
-            }
+impl<'de> Deserialize<'de> for RawValue {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<RawValue, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Ok(RawValue(
+            deserializer.deserialize_any(ValueVisitor { raw: true })?,
+        ))
+    }
+}
 
-            #[inline]
-            fn visit_bool<E>(self, value: bool) -> Result<Value, E> {
-                Ok(Value::Bool(value))
-            }
+#[derive(Clone, Copy)]
+struct ValueVisitor {
+    raw: bool,
+}
 
-            #[inline]
-            fn visit_f64<E>(self, value: f64) -> Result<Value, E> {
-                Ok(Value::Float(value))
-            }
+impl<'de> DeserializeSeed<'de> for ValueVisitor {
+    type Value = Value;
 
-            #[inline]
-            fn visit_f32<E>(self, value: f32) -> Result<Value, E> {
-                Ok(Value::Float(value.into()))
-            }
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
 
-            #[inline]
-            fn visit_i128<E>(self, value: i128) -> Result<Self::Value, E>
-            where
-                E: serde::de::Error,
-            {
-                Ok(Value::Int(
-                    value.try_into().map_err(serde::de::Error::custom)?,
-                ))
-            }
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
 
-            #[inline]
-            fn visit_i64<E>(self, value: i64) -> Result<Value, E> {
-                Ok(Value::Int(value))
-            }
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("data that can be represented as a value")
+    }
 
-            #[inline]
-            fn visit_i32<E>(self, value: i32) -> Result<Self::Value, E> {
-                Ok(Value::Int(value.into()))
-            }
+    #[inline]
+    fn visit_bool<E>(self, value: bool) -> Result<Value, E> {
+        Ok(Value::Bool(value))
+    }
 
-            #[inline]
-            fn visit_i16<E>(self, value: i16) -> Result<Self::Value, E> {
-                Ok(Value::Int(value.into()))
-            }
+    #[inline]
+    fn visit_f64<E>(self, value: f64) -> Result<Value, E> {
+        Ok(Value::Float(value))
+    }
 
-            #[inline]
-            fn visit_i8<E>(self, value: i8) -> Result<Self::Value, E> {
-                Ok(Value::Int(value.into()))
-            }
+    #[inline]
+    fn visit_f32<E>(self, value: f32) -> Result<Value, E> {
+        Ok(Value::Float(value.into()))
+    }
 
-            #[inline]
-            fn visit_u128<E>(self, value: u128) -> Result<Self::Value, E>
-            where
-                E: serde::de::Error,
-            {
-                Ok(Value::Int(
-                    value.try_into().map_err(serde::de::Error::custom)?,
-                ))
-            }
+    #[inline]
+    fn visit_i128<E>(self, value: i128) -> Result<Self::Value, E> {
+        Ok(Value::from(BigInt::from(value)))
+    }
 
-            #[inline]
-            fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
-            where
-                E: serde::de::Error,
-            {
-                Ok(Value::Int(
-                    value.try_into().map_err(serde::de::Error::custom)?,
-                ))
-            }
+    #[inline]
+    fn visit_i64<E>(self, value: i64) -> Result<Value, E> {
+        Ok(Value::Int(value))
+    }
 
-            #[inline]
-            fn visit_u32<E>(self, value: u32) -> Result<Self::Value, E> {
-                Ok(Value::Int(value.into()))
-            }
+    #[inline]
+    fn visit_i32<E>(self, value: i32) -> Result<Self::Value, E> {
+        Ok(Value::Int(value.into()))
+    }
 
-            #[inline]
-            fn visit_u16<E>(self, value: u16) -> Result<Self::Value, E> {
-                Ok(Value::Int(value.into()))
-            }
+    #[inline]
+    fn visit_i16<E>(self, value: i16) -> Result<Self::Value, E> {
+        Ok(Value::Int(value.into()))
+    }
 
-            #[inline]
-            fn visit_u8<E>(self, value: u8) -> Result<Self::Value, E> {
-                Ok(Value::Int(value.into()))
-            }
+    #[inline]
+    fn visit_i8<E>(self, value: i8) -> Result<Self::Value, E> {
+        Ok(Value::Int(value.into()))
+    }
 
-            #[inline]
-            fn visit_char<E>(self, value: char) -> Result<Self::Value, E> {
-                Ok(Value::from(value.to_string()))
-            }
+    #[inline]
+    fn visit_u128<E>(self, value: u128) -> Result<Self::Value, E> {
+        Ok(Value::from(BigInt::from(value)))
+    }
 
-            #[inline]
-            fn visit_str<E>(self, value: &str) -> Result<Value, E>
-            where
-                E: serde::de::Error,
-            {
-                self.visit_string(String::from(value))
-            }
+    #[inline]
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E> {
+        Ok(Value::from(BigInt::from(value)))
+    }
 
-            #[inline]
-            fn visit_string<E>(self, value: String) -> Result<Value, E> {
-                Ok(Value::from(value))
-            }
+    #[inline]
+    fn visit_u32<E>(self, value: u32) -> Result<Self::Value, E> {
+        Ok(Value::Int(value.into()))
+    }
 
-            #[inline]
-            fn visit_none<E>(self) -> Result<Value, E> {
-                Ok(Value::Null)
-            }
+    #[inline]
+    fn visit_u16<E>(self, value: u16) -> Result<Self::Value, E> {
+        Ok(Value::Int(value.into()))
+    }
 
-            #[inline]
-            fn visit_some<D>(self, deserializer: D) -> Result<Value, D::Error>
-            where
-                D: serde::Deserializer<'de>,
-            {
-                Deserialize::deserialize(deserializer)
-            }
+    #[inline]
+    fn visit_u8<E>(self, value: u8) -> Result<Self::Value, E> {
+        Ok(Value::Int(value.into()))
+    }
 
-            #[inline]
-            fn visit_unit<E>(self) -> Result<Value, E> {
-                Ok(Value::Null)
-            }
+    #[inline]
+    fn visit_char<E>(self, value: char) -> Result<Self::Value, E> {
+        Ok(Value::from(value.to_string()))
+    }
 
-            #[inline]
-            fn visit_seq<V>(self, mut visitor: V) -> Result<Value, V::Error>
-            where
-                V: SeqAccess<'de>,
-            {
-                let mut vec: Vec<Value> = Vec::new();
-                while let Some(elem) = visitor.next_element()? {
-                    vec.push(elem);
-                }
+    #[inline]
+    fn visit_str<E>(self, value: &str) -> Result<Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.visit_string(String::from(value))
+    }
 
-                Ok(Value::from(vec))
-            }
+    #[inline]
+    fn visit_string<E>(self, value: String) -> Result<Value, E> {
+        Ok(Value::from(value))
+    }
 
-            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
-            where
-                E: serde::de::Error,
-            {
-                Ok(Value::from(v.to_vec()))
-            }
+    #[inline]
+    fn visit_none<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
 
-            #[inline]
-            fn visit_map<V>(self, mut visitor: V) -> Result<Value, V::Error>
-            where
-                V: MapAccess<'de>,
-            {
-                let mut values = IndexMap::new();
-                while let Some((key, value)) = visitor.next_entry::<String, _>()? {
-                    values.insert(key.into(), value);
-                }
+    #[inline]
+    fn visit_some<D>(self, deserializer: D) -> Result<Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        DeserializeSeed::deserialize(self, deserializer)
+    }
 
-                Ok(Value::from(values))
-            }
+    #[inline]
+    fn visit_unit<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    #[inline]
+    fn visit_seq<V>(self, mut visitor: V) -> Result<Value, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let mut vec: Vec<Value> = Vec::new();
+        while let Some(elem) = visitor.next_element_seed(self)? {
+            vec.push(elem);
+        }
+
+        if self.raw {
+            Ok(Value::from(vec))
+        } else {
+            Ok(list_or_table(vec))
+        }
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(Value::from(v.to_vec()))
+    }
+
+    #[inline]
+    fn visit_map<V>(self, mut visitor: V) -> Result<Value, V::Error>
+    where
+        V: MapAccess<'de>,
+    {
+        let mut values = IndexMap::new();
+        while let Some(key) = visitor.next_key::<String>()? {
+            let value = visitor.next_value_seed(self)?;
+            values.insert(key.into(), value);
+        }
+
+        if self.raw {
+            Ok(Value::from(values))
+        } else {
+            from_tagged_object(values)
         }
+    }
+}
+
+// arrays where every item is a map with the same keys are loaded as tables
+fn list_or_table(list: Vec<Value>) -> Value {
+    let Some(Value::Map(first)) = list.first() else {
+        return Value::from(list);
+    };
 
-        deserializer.deserialize_any(ValueVisitor)
+    let uniform = list.iter().all(|value| match value {
+        Value::Map(map) => map.len() == first.len() && map.keys().all(|k| first.contains_key(k)),
+        _ => false,
+    });
+
+    if !uniform {
+        return Value::from(list);
+    }
+
+    let mut table = Table::new();
+    for value in list {
+        table.insert_map(Rc::unwrap_or_clone(value.unwrap_map()));
+    }
+    Value::from(table)
+}
+
+// ranges, regexes, durations, filesizes and bigints are stored as objects with a `$type` tag
+fn from_tagged_object<E: Error>(map: IndexMap<Rc<str>, Value>) -> Result<Value, E> {
+    match (map.get("$type"), map.len()) {
+        (Some(Value::String(tag)), 3) if tag.as_str() == "range" => {
+            match (map.get("start"), map.get("end")) {
+                (Some(Value::Int(start)), Some(Value::Int(end))) => Ok(Value::from(*start..*end)),
+                _ => Ok(Value::from(map)),
+            }
+        }
+        (Some(Value::String(tag)), 2) if tag.as_str() == "regex" => match map.get("pattern") {
+            Some(Value::String(pattern)) => {
+                let regex = Regex::new(pattern).map_err(E::custom)?;
+                Ok(Value::Regex(Rc::new((regex, pattern.to_string()))))
+            }
+            _ => Ok(Value::from(map)),
+        },
+        (Some(Value::String(tag)), 2) if tag.as_str() == "duration" => match map.get("nanos") {
+            Some(Value::Int(nanos)) => Ok(Value::Duration(*nanos)),
+            _ => Ok(Value::from(map)),
+        },
+        (Some(Value::String(tag)), 2) if tag.as_str() == "filesize" => match map.get("bytes") {
+            Some(Value::Int(bytes)) => Ok(Value::Filesize(*bytes)),
+            _ => Ok(Value::from(map)),
+        },
+        (Some(Value::String(tag)), 2) if tag.as_str() == "bigint" => match map.get("digits") {
+            Some(Value::String(digits)) => match digits.parse::<BigInt>() {
+                Ok(int) => Ok(Value::from(int)),
+                Err(_) => Ok(Value::from(map)),
+            },
+            _ => Ok(Value::from(map)),
+        },
+        _ => Ok(Value::from(map)),
     }
 }
 
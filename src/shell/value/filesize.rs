@@ -0,0 +1,36 @@
+use humansize::{format_size, DECIMAL};
+
+pub const FILESIZE_UNITS: [(&str, u64); 11] = [
+    ("b", 1),
+    ("kb", 1_000),
+    ("mb", 1_000_000),
+    ("gb", 1_000_000_000),
+    ("tb", 1_000_000_000_000),
+    ("pb", 1_000_000_000_000_000),
+    ("kib", 1 << 10),
+    ("mib", 1 << 20),
+    ("gib", 1 << 30),
+    ("tib", 1 << 40),
+    ("pib", 1 << 50),
+];
+
+// sizes are written without a space so that they can be pasted back as literals
+// decimal units are used because the short units like `kb` are decimal as well
+pub fn format_filesize(bytes: i64) -> String {
+    let sign = if bytes < 0 { "-" } else { "" };
+    let size = format_size(bytes.unsigned_abs(), DECIMAL.space_after_value(false));
+    format!("{sign}{size}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filesize_format_test() {
+        assert_eq!(format_filesize(0), "0B");
+        assert_eq!(format_filesize(1500), "1.50kB");
+        assert_eq!(format_filesize(-1000), "-1kB");
+        assert_eq!(format_filesize(1024), "1.02kB");
+    }
+}
@@ -41,6 +41,10 @@ impl Hash for HashableValue {
                 string.hash(state);
             }
             Value::Binary(binary) => binary.hash(state),
+            Value::Date(date) => date.hash(state),
+            Value::Duration(duration) => duration.hash(state),
+            Value::Filesize(size) => size.hash(state),
+            Value::BigInt(int) => int.hash(state),
             _ => (),
         }
         state.finish();
@@ -98,6 +102,22 @@ impl PartialEq for HashableValue {
                 Value::Closure(rhs) => ptr::eq(Rc::as_ptr(closure), Rc::as_ptr(rhs)),
                 _ => false,
             },
+            Value::Date(date) => match other {
+                Value::Date(rhs) => date == rhs,
+                _ => false,
+            },
+            Value::Duration(duration) => match other {
+                Value::Duration(rhs) => duration == rhs,
+                _ => false,
+            },
+            Value::Filesize(size) => match other {
+                Value::Filesize(rhs) => size == rhs,
+                _ => false,
+            },
+            Value::BigInt(int) => match other {
+                Value::BigInt(rhs) => int == rhs,
+                _ => false,
+            },
         }
     }
 }
@@ -0,0 +1,99 @@
+use std::rc::Rc;
+
+use indexmap::IndexMap;
+use ini::Ini;
+
+use super::Value;
+use crate::parser::shell_error::ShellErrorKind;
+
+// ini files have no types so every value is read as a string
+// keys outside of any section are placed at the top level of the map
+// and every section becomes a nested map
+pub fn from_ini(src: &str) -> Result<Value, ShellErrorKind> {
+    let ini = Ini::load_from_str(src)?;
+    let mut map: IndexMap<Rc<str>, Value> = IndexMap::new();
+    for (section, properties) in ini.iter() {
+        let mut entries = IndexMap::new();
+        for (key, value) in properties.iter() {
+            entries.insert(Rc::from(key), Value::from(value));
+        }
+
+        match section {
+            Some(section) => {
+                map.insert(Rc::from(section), Value::from(entries));
+            }
+            None => map.extend(entries),
+        }
+    }
+    Ok(Value::from(map))
+}
+
+pub fn to_ini(value: &Value) -> Result<String, ShellErrorKind> {
+    let Value::Map(map) = value else {
+        return Err(ShellErrorKind::Basic(
+            "Serialization Error",
+            format!(
+                "Cannot serialize {} to ini, expected a `map`",
+                value.to_type()
+            ),
+        ));
+    };
+
+    let mut ini = Ini::new();
+    for (key, value) in map.iter() {
+        match value {
+            Value::Map(section) => {
+                let mut setter = ini.with_section(Some(&**key));
+                for (key, value) in section.iter() {
+                    setter.set(&**key, ini_string(value)?);
+                }
+            }
+            _ => {
+                ini.with_general_section().set(&**key, ini_string(value)?);
+            }
+        }
+    }
+
+    let mut output = Vec::new();
+    ini.write_to(&mut output)
+        .map_err(|e| ShellErrorKind::Io(None, e))?;
+    String::from_utf8(output).map_err(|e| {
+        ShellErrorKind::Basic(
+            "Serialization Error",
+            format!("Ini output is not valid utf-8: {e}"),
+        )
+    })
+}
+
+fn ini_string(value: &Value) -> Result<String, ShellErrorKind> {
+    match value {
+        Value::Null => Ok(String::new()),
+        Value::Int(int) => Ok(int.to_string()),
+        Value::Float(float) => Ok(float.to_string()),
+        Value::Bool(boolean) => Ok(boolean.to_string()),
+        Value::String(string) => Ok(string.to_string()),
+        _ => Err(ShellErrorKind::Basic(
+            "Serialization Error",
+            format!("Cannot serialize {} as an ini value", value.to_type()),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ini_test() {
+        let src = "name = crust\n\n[server]\nhost = localhost\nport = 8080\n";
+        let value = from_ini(src).unwrap();
+        let map = value.clone().unwrap_map();
+        assert_eq!(map.get("name"), Some(&Value::from("crust")));
+        let server = map.get("server").unwrap().clone().unwrap_map();
+        assert_eq!(
+            server.keys().map(|k| &**k).collect::<Vec<_>>(),
+            ["host", "port"]
+        );
+        assert_eq!(from_ini(&to_ini(&value).unwrap()).unwrap(), value);
+    }
+}
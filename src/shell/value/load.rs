@@ -0,0 +1,70 @@
+use std::path::Path;
+
+use serde::Deserialize;
+
+use super::{de::RawValue, ini, tagged::from_tagged, Value};
+use crate::{
+    parser::shell_error::ShellErrorKind,
+    shell::builtins::functions::{read_file, read_file_raw},
+};
+
+pub fn load_value(path: impl AsRef<Path>, tagged: bool) -> Result<Value, ShellErrorKind> {
+    let path = path.as_ref();
+    let ext = path.extension();
+    if let Some(ext) = ext {
+        let ext = ext.to_string_lossy().to_string();
+        match ext.as_str() {
+            "txt" => Ok(read_file(path)?.into()),
+            _ if is_format(&ext) && tagged => deserialize_tagged(&ext, &read_file(path)?),
+            _ if is_format(&ext) => deserialize(&ext, &read_file(path)?),
+            _ => Err(ShellErrorKind::UnknownFileType(ext)),
+        }
+    } else {
+        let file = read_file_raw(path)?;
+        match String::from_utf8(file) {
+            Ok(string) => Ok(string.into()),
+            Err(e) => Ok(e.into_bytes().into()),
+        }
+    }
+}
+
+pub fn is_format(format: &str) -> bool {
+    matches!(format, "json" | "toml" | "yaml" | "yml" | "ini")
+}
+
+pub fn deserialize(format: &str, src: &str) -> Result<Value, ShellErrorKind> {
+    match format {
+        "json" => Ok(serde_json::from_str(src)?),
+        "toml" => Ok(toml::from_str(src)?),
+        "yaml" | "yml" => {
+            // a stream with multiple documents is loaded as a list of documents
+            let mut documents = Vec::new();
+            for document in serde_yaml::Deserializer::from_str(src) {
+                documents.push(Value::deserialize(document)?);
+            }
+            match documents.len() {
+                0 => Ok(Value::Null),
+                1 => Ok(documents.pop().unwrap()),
+                _ => Ok(Value::from(documents)),
+            }
+        }
+        "ini" => ini::from_ini(src),
+        _ => Err(ShellErrorKind::UnknownFormat(format.to_string())),
+    }
+}
+
+pub fn deserialize_tagged(format: &str, src: &str) -> Result<Value, ShellErrorKind> {
+    let raw: RawValue = match format {
+        "json" => serde_json::from_str(src)?,
+        "toml" => toml::from_str(src)?,
+        "yaml" | "yml" => serde_yaml::from_str(src)?,
+        "ini" => {
+            return Err(ShellErrorKind::Basic(
+                "Deserialization Error",
+                String::from("The tagged format is not supported for ini"),
+            ))
+        }
+        _ => return Err(ShellErrorKind::UnknownFormat(format.to_string())),
+    };
+    from_tagged(raw.0)
+}
@@ -0,0 +1,243 @@
+use std::rc::Rc;
+
+use bigdecimal::num_bigint::BigInt;
+
+use super::Value;
+use crate::parser::{lexer::token::span::Span, shell_error::ShellErrorKind};
+
+// promoted results larger than this are an error instead of using up all memory
+const MAX_PROMOTED_BITS: u64 = 1 << 20;
+
+// what happens when the result of integer arithmetic does not fit in an int
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    #[default]
+    Wrap,
+    Error,
+    Promote,
+}
+
+impl Overflow {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "wrap" => Some(Self::Wrap),
+            "error" => Some(Self::Error),
+            "promote" => Some(Self::Promote),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Wrap => "wrap",
+            Self::Error => "error",
+            Self::Promote => "promote",
+        }
+    }
+
+    // only called once an operation has overflowed, `exact` is the result computed without limits
+    pub fn overflowed(
+        self,
+        wrapped: i64,
+        exact: BigInt,
+        op: &str,
+        span: Option<Span>,
+    ) -> Result<Value, ShellErrorKind> {
+        match self {
+            Self::Wrap => Ok(Value::Int(wrapped)),
+            Self::Error => Err(ShellErrorKind::ArithmeticOverflow(op.to_string(), span)),
+            Self::Promote => Ok(Value::BigInt(Rc::new(exact))),
+        }
+    }
+
+    pub fn add(self, lhs: i64, rhs: i64, span: Span) -> Result<Value, ShellErrorKind> {
+        match lhs.overflowing_add(rhs) {
+            (result, false) => Ok(Value::Int(result)),
+            (wrapped, true) => self.overflowed(
+                wrapped,
+                BigInt::from(lhs) + BigInt::from(rhs),
+                "+",
+                Some(span),
+            ),
+        }
+    }
+
+    pub fn sub(self, lhs: i64, rhs: i64, span: Span) -> Result<Value, ShellErrorKind> {
+        match lhs.overflowing_sub(rhs) {
+            (result, false) => Ok(Value::Int(result)),
+            (wrapped, true) => self.overflowed(
+                wrapped,
+                BigInt::from(lhs) - BigInt::from(rhs),
+                "-",
+                Some(span),
+            ),
+        }
+    }
+
+    pub fn mul(self, lhs: i64, rhs: i64, span: Span) -> Result<Value, ShellErrorKind> {
+        match lhs.overflowing_mul(rhs) {
+            (result, false) => Ok(Value::Int(result)),
+            (wrapped, true) => self.overflowed(
+                wrapped,
+                BigInt::from(lhs) * BigInt::from(rhs),
+                "*",
+                Some(span),
+            ),
+        }
+    }
+
+    pub fn neg(self, int: i64, span: Span) -> Result<Value, ShellErrorKind> {
+        match int.overflowing_neg() {
+            (result, false) => Ok(Value::Int(result)),
+            (wrapped, true) => self.overflowed(wrapped, -BigInt::from(int), "-", Some(span)),
+        }
+    }
+
+    pub fn pow(self, base: i64, exp: u64, span: Span) -> Result<Value, ShellErrorKind> {
+        // only 0, 1 and -1 have powers that fit with exponents that do not fit in a u32
+        let checked = match base {
+            -1..=1 => Some(wrapping_pow(base, exp)),
+            _ => u32::try_from(exp)
+                .ok()
+                .and_then(|exp| base.checked_pow(exp)),
+        };
+        if let Some(result) = checked {
+            return Ok(Value::Int(result));
+        }
+        match self {
+            Self::Wrap => Ok(Value::Int(wrapping_pow(base, exp))),
+            Self::Error => Err(ShellErrorKind::ArithmeticOverflow(
+                String::from("**"),
+                Some(span),
+            )),
+            Self::Promote => big_pow(&BigInt::from(base), exp, span),
+        }
+    }
+
+    // units can not be promoted so promote is an error like error
+    pub fn unit(
+        self,
+        (result, overflowed): (i64, bool),
+        unit: fn(i64) -> Value,
+        op: &str,
+        span: Span,
+    ) -> Result<Value, ShellErrorKind> {
+        match (overflowed, self) {
+            (false, _) | (true, Self::Wrap) => Ok(unit(result)),
+            (true, _) => Err(ShellErrorKind::ArithmeticOverflow(
+                op.to_string(),
+                Some(span),
+            )),
+        }
+    }
+
+    // for units multiplied or divided by a float, the cast saturates in wrap mode
+    pub fn float_unit(
+        self,
+        float: f64,
+        unit: fn(i64) -> Value,
+        op: &str,
+        span: Span,
+    ) -> Result<Value, ShellErrorKind> {
+        // i64::MAX as f64 rounds up to 2^63 which is already out of range
+        let fits = float.is_finite() && float >= i64::MIN as f64 && float < i64::MAX as f64;
+        self.unit((float as i64, !fits), unit, op, span)
+    }
+}
+
+// powers of ints that do not fit are exact when promoted, the base is never 0, 1 or -1
+pub fn big_pow(base: &BigInt, exp: u64, span: Span) -> Result<Value, ShellErrorKind> {
+    if base.bits().saturating_mul(exp) > MAX_PROMOTED_BITS {
+        return Err(ShellErrorKind::ArithmeticOverflow(
+            String::from("**"),
+            Some(span),
+        ));
+    }
+    Ok(Value::from(base.pow(exp as u32)))
+}
+
+fn wrapping_pow(mut base: i64, mut exp: u64) -> i64 {
+    let mut result: i64 = 1;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result.wrapping_mul(base);
+        }
+        base = base.wrapping_mul(base);
+        exp >>= 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overflow_mode_test() {
+        let span = Span::new(0, 1);
+        assert!(matches!(
+            Overflow::Wrap.add(i64::MAX, 1, span),
+            Ok(Value::Int(i64::MIN))
+        ));
+        assert!(matches!(
+            Overflow::Error.mul(i64::MAX, 2, span),
+            Err(ShellErrorKind::ArithmeticOverflow(..))
+        ));
+        assert!(matches!(
+            Overflow::Promote.sub(i64::MIN, 1, span),
+            Ok(Value::BigInt(int)) if *int == BigInt::from(i64::MIN) - 1
+        ));
+        assert!(matches!(Overflow::Error.add(1, 2, span), Ok(Value::Int(3))));
+    }
+
+    #[test]
+    fn pow_test() {
+        let span = Span::new(0, 1);
+        assert!(matches!(
+            Overflow::Error.pow(3, 4, span),
+            Ok(Value::Int(81))
+        ));
+        assert!(matches!(Overflow::Wrap.pow(2, 64, span), Ok(Value::Int(0))));
+        assert!(matches!(
+            Overflow::Wrap.pow(3, 1 << 40, span),
+            Ok(Value::Int(int)) if int == wrapping_pow(3, 1 << 40)
+        ));
+        assert!(matches!(
+            Overflow::Error.pow(2, 63, span),
+            Err(ShellErrorKind::ArithmeticOverflow(..))
+        ));
+        assert!(matches!(
+            Overflow::Promote.pow(2, 100, span),
+            Ok(Value::BigInt(int)) if *int == BigInt::from(1) << 100
+        ));
+        assert!(matches!(
+            Overflow::Error.pow(-1, u64::MAX, span),
+            Ok(Value::Int(-1))
+        ));
+        assert!(matches!(
+            Overflow::Promote.pow(2, u64::MAX, span),
+            Err(ShellErrorKind::ArithmeticOverflow(..))
+        ));
+    }
+
+    #[test]
+    fn unit_test() {
+        let span = Span::new(0, 1);
+        assert!(matches!(
+            Overflow::Wrap.unit(i64::MAX.overflowing_add(1), Value::Duration, "+", span),
+            Ok(Value::Duration(i64::MIN))
+        ));
+        assert!(matches!(
+            Overflow::Promote.unit(i64::MAX.overflowing_add(1), Value::Filesize, "+", span),
+            Err(ShellErrorKind::ArithmeticOverflow(..))
+        ));
+        assert!(matches!(
+            Overflow::Error.float_unit(1.5e19, Value::Duration, "*", span),
+            Err(ShellErrorKind::ArithmeticOverflow(..))
+        ));
+        assert!(matches!(
+            Overflow::Error.float_unit(2.5, Value::Duration, "*", span),
+            Ok(Value::Duration(2))
+        ));
+    }
+}
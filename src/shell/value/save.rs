@@ -1,6 +1,6 @@
 use std::{borrow::Cow, path::Path};
 
-use super::Value;
+use super::{ini, load::is_format, tagged::to_tagged, Value};
 use crate::{
     parser::shell_error::ShellErrorKind,
     shell::{builtins::functions::save_file, stream::ValueStream},
@@ -11,46 +11,36 @@ pub fn save_value(
     input: ValueStream,
     append: bool,
     pretty: bool,
+    tagged: bool,
 ) -> Result<(), ShellErrorKind> {
     let path = path.as_ref();
     let ext = path.extension();
     if let Some(ext) = ext {
         let ext = ext.to_string_lossy().to_string();
-        match ext.as_str() {
-            "json" => {
-                let data = if pretty {
-                    serde_json::to_string_pretty(&input.unpack())?
-                } else {
-                    serde_json::to_string(&input.unpack())?
-                };
-                save_file(path, data.as_bytes(), append)
-            }
-            "toml" => {
-                let data = if pretty {
-                    toml::to_string_pretty(&input.unpack())?
-                } else {
-                    toml::to_string(&input.unpack())?
-                };
-                save_file(path, data.as_bytes(), append)
-            }
-            _ => {
-                // TODO use try_expand_to_strings
-                let input = input.unpack();
-                let data: Cow<str> = match &input {
-                    Value::Int(int) => int.to_string().into(),
-                    Value::Float(float) => float.to_string().into(),
-                    Value::Bool(boolean) => boolean.to_string().into(),
-                    Value::String(string) => string.as_str().into(),
-                    Value::Null => String::new().into(),
-                    _ => {
-                        return Err(ShellErrorKind::Basic(
-                            "TypeError",
-                            format!("Cannot cast a {} to a `string`", input.to_type()),
-                        ))
-                    }
-                };
-                save_file(path, data.as_bytes(), append)
-            }
+        if is_format(&ext) {
+            let data = if tagged {
+                serialize_tagged(&ext, &input.unpack(), pretty)?
+            } else {
+                serialize(&ext, &input.unpack(), pretty)?
+            };
+            save_file(path, data.as_bytes(), append)
+        } else {
+            // TODO use try_expand_to_strings
+            let input = input.unpack();
+            let data: Cow<str> = match &input {
+                Value::Int(int) => int.to_string().into(),
+                Value::Float(float) => float.to_string().into(),
+                Value::Bool(boolean) => boolean.to_string().into(),
+                Value::String(string) => string.as_str().into(),
+                Value::Null => String::new().into(),
+                _ => {
+                    return Err(ShellErrorKind::Basic(
+                        "TypeError",
+                        format!("Cannot cast a {} to a `string`", input.to_type()),
+                    ))
+                }
+            };
+            save_file(path, data.as_bytes(), append)
         }
     } else {
         Err(ShellErrorKind::Basic(
@@ -59,3 +49,39 @@ pub fn save_value(
         ))
     }
 }
+
+pub fn serialize(format: &str, value: &Value, pretty: bool) -> Result<String, ShellErrorKind> {
+    match format {
+        "json" => {
+            if pretty {
+                Ok(serde_json::to_string_pretty(value)?)
+            } else {
+                Ok(serde_json::to_string(value)?)
+            }
+        }
+        "toml" => {
+            if pretty {
+                Ok(toml::to_string_pretty(value)?)
+            } else {
+                Ok(toml::to_string(value)?)
+            }
+        }
+        "yaml" | "yml" => Ok(serde_yaml::to_string(value)?),
+        "ini" => ini::to_ini(value),
+        _ => Err(ShellErrorKind::UnknownFormat(format.to_string())),
+    }
+}
+
+pub fn serialize_tagged(
+    format: &str,
+    value: &Value,
+    pretty: bool,
+) -> Result<String, ShellErrorKind> {
+    if format == "ini" {
+        return Err(ShellErrorKind::Basic(
+            "Serialization Error",
+            String::from("The tagged format is not supported for ini"),
+        ));
+    }
+    serialize(format, &to_tagged(value)?, pretty)
+}
@@ -6,6 +6,23 @@ use serde::{
 
 use super::Value;
 
+// Values are serialized with the following mapping:
+//
+// null, int, float, bool and string -> the matching primitive
+// list -> array
+// map -> object, keeping the key order
+// table -> array of objects with the same keys
+// range -> {"$type": "range", "start": int, "end": int}
+// regex -> {"$type": "regex", "pattern": string}
+// binary -> array of bytes
+// date -> RFC 3339 string
+// duration -> {"$type": "duration", "nanos": int}
+// filesize -> {"$type": "filesize", "bytes": int}
+// bigint -> {"$type": "bigint", "digits": string}
+// closure -> error
+//
+// Deserializing reverses this, arrays of objects that all have the same keys become tables.
+// Use the tagged format in `tagged.rs` when every variant needs to round-trip exactly.
 impl Serialize for Value {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -35,16 +52,39 @@ impl Serialize for Value {
                 seq.end()
             }
             Value::Range(range) => {
-                let mut seq = serializer.serialize_seq(None)?;
-                let range = (**range).clone();
-                for i in range {
-                    seq.serialize_element(&i)?;
-                }
-                seq.end()
+                let mut ser_map = serializer.serialize_map(Some(3))?;
+                ser_map.serialize_entry("$type", "range")?;
+                ser_map.serialize_entry("start", &range.start)?;
+                ser_map.serialize_entry("end", &range.end)?;
+                ser_map.end()
+            }
+            Value::Regex(regex) => {
+                let mut ser_map = serializer.serialize_map(Some(2))?;
+                ser_map.serialize_entry("$type", "regex")?;
+                ser_map.serialize_entry("pattern", &regex.1)?;
+                ser_map.end()
             }
-            Value::Regex(regex) => regex.1.serialize(serializer),
             Value::Binary(binary) => binary.serialize(serializer),
             Value::Closure(..) => Err(Error::custom("closure cannot be serialized")),
+            Value::Date(date) => date.to_rfc3339().serialize(serializer),
+            Value::Duration(duration) => {
+                let mut ser_map = serializer.serialize_map(Some(2))?;
+                ser_map.serialize_entry("$type", "duration")?;
+                ser_map.serialize_entry("nanos", duration)?;
+                ser_map.end()
+            }
+            Value::Filesize(size) => {
+                let mut ser_map = serializer.serialize_map(Some(2))?;
+                ser_map.serialize_entry("$type", "filesize")?;
+                ser_map.serialize_entry("bytes", size)?;
+                ser_map.end()
+            }
+            Value::BigInt(int) => {
+                let mut ser_map = serializer.serialize_map(Some(2))?;
+                ser_map.serialize_entry("$type", "bigint")?;
+                ser_map.serialize_entry("digits", &int.to_string())?;
+                ser_map.end()
+            }
         }
     }
 }
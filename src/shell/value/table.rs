@@ -45,6 +45,11 @@ impl Table {
         Self::default()
     }
 
+    pub fn from_parts(headers: Vec<Rc<str>>, rows: Vec<Vec<Value>>) -> Self {
+        debug_assert!(rows.iter().all(|row| row.len() == headers.len()));
+        Self { headers, rows }
+    }
+
     pub fn is_empty(&self) -> bool {
         self.rows.is_empty()
     }
@@ -103,6 +108,22 @@ impl Table {
         self.headers.iter().any(|h| &**h == name)
     }
 
+    pub fn map_column(
+        &mut self,
+        name: &str,
+        mut f: impl FnMut(Value) -> Result<Value, ShellErrorKind>,
+    ) -> Result<(), ShellErrorKind> {
+        let index = match self.headers.iter().position(|h| &**h == name) {
+            Some(index) => index,
+            None => return Err(ShellErrorKind::ColumnNotFound(name.to_string())),
+        };
+        for row in &mut self.rows {
+            let value = mem::replace(&mut row[index], Value::Null);
+            row[index] = f(value)?;
+        }
+        Ok(())
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = IndexMap<Rc<str>, Value>> + '_ {
         self.rows.iter().map(|row| {
             self.headers
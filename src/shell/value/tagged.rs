@@ -0,0 +1,295 @@
+use std::rc::Rc;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use bigdecimal::num_bigint::BigInt;
+use chrono::DateTime;
+use indexmap::IndexMap;
+use regex::Regex;
+
+use super::{table::Table, Value};
+use crate::parser::shell_error::ShellErrorKind;
+
+// The tagged format wraps every value in an object with a `$type` tag.
+// Every variant except closures survive a round-trip through it exactly.
+//
+// {"$type": "null"}
+// {"$type": "int", "value": 1}
+// {"$type": "float", "value": 1.5} (nan and infinity are stored as strings)
+// {"$type": "bool", "value": true}
+// {"$type": "string", "value": "abc"}
+// {"$type": "list", "value": [...]}
+// {"$type": "map", "value": {...}}
+// {"$type": "table", "headers": [...], "rows": [[...], ...]}
+// {"$type": "range", "start": 0, "end": 10}
+// {"$type": "regex", "pattern": "a+"}
+// {"$type": "binary", "value": "base64 encoded data"}
+// {"$type": "date", "value": "RFC 3339 string"}
+// {"$type": "duration", "value": nanoseconds}
+// {"$type": "filesize", "value": bytes}
+// {"$type": "bigint", "value": "decimal digits"}
+
+pub fn to_tagged(value: &Value) -> Result<Value, ShellErrorKind> {
+    let tagged = match value {
+        Value::Null => tag("null", []),
+        Value::Int(int) => tag("int", [("value", Value::Int(*int))]),
+        Value::Float(float) if float.is_finite() => tag("float", [("value", Value::Float(*float))]),
+        Value::Float(float) => tag("float", [("value", Value::from(float.to_string()))]),
+        Value::Bool(boolean) => tag("bool", [("value", Value::Bool(*boolean))]),
+        Value::String(string) => tag("string", [("value", Value::String(string.clone()))]),
+        Value::List(list) => {
+            let mut values = Vec::with_capacity(list.len());
+            for value in list.iter() {
+                values.push(to_tagged(value)?);
+            }
+            tag("list", [("value", Value::from(values))])
+        }
+        Value::Map(map) => {
+            let mut values = IndexMap::with_capacity(map.len());
+            for (k, v) in map.iter() {
+                values.insert(k.clone(), to_tagged(v)?);
+            }
+            tag("map", [("value", Value::from(values))])
+        }
+        Value::Table(table) => {
+            let headers = table
+                .headers()
+                .iter()
+                .map(|header| Value::from(&**header))
+                .collect::<Vec<_>>();
+            let mut rows = Vec::with_capacity(table.len());
+            for row in table.rows() {
+                let mut values = Vec::with_capacity(row.len());
+                for value in row {
+                    values.push(to_tagged(value)?);
+                }
+                rows.push(Value::from(values));
+            }
+            tag(
+                "table",
+                [
+                    ("headers", Value::from(headers)),
+                    ("rows", Value::from(rows)),
+                ],
+            )
+        }
+        Value::Range(range) => tag(
+            "range",
+            [
+                ("start", Value::Int(range.start)),
+                ("end", Value::Int(range.end)),
+            ],
+        ),
+        Value::Regex(regex) => tag("regex", [("pattern", Value::from(regex.1.as_str()))]),
+        Value::Binary(data) => tag("binary", [("value", Value::from(STANDARD.encode(&**data)))]),
+        Value::Date(date) => tag("date", [("value", Value::from(date.to_rfc3339()))]),
+        Value::Duration(duration) => tag("duration", [("value", Value::Int(*duration))]),
+        Value::Filesize(size) => tag("filesize", [("value", Value::Int(*size))]),
+        Value::BigInt(int) => tag("bigint", [("value", Value::from(int.to_string()))]),
+        Value::Closure(..) => {
+            return Err(ShellErrorKind::Basic(
+                "Serialization Error",
+                String::from("closure cannot be serialized"),
+            ))
+        }
+    };
+    Ok(tagged)
+}
+
+fn tag<const N: usize>(name: &str, fields: [(&str, Value); N]) -> Value {
+    let mut map = IndexMap::with_capacity(N + 1);
+    map.insert(Rc::from("$type"), Value::from(name));
+    for (k, v) in fields {
+        map.insert(Rc::from(k), v);
+    }
+    Value::from(map)
+}
+
+pub fn from_tagged(value: Value) -> Result<Value, ShellErrorKind> {
+    let Value::Map(map) = value else {
+        return Err(invalid(format!(
+            "expected a tagged `map` found {}",
+            value.to_type()
+        )));
+    };
+
+    let Some(Value::String(tag)) = map.get("$type") else {
+        return Err(invalid(String::from("missing `$type` tag")));
+    };
+
+    let value = match tag.as_str() {
+        "null" => Value::Null,
+        "int" => match field(&map, "value")? {
+            Value::Int(int) => Value::Int(*int),
+            value => return Err(wrong_field("value", value)),
+        },
+        "float" => match field(&map, "value")? {
+            Value::Float(float) => Value::Float(*float),
+            Value::Int(int) => Value::Float(*int as f64),
+            Value::String(string) => Value::Float(string.parse()?),
+            value => return Err(wrong_field("value", value)),
+        },
+        "bool" => match field(&map, "value")? {
+            Value::Bool(boolean) => Value::Bool(*boolean),
+            value => return Err(wrong_field("value", value)),
+        },
+        "string" => match field(&map, "value")? {
+            Value::String(string) => Value::String(string.clone()),
+            value => return Err(wrong_field("value", value)),
+        },
+        "list" => match field(&map, "value")? {
+            Value::List(list) => {
+                let mut values = Vec::with_capacity(list.len());
+                for value in list.iter() {
+                    values.push(from_tagged(value.clone())?);
+                }
+                Value::from(values)
+            }
+            value => return Err(wrong_field("value", value)),
+        },
+        "map" => match field(&map, "value")? {
+            Value::Map(entries) => {
+                let mut values = IndexMap::with_capacity(entries.len());
+                for (k, v) in entries.iter() {
+                    values.insert(k.clone(), from_tagged(v.clone())?);
+                }
+                Value::from(values)
+            }
+            value => return Err(wrong_field("value", value)),
+        },
+        "table" => {
+            let headers = match field(&map, "headers")? {
+                Value::List(headers) => {
+                    let mut output: Vec<Rc<str>> = Vec::with_capacity(headers.len());
+                    for header in headers.iter() {
+                        match header {
+                            Value::String(header) => output.push(Rc::from(header.as_str())),
+                            value => return Err(wrong_field("headers", value)),
+                        }
+                    }
+                    output
+                }
+                value => return Err(wrong_field("headers", value)),
+            };
+
+            let rows = match field(&map, "rows")? {
+                Value::List(rows) => {
+                    let mut output = Vec::with_capacity(rows.len());
+                    for row in rows.iter() {
+                        match row {
+                            Value::List(row) if row.len() == headers.len() => {
+                                let mut values = Vec::with_capacity(row.len());
+                                for value in row.iter() {
+                                    values.push(from_tagged(value.clone())?);
+                                }
+                                output.push(values);
+                            }
+                            value => return Err(wrong_field("rows", value)),
+                        }
+                    }
+                    output
+                }
+                value => return Err(wrong_field("rows", value)),
+            };
+
+            Value::from(Table::from_parts(headers, rows))
+        }
+        "range" => match (field(&map, "start")?, field(&map, "end")?) {
+            (Value::Int(start), Value::Int(end)) => Value::from(*start..*end),
+            (Value::Int(_), value) => return Err(wrong_field("end", value)),
+            (value, _) => return Err(wrong_field("start", value)),
+        },
+        "regex" => match field(&map, "pattern")? {
+            Value::String(pattern) => {
+                let regex = Regex::new(pattern)
+                    .map_err(|e| invalid(format!("invalid regex pattern: {e}")))?;
+                Value::Regex(Rc::new((regex, pattern.to_string())))
+            }
+            value => return Err(wrong_field("pattern", value)),
+        },
+        "binary" => match field(&map, "value")? {
+            Value::String(data) => Value::from(
+                STANDARD
+                    .decode(data.as_bytes())
+                    .map_err(|e| invalid(format!("invalid base64 data: {e}")))?,
+            ),
+            value => return Err(wrong_field("value", value)),
+        },
+        "date" => match field(&map, "value")? {
+            Value::String(date) => Value::from(
+                DateTime::parse_from_rfc3339(date)
+                    .map_err(|e| invalid(format!("invalid date: {e}")))?,
+            ),
+            value => return Err(wrong_field("value", value)),
+        },
+        "duration" => match field(&map, "value")? {
+            Value::Int(nanos) => Value::Duration(*nanos),
+            value => return Err(wrong_field("value", value)),
+        },
+        "filesize" => match field(&map, "value")? {
+            Value::Int(bytes) => Value::Filesize(*bytes),
+            value => return Err(wrong_field("value", value)),
+        },
+        "bigint" => match field(&map, "value")? {
+            Value::String(digits) => Value::from(
+                digits
+                    .parse::<BigInt>()
+                    .map_err(|e| invalid(format!("invalid bigint: {e}")))?,
+            ),
+            value => return Err(wrong_field("value", value)),
+        },
+        tag => return Err(invalid(format!("unknown type `{tag}`"))),
+    };
+    Ok(value)
+}
+
+fn field<'a>(map: &'a IndexMap<Rc<str>, Value>, name: &str) -> Result<&'a Value, ShellErrorKind> {
+    map.get(name)
+        .ok_or_else(|| invalid(format!("missing field `{name}`")))
+}
+
+fn wrong_field(name: &str, value: &Value) -> ShellErrorKind {
+    invalid(format!(
+        "field `{name}` has the wrong type {}",
+        value.to_type()
+    ))
+}
+
+fn invalid(message: String) -> ShellErrorKind {
+    ShellErrorKind::Basic(
+        "Deserialization Error",
+        format!("Invalid tagged value, {message}"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shell::value::de::RawValue;
+
+    #[test]
+    fn round_trip_test() {
+        let mut table = Table::new();
+        table.insert_map(IndexMap::from([(Rc::from("a"), Value::Int(1))]));
+        table.insert_map(IndexMap::from([(
+            Rc::from("b"),
+            Value::Float(f64::INFINITY),
+        )]));
+
+        let value = Value::from(vec![
+            Value::Null,
+            Value::Float(1.0),
+            Value::from(table),
+            Value::from(0..10),
+            Value::from(vec![0u8, 159, 146, 150]),
+            Value::from(DateTime::parse_from_rfc3339("2024-02-29T12:30:00.5+01:00").unwrap()),
+            Value::Duration(-1_500),
+            Value::Filesize(1 << 30),
+            Value::from(BigInt::from(i64::MAX) * 1000),
+            Value::from(IndexMap::from([(Rc::from("z"), Value::from("last"))])),
+        ]);
+
+        let json = serde_json::to_string(&to_tagged(&value).unwrap()).unwrap();
+        let raw: RawValue = serde_json::from_str(&json).unwrap();
+        assert_eq!(from_tagged(raw.0).unwrap(), value);
+    }
+}
@@ -16,8 +16,12 @@ bitflags::bitflags! {
         const REGEX =       1 << 9;
         const BINARY =      1 << 10;
         const CLOSURE =     1 << 11;
+        const DATE =        1 << 12;
+        const DURATION =    1 << 13;
+        const FILESIZE =    1 << 14;
+        const BIGINT =      1 << 15;
 
-        const ANY = Self::NULL.bits() | Self::INT.bits() | Self::FLOAT.bits() | Self::BOOL.bits() | Self::STRING.bits() | Self::LIST.bits() | Self::MAP.bits() | Self::TABLE.bits() | Self::RANGE.bits() | Self::RANGE.bits() | Self::BINARY.bits();
+        const ANY = Self::NULL.bits() | Self::INT.bits() | Self::FLOAT.bits() | Self::BOOL.bits() | Self::STRING.bits() | Self::LIST.bits() | Self::MAP.bits() | Self::TABLE.bits() | Self::RANGE.bits() | Self::RANGE.bits() | Self::BINARY.bits() | Self::DATE.bits() | Self::DURATION.bits() | Self::FILESIZE.bits() | Self::BIGINT.bits();
     }
 }
 
@@ -103,9 +107,41 @@ impl fmt::Display for Type {
             if is_first {
                 write!(f, " or ")?;
             }
+            is_first = true;
             write!(f, "`closure`")?;
         }
 
+        if self.intersects(Self::DATE) {
+            if is_first {
+                write!(f, " or ")?;
+            }
+            is_first = true;
+            write!(f, "`date`")?;
+        }
+
+        if self.intersects(Self::DURATION) {
+            if is_first {
+                write!(f, " or ")?;
+            }
+            is_first = true;
+            write!(f, "`duration`")?;
+        }
+
+        if self.intersects(Self::FILESIZE) {
+            if is_first {
+                write!(f, " or ")?;
+            }
+            is_first = true;
+            write!(f, "`filesize`")?;
+        }
+
+        if self.intersects(Self::BIGINT) {
+            if is_first {
+                write!(f, " or ")?;
+            }
+            write!(f, "`bigint`")?;
+        }
+
         Ok(())
     }
 }
@@ -1,13 +1,22 @@
 #[cfg(test)]
 mod tests {
-    use std::{fs, sync::Arc};
+    use std::{
+        fs,
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::Arc,
+        thread,
+    };
 
     use miette::NamedSource;
 
     use crate::{
         parser::{lexer::Lexer, Parser},
         shell::{
+            dir_history::{frecency::Frecency, DirHistory},
+            hooks::HookEvent,
             stream::{OutputStream, ValueStream},
+            value::Value,
             Shell,
         },
     };
@@ -25,11 +34,17 @@ mod tests {
 
     #[test]
     fn language_test() {
+        // the tests directory was visited so that `z` has somewhere to jump to
+        let visited = std::env::temp_dir().join(format!("crust-dirs-{}.jsonl", std::process::id()));
+        let tests_dir = fs::canonicalize("tests").unwrap();
+        Frecency::new(visited.clone()).visit(&tests_dir).unwrap();
+
         for _ in 0..10 {
             for entry in glob::glob("tests/*.crust").unwrap() {
                 let path = entry.unwrap();
                 let file = fs::read_to_string(&path).unwrap();
                 let mut shell = Shell::new(Vec::new());
+                shell.dir_history = DirHistory::new(visited.clone());
                 shell.run_src(
                     path.to_str().unwrap().into(),
                     file,
@@ -39,6 +54,218 @@ mod tests {
                 assert_eq!(0, shell.status());
             }
         }
+        fs::remove_file(visited).unwrap();
+    }
+
+    // answers every request on a background thread, `/echo` returns the request as json
+    fn spawn_http_server() -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut parts = request_line.split_whitespace();
+                let method = parts.next().unwrap().to_string();
+                let path = parts.next().unwrap().to_string();
+
+                let mut headers = serde_json::Map::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let Some((name, value)) = line.trim_end().split_once(':') else {
+                        break;
+                    };
+                    let name = name.to_ascii_lowercase();
+                    if name == "content-length" {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                    headers.insert(name, value.trim().into());
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+
+                let (status, content_type, body) = match path.as_str() {
+                    "/echo" => (
+                        "200 OK",
+                        "application/json",
+                        serde_json::json!({
+                            "method": method,
+                            "headers": headers,
+                            "body": String::from_utf8(body).unwrap(),
+                        })
+                        .to_string()
+                        .into_bytes(),
+                    ),
+                    "/text" => ("200 OK", "text/plain; charset=utf-8", b"hello".to_vec()),
+                    "/bytes" => ("200 OK", "application/octet-stream", vec![0, 1, 2]),
+                    _ => ("404 Not Found", "text/plain", b"not found".to_vec()),
+                };
+                write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                )
+                .unwrap();
+                stream.write_all(&body).unwrap();
+            }
+        });
+        port
+    }
+
+    #[test]
+    fn http_test() {
+        let port = spawn_http_server();
+        let src = format!(
+            r#"
+let url = "http://127.0.0.1:{port}"
+
+let res = (http get "$url/echo" -H "X-Token: secret")
+assert ($res.status == 200)
+assert ($res.body.method == GET)
+assert ($res.body.headers.x-token == secret)
+assert ($res.headers.content-type == "application/json")
+
+let res = (@{{name: crust, tags: [a, b]}} | http post "$url/echo" --json)
+assert ($res.body.method == POST)
+assert ($res.body.headers.content-type == "application/json")
+assert (($res.body.body | from json) == @{{name: crust, tags: [a, b]}})
+
+let res = (@{{name: crust, tags: [a, b]}} | http put "$url/echo" --form)
+assert ($res.body.method == PUT)
+assert ($res.body.body == "name=crust&tags=a&tags=b")
+
+let res = ("plain" | http patch "$url/echo")
+assert ($res.body.method == PATCH)
+assert ($res.body.body == plain)
+
+assert ((http delete "$url/echo").body.method == DELETE)
+assert ((http get "$url/text").body == hello)
+assert ((http get "$url/bytes").body == (http get "$url/bytes" --raw).body)
+assert ((http get "$url/text" --raw).body != hello)
+
+let res = (http get "$url/missing")
+assert ($res.status == 404)
+assert ($res.body == "not found")
+"#
+        );
+        let mut shell = Shell::new(Vec::new());
+        shell.run_src(
+            "http test".into(),
+            src,
+            &mut OutputStream::new_capture(),
+            ValueStream::new(),
+        );
+        assert_eq!(0, shell.status());
+    }
+
+    #[test]
+    fn hook_test() {
+        let mut shell = Shell::new(Vec::new());
+        shell.run_src(
+            "hook test".into(),
+            r#"
+let calls = []
+hook add pre_exec {|line| $calls = ($calls | append $line)}
+hook add post_exec {|status, took| $calls = ($calls | append $status)}
+hook add on_cd {|old, new| $calls = ($calls | append cd)}
+hook add post_exec {|status, took| assert false}
+# changing the directory in an on_cd hook does not run the hooks again
+hook add on_cd {|old, new| cd .}
+assert ((hook list | len) == 5)
+"#
+            .into(),
+            &mut OutputStream::new_capture(),
+            ValueStream::new(),
+        );
+        assert_eq!(0, shell.status());
+
+        shell.run_hooks(HookEvent::PreExec, &[Value::from("ls")]);
+        // a failing hook is reported and does not change the status
+        shell.run_hooks(HookEvent::PostExec, &[Value::Int(3), Value::Duration(10)]);
+        shell.run_hooks(HookEvent::PrePrompt, &[]);
+        assert_eq!(0, shell.status());
+
+        shell.run_src(
+            "hook test".into(),
+            r#"
+assert ($calls == [ls 3])
+cd .
+assert ($calls == [ls 3 cd])
+hook clear post_exec
+assert ((hook list | len) == 3)
+hook clear
+assert ((hook list | len) == 0)
+let failed = false
+try { hook add never {||} } catch { $failed = true }
+assert $failed
+"#
+            .into(),
+            &mut OutputStream::new_capture(),
+            ValueStream::new(),
+        );
+        assert_eq!(0, shell.status());
+    }
+
+    #[test]
+    fn complete_test() {
+        let fish = std::env::temp_dir().join(format!("crust-complete-{}.fish", std::process::id()));
+        fs::write(
+            &fish,
+            "complete -c kubectl -f -a 'apply\\t\"Apply a configuration\" get'\n\
+             complete -c kubectl -s n -l namespace -d 'Namespace'\n",
+        )
+        .unwrap();
+
+        let src = format!(
+            r#"
+complete git {{|line, pos| [@{{value: add, description: "Add files"}} @{{value: commit, description: "Record changes"}}]}}
+complete cargo {{|line, pos| if ($line =~ "cargo b") {{ [build bench] }} else {{ [run] }}}}
+complete --fish "{}"
+assert ((complete | len) == 3)
+complete --erase cargo
+assert ((complete | len) == 2)
+"#,
+            fish.display()
+        );
+        let mut shell = Shell::new(Vec::new());
+        shell.run_src(
+            "complete test".into(),
+            src,
+            &mut OutputStream::new_capture(),
+            ValueStream::new(),
+        );
+        fs::remove_file(&fish).unwrap();
+        assert_eq!(0, shell.status());
+
+        let git = shell.complete_external("git", &[], "a", "git a").unwrap();
+        assert_eq!(
+            git.candidates,
+            vec![(String::from("add"), String::from("Add files"))]
+        );
+        assert!(shell
+            .complete_external("cargo", &[], "", "cargo ")
+            .is_none());
+
+        let kubectl = shell
+            .complete_external("kubectl", &[], "", "kubectl ")
+            .unwrap();
+        assert!(!kubectl.files);
+        assert_eq!(
+            kubectl.candidates[0],
+            (String::from("apply"), String::from("Apply a configuration"))
+        );
+        assert_eq!(kubectl.candidates.len(), 2);
+        let kubectl = shell
+            .complete_external("kubectl", &[], "--n", "kubectl --n")
+            .unwrap();
+        assert_eq!(
+            kubectl.candidates,
+            vec![(String::from("--namespace"), String::from("Namespace"))]
+        );
     }
 
     fn random_ascii_string(len: usize) -> String {